@@ -0,0 +1,236 @@
+use dotenv::dotenv;
+use std::env;
+use std::io::{
+    self,
+    BufRead,
+};
+use std::sync::Arc;
+
+use chrono::Utc;
+use mogcord::db::migration::Migrator;
+use mogcord::db::MongolDB;
+use mogcord::handlers::logic;
+use mogcord::handlers::logic::user::CreateUserRequest;
+use mogcord::model::config::Config;
+use mogcord::model::metrics::Metrics;
+use mogcord::model::user::{
+    Flag,
+    User,
+};
+use mogcord::model::AppState;
+
+const USAGE: &str = "usage: mogcord-admin <command>
+
+  user create <username> <email> [flag]   password is read from stdin
+  user get <user id|email>
+  user flag <user id|email> <flag>        none, unverified, disabled, banned, deleted, admin or owner
+  user revoke-tokens <user id|email>
+  server get <server id>
+  migrate [status|up|down] [version]";
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+#[tokio::main]
+async fn main()
+{
+    //the default `Debug` output would escape the usage text
+    if let Err(err) = run().await
+    {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run() -> CliResult
+{
+    dotenv().ok();
+
+    let mut config = Config::load()?;
+
+    config.telemetry.init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    //the other commands go through `AppState`, which would migrate on its own
+    if let ["migrate", rest @ ..] = args.as_slice()
+    {
+        return migrate(&config, rest).await;
+    }
+    if !matches!(
+        args.first(),
+        Some(&("user" | "server"))
+    )
+    {
+        return Err(USAGE.into());
+    }
+    config.migrate_on_start = false;
+
+    let state = AppState::new(config).await;
+
+    match args.as_slice()
+    {
+        ["user", "create", username, email, flag @ ..] =>
+        {
+            let flag = match flag
+            {
+                [] => Flag::None,
+                [flag] => internal_parse_flag(flag)?,
+                _ => return Err(USAGE.into()),
+            };
+
+            let payload = CreateUserRequest::new(
+                (*username).to_string(),
+                (*email).to_string(),
+                internal_read_password()?,
+            );
+
+            let user =
+                logic::user::create_user_with_flag(&state, &payload, flag)
+                    .await?;
+
+            internal_print_user(&user);
+        },
+        ["user", "get", user] =>
+        {
+            internal_print_user(&internal_get_user(&state, user).await?);
+        },
+        ["user", "flag", user, flag] =>
+        {
+            let user = internal_get_user(&state, user).await?;
+
+            let user = logic::user::update_user_flag(
+                &state,
+                &user.id,
+                internal_parse_flag(flag)?,
+            )
+            .await?;
+
+            internal_print_user(&user);
+        },
+        ["user", "revoke-tokens", user] =>
+        {
+            let user = internal_get_user(&state, user).await?;
+
+            state.refresh_tokens.revoke_all_tokens(&user.id).await?;
+
+            println!(
+                "revoked every session of {}",
+                user.id
+            );
+        },
+        ["server", "get", server_id] =>
+        {
+            let server = state.servers.get_server_by_id(server_id).await?;
+
+            println!("id:           {}", server.id);
+            println!(
+                "name:         {}",
+                server.name
+            );
+            println!(
+                "owner:        {} ({})",
+                server.owner.username, server.owner.id
+            );
+            println!(
+                "discoverable: {}",
+                server.discoverable
+            );
+            println!(
+                "users:        {}",
+                server.users.len()
+            );
+            for channel in server.channels.values()
+            {
+                println!(
+                    "channel:      {} ({})",
+                    channel.name.as_deref().unwrap_or("<unnamed>"),
+                    channel.id
+                );
+            }
+        },
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+async fn migrate(
+    config: &Config,
+    args: &[&str],
+) -> CliResult
+{
+    let db = MongolDB::init(
+        &config.mongoldb_connection,
+        Arc::new(Metrics::new()?),
+    )
+    .await?;
+
+    let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+
+    for line in Migrator::new(db.database()).run_command(&args).await?
+    {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// ids never contain an `@`, emails always do.
+async fn internal_get_user(
+    state: &Arc<AppState>,
+    user: &str,
+) -> Result<User, Box<dyn std::error::Error>>
+{
+    let user = if user.contains('@')
+    {
+        state.users.get_user_by_mail(user).await?
+    }
+    else
+    {
+        state.users.get_user_by_id(user).await?
+    };
+
+    Ok(user)
+}
+
+/// banned and deleted take a date, leaving it out means now.
+fn internal_parse_flag(flag: &str) -> Result<Flag, Box<dyn std::error::Error>>
+{
+    let flag = match flag.trim().to_lowercase().as_str()
+    {
+        "banned" => Flag::Banned {
+            date: Utc::now(),
+        },
+        "deleted" => Flag::Deleted {
+            date: Utc::now(),
+        },
+        _ => flag.parse()?,
+    };
+
+    Ok(flag)
+}
+
+//not an argument, those end up in the shell history and process list
+fn internal_read_password() -> Result<String, Box<dyn std::error::Error>>
+{
+    let mut password = String::new();
+    io::stdin().lock().read_line(&mut password)?;
+
+    let password = password.trim_end_matches(['\r', '\n']).to_string();
+
+    if password.is_empty()
+    {
+        return Err("expected the password on stdin".into());
+    }
+
+    Ok(password)
+}
+
+fn internal_print_user(user: &User)
+{
+    println!("id:       {}", user.id);
+    println!("username: {}", user.username);
+    println!("email:    {}", user.email);
+    println!("flag:     {}", user.flag);
+}
@@ -1,37 +1,50 @@
 mod bucket;
 mod channel;
 mod channel_parent;
+mod external_identity;
+mod health;
 pub mod helper;
 mod log;
 pub mod macros;
+mod mail_token;
 mod message;
+pub mod migration;
+mod presence;
 mod refresh_token;
 mod relation;
+mod two_factor;
 mod user;
+mod webhook;
 
 pub use bucket::*;
 pub use channel::*;
 pub use channel_parent::*;
+pub use external_identity::*;
 pub use log::*;
+pub use mail_token::*;
 pub use message::*;
+pub use presence::*;
 pub use refresh_token::*;
 pub use relation::*;
+pub use two_factor::*;
 pub use user::*;
+pub use webhook::*;
 
-use bson::doc;
-use mongodb::error::Error;
+use mongodb::event::EventHandler;
 use mongodb::options::{
     ClientOptions,
     Compressor,
-    IndexOptions,
 };
 use mongodb::{
     Client,
     Collection,
-    IndexModel,
+    Database,
 };
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::model::metrics::Metrics;
+
 #[derive(Clone, Debug)]
 pub struct MongolDB
 {
@@ -45,12 +58,23 @@ pub struct MongolDB
     refreshtokens: Collection<MongolRefreshToken>,
     relations: Collection<MongolRelation>,
     logs: Collection<MongolLog>,
+    presences: Collection<MongolPresence>,
+    typings: Collection<MongolTyping>,
+    webhooks: Collection<MongolWebhook>,
+    webhook_deliveries: Collection<MongolWebhookDelivery>,
+    incoming_webhooks: Collection<MongolIncomingWebhook>,
+    two_factors: Collection<MongolTwoFactor>,
+    mail_tokens: Collection<MongolMailToken>,
+    external_identities: Collection<MongolExternalIdentity>,
 }
 
 impl MongolDB
 {
+    pub const DATABASE: &'static str = "db_mogcord";
+
     pub async fn init(
-        connection_string: &str
+        connection_string: &str,
+        metrics: Arc<Metrics>,
     ) -> Result<Self, Box<dyn std::error::Error>>
     {
         let mut client_options =
@@ -68,39 +92,41 @@ impl MongolDB
             },
         ]);
 
+        //times every command, without touching the repositories
+        client_options.command_event_handler = Some(EventHandler::callback(
+            move |event| {
+                metrics.observe_mongo_command(&event);
+            },
+        ));
+
         let client = Client::with_options(client_options)?;
-        let db = client.database("db_mogcord");
+        let db = client.database(Self::DATABASE);
 
-        println!("MongolDB connection...");
+        tracing::info!("connected to mongo");
 
         let users: Collection<MongolUser> = db.collection("users");
-        Self::internal_add_user_indexes(&users).await?;
-
         let chats: Collection<MongolChat> = db.collection("chats");
-        Self::internal_add_chat_indexes(&chats).await?;
-
         let servers: Collection<MongolServer> = db.collection("servers");
-        Self::internal_add_server_indexes(&servers).await?;
-
         let channels: Collection<MongolChannel> = db.collection("channels");
-
         let buckets: Collection<MongolBucket> = db.collection("buckets");
-        Self::internal_add_bucket_indexes(&buckets).await?;
-
         let messages: Collection<MongolMessage> = db.collection("messages");
-        Self::internal_add_message_indexes(&messages).await?;
-
         let refreshtokens: Collection<MongolRefreshToken> =
             db.collection("refresh_tokens");
-        Self::internal_add_refresh_token_indexes(&refreshtokens).await?;
-
         let relations: Collection<MongolRelation> = db.collection("relations");
-        Self::internal_add_relation_indexes(&relations).await?;
-
         let logs: Collection<MongolLog> = db.collection("logs");
-        Self::internal_add_log_indexes(&logs).await?;
-
-        println!("Mongol indexes set...");
+        let presences: Collection<MongolPresence> = db.collection("presences");
+        let typings: Collection<MongolTyping> = db.collection("typings");
+        let webhooks: Collection<MongolWebhook> = db.collection("webhooks");
+        let webhook_deliveries: Collection<MongolWebhookDelivery> =
+            db.collection("webhook_deliveries");
+        let incoming_webhooks: Collection<MongolIncomingWebhook> =
+            db.collection("incoming_webhooks");
+        let two_factors: Collection<MongolTwoFactor> =
+            db.collection("two_factors");
+        let mail_tokens: Collection<MongolMailToken> =
+            db.collection("mail_tokens");
+        let external_identities: Collection<MongolExternalIdentity> =
+            db.collection("external_identities");
 
         Ok(Self {
             client,
@@ -113,259 +139,131 @@ impl MongolDB
             refreshtokens,
             relations,
             logs,
+            presences,
+            typings,
+            webhooks,
+            webhook_deliveries,
+            incoming_webhooks,
+            two_factors,
+            mail_tokens,
+            external_identities,
         })
     }
+}
 
-    async fn internal_add_refresh_token_indexes(
-        coll: &Collection<MongolRefreshToken>
-    ) -> Result<(), Error>
+impl MongolDB
+{
+    #[must_use]
+    pub fn client(&self) -> &Client
     {
-        let device_user_flag_expiration_compound = IndexModel::builder()
-            .keys(doc!{ "device_id": 1, "owner_id": 1, "flag": -1, "expiration_date": -1 })
-            .build();
-
-        let device_flag_expiration_compound = IndexModel::builder()
-            .keys(doc! { "device_id": 1, "flag": -1, "expiration_date": -1 })
-            .build();
-
-        let owner_flag_expiration_compound = IndexModel::builder()
-            .keys(doc! { "owner_id": 1, "flag": -1, "expiration_date": -1 })
-            .build();
-
-        let ip_owner_compound = IndexModel::builder()
-            .keys(doc! { "ip_addr": 1, "owner_id": 1 })
-            .build();
-
-        coll.create_index(device_user_flag_expiration_compound)
-            .await?;
-        coll.create_index(device_flag_expiration_compound).await?;
-        coll.create_index(owner_flag_expiration_compound).await?;
-        coll.create_index(ip_owner_compound).await?;
-
-        Ok(())
+        &self.client
     }
 
-    async fn internal_add_message_indexes(
-        coll: &Collection<MongolMessage>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn database(&self) -> Database
     {
-        let channel_timestamp_flag_compound = IndexModel::builder()
-            .keys(doc! { "channel_id": 1, "timestamp": -1, "flag": 1 })
-            .build();
-
-        coll.create_index(channel_timestamp_flag_compound).await?;
-
-        Ok(())
+        self.client.database(Self::DATABASE)
     }
 
-    async fn internal_add_server_indexes(
-        coll: &Collection<MongolServer>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn users(&self) -> &Collection<MongolUser>
     {
-        let owner_index =
-            IndexModel::builder().keys(doc! { "owner_id": 1}).build();
-
-        let users_index =
-            IndexModel::builder().keys(doc! { "user_ids": 1}).build();
-
-        let channels_index =
-            IndexModel::builder().keys(doc! { "channel_ids": 1}).build();
-
-        coll.create_index(owner_index).await?;
-        coll.create_index(users_index).await?;
-        coll.create_index(channels_index).await?;
-
-        Ok(())
+        &self.users
     }
 
-    async fn internal_add_bucket_indexes(
-        coll: &Collection<MongolBucket>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn chats(&self) -> &Collection<MongolChat>
     {
-        let channel_date_compound = IndexModel::builder()
-            .keys(doc! { "channel_id": 1, "date": -1 })
-            .build();
-
-        coll.create_index(channel_date_compound).await?;
-
-        Ok(())
+        &self.chats
     }
 
-    async fn internal_add_relation_indexes(
-        coll: &Collection<MongolRelation>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn servers(&self) -> &Collection<MongolServer>
     {
-        let user_friends_compound = IndexModel::builder()
-            .keys(doc! { "user_id": 1, "friend_ids": 1 })
-            .build();
-
-        let user_incoming_compound = IndexModel::builder()
-            .keys(doc! { "user_id": 1, "pending_incoming_friend_ids": 1 })
-            .build();
-
-        let user_outgoing_compound = IndexModel::builder()
-            .keys(doc! { "user_id": 1, "pending_outgoing_friend_ids": 1 })
-            .build();
-
-        let user_blocked_compound = IndexModel::builder()
-            .keys(doc! { "user_id": 1, "blocked_ids": 1 })
-            .build();
-
-        coll.create_index(user_friends_compound).await?;
-        coll.create_index(user_incoming_compound).await?;
-        coll.create_index(user_outgoing_compound).await?;
-        coll.create_index(user_blocked_compound).await?;
-
-        Ok(())
+        &self.servers
     }
 
-    async fn internal_add_chat_indexes(
-        coll: &Collection<MongolChat>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn channels(&self) -> &Collection<MongolChannel>
     {
-        let opts_sparse =
-            IndexOptions::builder().unique(true).sparse(true).build();
-
-        let private_id_index = IndexModel::builder()
-            .keys(doc! { "Private._id": 1 })
-            .options(opts_sparse.clone())
-            .build();
-
-        let private_owners_index = IndexModel::builder()
-            .keys(doc! { "Private.owner_ids": 1 })
-            .options(opts_sparse.clone())
-            .build();
-
-        let group_id_index = IndexModel::builder()
-            .keys(doc! { "Group._id": 1 })
-            .options(opts_sparse.clone())
-            .build();
-
-        let group_owner_index = IndexModel::builder()
-            .keys(doc! { "Group.owner_id": 1 })
-            .options(opts_sparse.clone())
-            .build();
-
-        let group_users_index = IndexModel::builder()
-            .keys(doc! { "Group.user_ids": 1 })
-            .options(opts_sparse)
-            .build();
-
-        coll.create_index(private_id_index).await?;
-        coll.create_index(private_owners_index).await?;
-        coll.create_index(group_id_index).await?;
-        coll.create_index(group_owner_index).await?;
-        coll.create_index(group_users_index).await?;
-
-        Ok(())
+        &self.channels
     }
 
-    async fn internal_add_user_indexes(
-        coll: &Collection<MongolUser>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn buckets(&self) -> &Collection<MongolBucket>
     {
-        let opts = IndexOptions::builder().unique(true).build();
-
-        let username_index = IndexModel::builder()
-            .keys(doc! { "username": 1 })
-            .options(opts.clone())
-            .build();
-
-        let mail_index = IndexModel::builder()
-            .keys(doc! { "email": 1 })
-            .options(opts)
-            .build();
-
-        coll.create_index(username_index).await?;
-        coll.create_index(mail_index).await?;
-
-        Ok(())
+        &self.buckets
     }
 
-    async fn internal_add_log_indexes(
-        coll: &Collection<MongolLog>
-    ) -> Result<(), Error>
+    #[must_use]
+    pub fn messages(&self) -> &Collection<MongolMessage>
     {
-        let opts = IndexOptions::builder().unique(true).build();
-
-        let opts_sparse = IndexOptions::builder().sparse(true).build();
-
-        let req_idex = IndexModel::builder()
-            .keys(doc! { "req_id": 1 })
-            .options(opts)
-            .build();
-
-        let user_index = IndexModel::builder()
-            .keys(doc! { "user_info.user_id": 1 })
-            .options(opts_sparse)
-            .build();
-
-        coll.create_index(req_idex).await?;
-        coll.create_index(user_index).await?;
+        &self.messages
+    }
 
-        Ok(())
+    #[must_use]
+    pub fn refresh_tokens(&self) -> &Collection<MongolRefreshToken>
+    {
+        &self.refreshtokens
     }
-}
 
-impl MongolDB
-{
     #[must_use]
-    pub fn client(&self) -> &Client
+    pub fn relations(&self) -> &Collection<MongolRelation>
     {
-        &self.client
+        &self.relations
     }
 
     #[must_use]
-    pub fn users(&self) -> &Collection<MongolUser>
+    pub fn logs(&self) -> &Collection<MongolLog>
     {
-        &self.users
+        &self.logs
     }
 
     #[must_use]
-    pub fn chats(&self) -> &Collection<MongolChat>
+    pub fn presences(&self) -> &Collection<MongolPresence>
     {
-        &self.chats
+        &self.presences
     }
 
     #[must_use]
-    pub fn servers(&self) -> &Collection<MongolServer>
+    pub fn typings(&self) -> &Collection<MongolTyping>
     {
-        &self.servers
+        &self.typings
     }
 
     #[must_use]
-    pub fn channels(&self) -> &Collection<MongolChannel>
+    pub fn webhooks(&self) -> &Collection<MongolWebhook>
     {
-        &self.channels
+        &self.webhooks
     }
 
     #[must_use]
-    pub fn buckets(&self) -> &Collection<MongolBucket>
+    pub fn webhook_deliveries(&self) -> &Collection<MongolWebhookDelivery>
     {
-        &self.buckets
+        &self.webhook_deliveries
     }
 
     #[must_use]
-    pub fn messages(&self) -> &Collection<MongolMessage>
+    pub fn incoming_webhooks(&self) -> &Collection<MongolIncomingWebhook>
     {
-        &self.messages
+        &self.incoming_webhooks
     }
 
     #[must_use]
-    pub fn refresh_tokens(&self) -> &Collection<MongolRefreshToken>
+    pub fn two_factors(&self) -> &Collection<MongolTwoFactor>
     {
-        &self.refreshtokens
+        &self.two_factors
     }
 
     #[must_use]
-    pub fn relations(&self) -> &Collection<MongolRelation>
+    pub fn mail_tokens(&self) -> &Collection<MongolMailToken>
     {
-        &self.relations
+        &self.mail_tokens
     }
 
     #[must_use]
-    pub fn logs(&self) -> &Collection<MongolLog>
+    pub fn external_identities(&self) -> &Collection<MongolExternalIdentity>
     {
-        &self.logs
+        &self.external_identities
     }
 }
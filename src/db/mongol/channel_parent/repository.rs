@@ -20,12 +20,16 @@ use crate::db::{
     MongolChannelVecWrapper,
 };
 use crate::model::channel_parent::chat::Chat;
+use crate::model::channel_parent::server::Listing;
 use crate::model::channel_parent::{
     self,
     ChannelParent,
     Server,
 };
-use crate::model::error;
+use crate::model::{
+    error,
+    Pagination,
+};
 use crate::{
     bubble,
     map_mongo_collection_keys_to_string,
@@ -551,6 +555,38 @@ impl channel_parent::server::Repository for MongolDB
         }
     }
 
+    async fn update_server_discoverable<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        discoverable: bool,
+    ) -> error::Result<'err, ()>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+
+        let filter = doc! {
+            "_id": server_id_local,
+        };
+
+        let update = doc! {
+            "$set": { "discoverable": discoverable }
+        };
+
+        match self.servers().update_one(filter, update).await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::Server
+            )
+            .add_debug_info("error", err.to_string())
+            .add_debug_info(
+                "server id",
+                server_id.to_string(),
+            )),
+        }
+    }
+
     async fn get_server_by_id<'input, 'err>(
         &'input self,
         server_id: &'input str,
@@ -663,6 +699,89 @@ impl channel_parent::server::Repository for MongolDB
             )),
         }
     }
+
+    async fn get_discoverable_servers<'input, 'err>(
+        &'input self,
+        name_option: Option<&'input str>,
+        pagination: Pagination,
+    ) -> error::Result<'err, Vec<Listing>>
+    {
+        let mut filter = doc! {
+            "discoverable": true,
+        };
+
+        if let Some(name) = name_option.filter(|name| !name.trim().is_empty())
+        {
+            filter.insert(
+                "name",
+                doc! {
+                    "$regex": helper::escape_regex(name.trim()),
+                    "$options": "i",
+                },
+            );
+        }
+
+        let pipeline = vec![
+            doc! {
+                "$match": filter
+            },
+            doc! {
+                "$sort": { "name": 1, "_id": 1 }
+            },
+            //skip offset
+            doc! {
+                "$skip": i32::try_from(pagination.get_skip_size()).ok().unwrap_or(0)
+            },
+            //limit output
+            doc! {
+                "$limit": i32::try_from(pagination.page_size).ok().unwrap_or(0)
+            },
+            //owner isnt part of user_ids
+            doc! {
+                "$project":
+                {
+                    "_id": 0,
+                    "id": map_mongo_key_to_string!("$_id", "uuid"),
+                    "name": 1,
+                    "member_count": { "$add": [{ "$size": "$user_ids" }, 1] },
+                }
+            },
+        ];
+
+        let mut cursor =
+            self.servers().aggregate(pipeline).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Server
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut listings = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let document = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Unexpected,
+                    error::OnType::Server
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            let listing = from_document(document).map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Server
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            listings.push(listing);
+        }
+
+        Ok(listings)
+    }
 }
 
 fn internal_private_chat_pipeline() -> [Document; 5]
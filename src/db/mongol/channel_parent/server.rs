@@ -22,6 +22,8 @@ pub struct MongolServer
     _id: Uuid,
     name: String,
     owner_id: Uuid,
+    #[serde(default)]
+    discoverable: bool,
     user_ids: Vec<Uuid>,
     channel_ids: Vec<Uuid>,
     //key is role name
@@ -58,6 +60,7 @@ impl TryFrom<&Server> for MongolServer
             _id: db_id,
             name: value.name.to_string(),
             owner_id,
+            discoverable: value.discoverable,
             user_ids,
             channel_ids,
             roles: value.roles.clone(),
@@ -0,0 +1,79 @@
+mod repository;
+
+use bson::{
+    DateTime,
+    Uuid,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::helper::{
+    self,
+    MongolHelper,
+};
+use crate::model::error;
+use crate::model::external_identity::ExternalIdentity;
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolExternalIdentity
+{
+    pub _id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime,
+}
+
+impl TryFrom<&ExternalIdentity> for MongolExternalIdentity
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &ExternalIdentity) -> Result<Self, Self::Error>
+    {
+        let id = bubble!(helper::convert_domain_id_to_mongol(&value.id))?;
+        let user_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.user_id))?;
+
+        let created_at =
+            value.created_at.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "external identity created at",
+                    value.created_at.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: id,
+            provider: value.provider.clone(),
+            subject: value.subject.clone(),
+            user_id,
+            created_at,
+        })
+    }
+}
+
+impl From<&MongolExternalIdentity> for ExternalIdentity
+{
+    fn from(value: &MongolExternalIdentity) -> Self
+    {
+        ExternalIdentity::convert(
+            value._id.to_string(),
+            value.provider.clone(),
+            value.subject.clone(),
+            value.user_id.to_string(),
+            value.created_at.to_chrono(),
+        )
+    }
+}
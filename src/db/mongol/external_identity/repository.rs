@@ -0,0 +1,77 @@
+use axum::async_trait;
+use bson::doc;
+
+use crate::db::mongol::{
+    MongolDB,
+    MongolExternalIdentity,
+};
+use crate::model::error;
+use crate::model::external_identity::{
+    self,
+    ExternalIdentity,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[async_trait]
+impl external_identity::Repository for MongolDB
+{
+    async fn create_external_identity<'input, 'err>(
+        &'input self,
+        identity: ExternalIdentity,
+    ) -> error::Result<'err, ExternalIdentity>
+    {
+        let db_identity = bubble!(MongolExternalIdentity::try_from(&identity))?;
+
+        //the unique index stops a subject from being linked twice
+        match self.external_identities().insert_one(&db_identity).await
+        {
+            Ok(_) => Ok(identity),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::ExternalIdentity
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_external_identity<'input, 'err>(
+        &'input self,
+        provider: &'input str,
+        subject: &'input str,
+    ) -> error::Result<'err, ExternalIdentity>
+    {
+        let filter = doc! {
+            "provider": provider,
+            "subject": subject,
+        };
+
+        let identity_option =
+            self.external_identities().find_one(filter).await.map_err(
+                |err| {
+                    server_error!(
+                        error::Kind::Fetch,
+                        error::OnType::ExternalIdentity
+                    )
+                    .add_debug_info("error", err.to_string())
+                },
+            )?;
+
+        match identity_option
+        {
+            Some(identity) => Ok(ExternalIdentity::from(
+                &identity,
+            )),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::ExternalIdentity
+            )
+            .add_debug_info(
+                "provider",
+                provider.to_string(),
+            )),
+        }
+    }
+}
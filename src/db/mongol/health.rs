@@ -0,0 +1,29 @@
+use axum::async_trait;
+use bson::doc;
+
+use crate::db::mongol::MongolDB;
+use crate::model::{
+    error,
+    health,
+};
+use crate::server_error;
+
+#[async_trait]
+impl health::Check for MongolDB
+{
+    async fn check<'err>(&self) -> error::Result<'err, ()>
+    {
+        self.client()
+            .database("admin")
+            .run_command(doc! { "ping": 1 })
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Mongo
+                )
+                .add_debug_info("error", err.to_string())
+            })
+    }
+}
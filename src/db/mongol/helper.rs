@@ -103,6 +103,42 @@ pub fn convert_domain_ids_to_mongol<'input, 'err>(
         .collect()
 }
 
+/// escapes user input so it can be safely used inside a mongo `$regex`.
+#[must_use]
+pub fn escape_regex(input: &str) -> String
+{
+    const SPECIAL_CHARS: &[char] = &[
+        '\\',
+        '^',
+        '$',
+        '.',
+        '|',
+        '?',
+        '*',
+        '+',
+        '(',
+        ')',
+        '[',
+        ']',
+        '{',
+        '}',
+    ];
+
+    let mut escaped = String::with_capacity(input.len());
+
+    for char in input.chars()
+    {
+        if SPECIAL_CHARS.contains(&char)
+        {
+            escaped.push('\\');
+        }
+
+        escaped.push(char);
+    }
+
+    escaped
+}
+
 pub fn as_string<S, T>(
     v: &T,
     s: S,
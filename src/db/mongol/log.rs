@@ -1,18 +1,23 @@
 mod repository;
 
 use bson::Uuid;
-use serde::Serialize;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use std::collections::HashMap;
 
 use super::helper;
 use crate::bubble;
 use crate::model::error;
 use crate::model::log::{
+    Entry,
+    EntryError,
     RequestLogLine,
     RequestLogLinePersonal,
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MongolLog
 {
     req_id: Uuid,
@@ -47,7 +52,34 @@ impl TryFrom<RequestLogLine<'_>> for MongolLog
     }
 }
 
-#[derive(Debug, Serialize)]
+impl From<MongolLog> for Entry
+{
+    fn from(value: MongolLog) -> Self
+    {
+        Self {
+            req_id: value.req_id.to_string(),
+            timestamp: value.timestamp,
+            user_info: value.user_info,
+            req_path: value.req_path,
+            req_method: value.req_method,
+            client_error_type: value.client_error_type,
+            server_error: value.server_error.map(|errors| {
+                errors
+                    .into_iter()
+                    .map(|error| EntryError {
+                        kind: error.kind,
+                        on_type: error.on_type,
+                        stack: error.stack,
+                        debug_info: error.debug_info,
+                        pub_info: error.pub_info,
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct MongolLogServerError
 {
     kind: String,
@@ -1,10 +1,26 @@
 use axum::async_trait;
+use bson::{
+    doc,
+    Document,
+    Uuid,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_util::StreamExt;
+use mongodb::options::FindOptions;
 
 use super::MongolLog;
-use crate::db::mongol::MongolDB;
+use crate::db::mongol::{
+    helper,
+    MongolDB,
+};
 use crate::model::error;
 use crate::model::log::{
     self,
+    Entry,
+    Filter,
     RequestLogLine,
 };
 use crate::{
@@ -32,4 +48,132 @@ impl log::Repository for MongolDB
             .add_debug_info("error", err.to_string())),
         }
     }
+
+    async fn get_logs<'input, 'err>(
+        &'input self,
+        filter: &'input Filter,
+        limit: usize,
+    ) -> error::Result<'err, Vec<Entry>>
+    {
+        let mongo_filter = bubble!(internal_log_filter(filter))?;
+
+        //req ids are uuid v7, so sorting on them sorts on time
+        let options = FindOptions::builder()
+            .sort(doc! { "req_id": -1 })
+            .limit(i64::try_from(limit).ok())
+            .build();
+
+        let mut cursor = self
+            .logs()
+            .find(mongo_filter)
+            .with_options(options)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Log
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut entries = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_log = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Log
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            let entry = Entry::from(mongol_log);
+
+            //the time range isnt part of the query when a req id is given
+            if filter.matches(&entry)
+            {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+fn internal_log_filter<'err>(filter: &Filter) -> error::Result<'err, Document>
+{
+    let mut mongo_filter = Document::new();
+
+    if let Some(req_id) = &filter.req_id
+    {
+        mongo_filter.insert(
+            "req_id",
+            bubble!(helper::convert_domain_id_to_mongol(req_id))?,
+        );
+    }
+    else if filter.from.is_some() || filter.to.is_some()
+    {
+        //the timestamp is a string, the time in the uuid v7 is indexed
+        let mut range = Document::new();
+
+        if let Some(from) = filter.from
+        {
+            range.insert(
+                "$gte",
+                internal_uuid_v7_bound(from, 0x00),
+            );
+        }
+        if let Some(to) = filter.to
+        {
+            range.insert(
+                "$lte",
+                internal_uuid_v7_bound(to, 0xff),
+            );
+        }
+
+        mongo_filter.insert("req_id", range);
+    }
+
+    if let Some(user_id) = &filter.user_id
+    {
+        mongo_filter.insert("user_info.user_id", user_id);
+    }
+    if let Some(device_id) = &filter.device_id
+    {
+        mongo_filter.insert(
+            "user_info.device_id",
+            device_id,
+        );
+    }
+    if let Some(client_error_type) = &filter.client_error_type
+    {
+        mongo_filter.insert(
+            "client_error_type",
+            client_error_type,
+        );
+    }
+    if let Some(path) = &filter.path
+    {
+        mongo_filter.insert(
+            "req_path",
+            doc! { "$regex": format!("^{}", helper::escape_regex(path)) },
+        );
+    }
+
+    Ok(mongo_filter)
+}
+
+//the first 48 bits of a uuid v7 are the unix time in ms
+fn internal_uuid_v7_bound(
+    time: DateTime<Utc>,
+    fill: u8,
+) -> Uuid
+{
+    let millis = u64::try_from(time.timestamp_millis()).unwrap_or(0);
+
+    let mut bytes = [fill; 16];
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+
+    Uuid::from_bytes(bytes)
 }
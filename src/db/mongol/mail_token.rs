@@ -0,0 +1,84 @@
+mod repository;
+
+use bson::{
+    DateTime,
+    Uuid,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::helper::{
+    self,
+    MongolHelper,
+};
+use crate::model::error;
+use crate::model::mail_token::{
+    MailToken,
+    Purpose,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolMailToken
+{
+    pub _id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: Purpose,
+    pub token_hash: String,
+    pub expiration_date: DateTime,
+}
+
+impl TryFrom<&MailToken> for MongolMailToken
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &MailToken) -> Result<Self, Self::Error>
+    {
+        let id = bubble!(helper::convert_domain_id_to_mongol(&value.id))?;
+        let user_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.user_id))?;
+
+        let expiration_date = value
+            .expiration_date
+            .convert_to_bson_datetime()
+            .map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "mail token expiration date",
+                    value.expiration_date.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: id,
+            user_id,
+            purpose: value.purpose.clone(),
+            token_hash: value.token_hash.clone(),
+            expiration_date,
+        })
+    }
+}
+
+impl From<&MongolMailToken> for MailToken
+{
+    fn from(value: &MongolMailToken) -> Self
+    {
+        MailToken::convert(
+            value._id.to_string(),
+            value.user_id.to_string(),
+            value.purpose.clone(),
+            value.token_hash.clone(),
+            value.expiration_date.to_chrono(),
+        )
+    }
+}
@@ -0,0 +1,148 @@
+use axum::async_trait;
+use bson::{
+    doc,
+    DateTime,
+    Document,
+};
+
+use crate::db::mongol::{
+    MongolDB,
+    MongolMailToken,
+};
+use crate::model::error;
+use crate::model::mail_token::{
+    self,
+    MailToken,
+    Purpose,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[async_trait]
+impl mail_token::Repository for MongolDB
+{
+    async fn create_mail_token<'input, 'err>(
+        &'input self,
+        token: MailToken,
+    ) -> error::Result<'err, MailToken>
+    {
+        let db_token = bubble!(MongolMailToken::try_from(
+            &token
+        ))?;
+
+        //only the newest mailed link stays valid
+        let filter = doc! {
+            "user_id": db_token.user_id,
+            "purpose": bson::to_bson(&db_token.purpose).map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::MailToken
+                )
+                .add_debug_info("error", err.to_string())
+            })?,
+        };
+
+        self.mail_tokens()
+            .delete_many(filter)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Delete,
+                    error::OnType::MailToken
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match self.mail_tokens().insert_one(&db_token).await
+        {
+            Ok(_) => Ok(token),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::MailToken
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_mail_token<'input, 'err>(
+        &'input self,
+        purpose: Purpose,
+        token_hash: &'input str,
+    ) -> error::Result<'err, MailToken>
+    {
+        let filter = internal_valid_token_filter(&purpose, token_hash)?;
+
+        let token_option =
+            self.mail_tokens().find_one(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::MailToken
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match token_option
+        {
+            Some(token) => Ok(MailToken::from(&token)),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::MailToken
+            )
+            .add_client(error::Client::MAIL_TOKEN_INVALID)),
+        }
+    }
+
+    async fn consume_mail_token<'input, 'err>(
+        &'input self,
+        purpose: Purpose,
+        token_hash: &'input str,
+    ) -> error::Result<'err, MailToken>
+    {
+        let filter = internal_valid_token_filter(&purpose, token_hash)?;
+
+        let token_option = self
+            .mail_tokens()
+            .find_one_and_delete(filter)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Delete,
+                    error::OnType::MailToken
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match token_option
+        {
+            Some(token) => Ok(MailToken::from(&token)),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::MailToken
+            )
+            .add_client(error::Client::MAIL_TOKEN_INVALID)),
+        }
+    }
+}
+
+fn internal_valid_token_filter<'err>(
+    purpose: &Purpose,
+    token_hash: &str,
+) -> error::Result<'err, Document>
+{
+    let purpose = bson::to_bson(purpose).map_err(|err| {
+        server_error!(
+            error::Kind::Parse,
+            error::OnType::MailToken
+        )
+        .add_debug_info("error", err.to_string())
+    })?;
+
+    //the ttl index only sweeps once a minute, so check the date too
+    Ok(doc! {
+        "purpose": purpose,
+        "token_hash": token_hash,
+        "expiration_date": { "$gt": DateTime::now() },
+    })
+}
@@ -31,6 +31,8 @@ pub struct MongolMessage
     pub value: String,
     pub timestamp: DateTime,
     pub owner_id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot: Option<MongolBot>,
     pub channel_id: Uuid,
     pub bucket_id: Option<Uuid>,
     #[serde(serialize_with = "as_string")]
@@ -52,6 +54,8 @@ impl TryFrom<&Message> for MongolMessage
         let channel_id =
             bubble!(helper::convert_domain_id_to_mongol(&value.channel.id))?;
 
+        let bot = value.bot.as_ref().map(MongolBot::try_from).transpose()?;
+
         let bucket_id_option = value
             .bucket_id
             .as_ref()
@@ -65,6 +69,7 @@ impl TryFrom<&Message> for MongolMessage
             value: value.value.clone(),
             timestamp: DateTime::from(timestamp),
             owner_id,
+            bot,
             channel_id,
             bucket_id: bucket_id_option,
             flag: value.flag.clone(),
@@ -72,6 +77,29 @@ impl TryFrom<&Message> for MongolMessage
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MongolBot
+{
+    pub webhook_id: Uuid,
+    pub name: String,
+}
+
+impl TryFrom<&message::Bot> for MongolBot
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &message::Bot) -> Result<Self, Self::Error>
+    {
+        let webhook_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.webhook_id))?;
+
+        Ok(Self {
+            webhook_id,
+            name: value.name.clone(),
+        })
+    }
+}
+
 impl From<message::Flag> for Bson
 {
     fn from(message_flag: message::Flag) -> Bson
@@ -235,7 +235,10 @@ impl message::Repository for MongolDB
                         })?;
                     messages.push(message);
                 },
-                Err(err) => println!("{err}"),
+                Err(err) =>
+                {
+                    tracing::error!(error = %err, "couldnt read message");
+                },
             };
         }
 
@@ -428,6 +431,18 @@ fn internal_message_pipeline() -> [Document; 8]
                 "bucket_id": map_mongo_key_to_string!("$bucket_id", "uuid"),
                 "chat.id": map_mongo_key_to_string!("$chat._id", "uuid"),
                 "owner.id": map_mongo_key_to_string!("$owner._id", "uuid"),
+                //only messages from incoming webhooks have a bot
+                "bot": {
+                    "$cond":
+                    [
+                        { "$ifNull": ["$bot", false] },
+                        {
+                            "webhook_id": map_mongo_key_to_string!("$bot.webhook_id", "uuid"),
+                            "name": "$bot.name",
+                        },
+                        "$$REMOVE",
+                    ]
+                },
                 "chat.owners": map_mongo_collection_keys_to_string!("$chat.owners", "_id", "id", "uuid"),
                 "chat.users": map_mongo_collection_keys_to_string!("$chat.users", "_id", "id", "uuid"),
             }
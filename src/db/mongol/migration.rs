@@ -0,0 +1,610 @@
+mod m0001_indexes;
+mod m0002_lowercase_emails;
+mod m0003_refresh_token_ttl;
+
+use std::collections::BTreeMap;
+
+use axum::async_trait;
+use bson::{
+    doc,
+    Document,
+};
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+use mongodb::error::Error;
+use mongodb::options::ReturnDocument;
+use mongodb::{
+    Collection,
+    Database,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+//a lock older than this is from a migrator that died, and gets taken over
+const LOCK_STALE_MIN: i64 = 30;
+const LOCK_ID: &str = "lock";
+
+/// a versioned change to the database, applied in ascending version order.
+///
+/// never change or renumber a migration once it's released,
+/// add a new one instead.
+#[async_trait]
+pub trait Migration: Send + Sync
+{
+    fn version(&self) -> u32;
+    fn name(&self) -> &'static str;
+    async fn up(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>;
+    async fn down(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>;
+}
+
+/// every migration this build knows about.
+#[must_use]
+pub fn all() -> Vec<Box<dyn Migration>>
+{
+    vec![
+        Box::new(m0001_indexes::Indexes),
+        Box::new(m0002_lowercase_emails::LowercaseEmails),
+        Box::new(m0003_refresh_token_ttl::RefreshTokenTtl),
+    ]
+}
+
+/// an applied migration, stored in the `migrations` collection.
+#[derive(Serialize, Deserialize)]
+struct Record
+{
+    #[serde(rename = "_id")]
+    version: i64,
+    name: String,
+    #[serde(with = "bson::serde_helpers::chrono_datetime_as_bson_datetime")]
+    applied_at: DateTime<Utc>,
+}
+
+/// state of a single migration, known to this build or applied.
+pub struct Status
+{
+    pub version: u32,
+    //none when the database has a migration this build doesn't know
+    pub name: Option<&'static str>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+pub struct Migrator
+{
+    db: Database,
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Migrator
+{
+    #[must_use]
+    pub fn new(db: Database) -> Self
+    {
+        Self::with_migrations(db, all())
+    }
+
+    #[must_use]
+    pub fn with_migrations(
+        db: Database,
+        mut migrations: Vec<Box<dyn Migration>>,
+    ) -> Self
+    {
+        migrations.sort_by_key(|migration| migration.version());
+
+        Self {
+            db,
+            migrations,
+        }
+    }
+
+    pub async fn status<'err>(&self) -> error::Result<'err, Vec<Status>>
+    {
+        let applied = self.internal_applied().await?;
+
+        let mut status: BTreeMap<u32, Status> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                (
+                    migration.version(),
+                    Status {
+                        version: migration.version(),
+                        name: Some(migration.name()),
+                        applied_at: None,
+                    },
+                )
+            })
+            .collect();
+
+        for (version, applied_at) in applied
+        {
+            status
+                .entry(version)
+                .or_insert(Status {
+                    version,
+                    name: None,
+                    applied_at: None,
+                })
+                .applied_at = Some(applied_at);
+        }
+
+        Ok(status.into_values().collect())
+    }
+
+    /// versions this build knows about that aren't applied yet.
+    pub async fn pending<'err>(&self) -> error::Result<'err, Vec<u32>>
+    {
+        let applied = self.internal_applied().await?;
+
+        internal_plan_up(
+            &self.internal_versions(),
+            &applied.keys().copied().collect::<Vec<_>>(),
+            None,
+        )
+    }
+
+    /// applies every pending migration up to and including `target`,
+    /// returns the applied versions.
+    pub async fn up<'err>(
+        &self,
+        target: Option<u32>,
+    ) -> error::Result<'err, Vec<u32>>
+    {
+        self.internal_lock().await?;
+        let result = self.internal_up(target).await;
+        self.internal_unlock().await?;
+
+        result
+    }
+
+    /// rolls back every applied migration above `target`, newest first,
+    /// without a target only the newest one. returns the rolled back versions.
+    pub async fn down<'err>(
+        &self,
+        target: Option<u32>,
+    ) -> error::Result<'err, Vec<u32>>
+    {
+        self.internal_lock().await?;
+        let result = self.internal_down(target).await;
+        self.internal_unlock().await?;
+
+        result
+    }
+
+    /// `status`, `up [version]` or `down [version]` from the command line,
+    /// returns the lines to print.
+    pub async fn run_command(
+        &self,
+        args: &[String],
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>>
+    {
+        let version = args.get(1).map(|version| version.parse()).transpose()?;
+
+        let lines = match args.first().map(String::as_str)
+        {
+            Some("status") | None => self
+                .status()
+                .await?
+                .into_iter()
+                .map(|status| {
+                    format!(
+                        "{:>4} {:<24} {}",
+                        status.version,
+                        status.name.unwrap_or("<unknown>"),
+                        status.applied_at.map_or(
+                            String::from("pending"),
+                            |at| at.to_rfc3339()
+                        ),
+                    )
+                })
+                .collect(),
+            Some("up") => vec![format!(
+                "applied {:?}",
+                self.up(version).await?
+            )],
+            Some("down") =>
+            {
+                vec![format!(
+                    "rolled back {:?}",
+                    self.down(version).await?
+                )]
+            },
+            Some(command) =>
+            {
+                return Err(format!(
+                    "unknown migrate command {command}, expected status, up or down"
+                )
+                .into());
+            },
+        };
+
+        Ok(lines)
+    }
+
+    async fn internal_up<'err>(
+        &self,
+        target: Option<u32>,
+    ) -> error::Result<'err, Vec<u32>>
+    {
+        let applied = self.internal_applied().await?;
+        let plan = internal_plan_up(
+            &self.internal_versions(),
+            &applied.keys().copied().collect::<Vec<_>>(),
+            target,
+        )?;
+
+        for version in &plan
+        {
+            let migration = self.internal_get(*version)?;
+
+            tracing::info!(
+                version,
+                name = migration.name(),
+                "applying migration"
+            );
+
+            migration.up(&self.db).await.map_err(|err| {
+                server_error!(
+                    Kind::Update,
+                    OnType::Migration
+                )
+                .add_debug_info("version", version.to_string())
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            self.internal_records()
+                .insert_one(Record {
+                    version: i64::from(*version),
+                    name: migration.name().to_string(),
+                    applied_at: Utc::now(),
+                })
+                .await
+                .map_err(|err| {
+                    server_error!(
+                        Kind::Insert,
+                        OnType::Migration
+                    )
+                    .add_debug_info("version", version.to_string())
+                    .add_debug_info("error", err.to_string())
+                })?;
+        }
+
+        Ok(plan)
+    }
+
+    async fn internal_down<'err>(
+        &self,
+        target: Option<u32>,
+    ) -> error::Result<'err, Vec<u32>>
+    {
+        let applied = self.internal_applied().await?;
+        let plan = internal_plan_down(
+            &self.internal_versions(),
+            &applied.keys().copied().collect::<Vec<_>>(),
+            target,
+        )?;
+
+        for version in &plan
+        {
+            let migration = self.internal_get(*version)?;
+
+            tracing::info!(
+                version,
+                name = migration.name(),
+                "rolling back migration"
+            );
+
+            migration.down(&self.db).await.map_err(|err| {
+                server_error!(
+                    Kind::Update,
+                    OnType::Migration
+                )
+                .add_debug_info("version", version.to_string())
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            self.internal_records()
+                .delete_one(doc! { "_id": i64::from(*version) })
+                .await
+                .map_err(|err| {
+                    server_error!(
+                        Kind::Delete,
+                        OnType::Migration
+                    )
+                    .add_debug_info("version", version.to_string())
+                    .add_debug_info("error", err.to_string())
+                })?;
+        }
+
+        Ok(plan)
+    }
+
+    fn internal_versions(&self) -> Vec<u32>
+    {
+        self.migrations
+            .iter()
+            .map(|migration| migration.version())
+            .collect()
+    }
+
+    fn internal_get<'err>(
+        &self,
+        version: u32,
+    ) -> error::Result<'err, &dyn Migration>
+    {
+        self.migrations
+            .iter()
+            .find(|migration| migration.version() == version)
+            .map(AsRef::as_ref)
+            .ok_or_else(|| {
+                server_error!(
+                    Kind::NotFound,
+                    OnType::Migration
+                )
+                .add_debug_info("version", version.to_string())
+            })
+    }
+
+    fn internal_records(&self) -> Collection<Record>
+    {
+        self.db.collection("migrations")
+    }
+
+    async fn internal_applied<'err>(
+        &self
+    ) -> error::Result<'err, BTreeMap<u32, DateTime<Utc>>>
+    {
+        let mut cursor =
+            self.internal_records().find(doc! {}).await.map_err(|err| {
+                server_error!(Kind::Fetch, OnType::Migration)
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut applied = BTreeMap::new();
+
+        while cursor.advance().await.map_err(|err| {
+            server_error!(Kind::Fetch, OnType::Migration)
+                .add_debug_info("error", err.to_string())
+        })?
+        {
+            let record = cursor.deserialize_current().map_err(|err| {
+                server_error!(Kind::Parse, OnType::Migration)
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+            let version = u32::try_from(record.version).map_err(|_| {
+                server_error!(
+                    Kind::InValid,
+                    OnType::Migration
+                )
+                .add_debug_info(
+                    "version",
+                    record.version.to_string(),
+                )
+            })?;
+
+            applied.insert(version, record.applied_at);
+        }
+
+        Ok(applied)
+    }
+
+    /// one migrator at a time, replicas starting together would otherwise
+    /// run the same migration twice.
+    async fn internal_lock<'err>(&self) -> error::Result<'err, ()>
+    {
+        let stale = bson::DateTime::from_chrono(
+            Utc::now() - Duration::minutes(LOCK_STALE_MIN),
+        );
+
+        //an existing fresh lock doesn't match, the upsert then collides on _id
+        let result = self
+            .db
+            .collection::<Document>("migration_lock")
+            .find_one_and_update(
+                doc! {
+                    "_id": LOCK_ID,
+                    "$or": [
+                        { "locked": false },
+                        { "locked_at": { "$lt": stale } },
+                    ],
+                },
+                doc! {
+                    "$set": {
+                        "locked": true,
+                        "locked_at": bson::DateTime::now(),
+                    },
+                },
+            )
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await;
+
+        match result
+        {
+            Ok(_) => Ok(()),
+            Err(err) if internal_is_duplicate_key(&err) => Err(server_error!(
+                Kind::AlreadyInUse,
+                OnType::Migration
+            )
+            .add_debug_info(
+                "reason",
+                String::from("another migrator holds the lock"),
+            )),
+            Err(err) => Err(server_error!(
+                Kind::Update,
+                OnType::Migration
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn internal_unlock<'err>(&self) -> error::Result<'err, ()>
+    {
+        self.db
+            .collection::<Document>("migration_lock")
+            .update_one(
+                doc! { "_id": LOCK_ID },
+                doc! { "$set": { "locked": false } },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|err| {
+                server_error!(
+                    Kind::Update,
+                    OnType::Migration
+                )
+                .add_debug_info("error", err.to_string())
+            })
+    }
+}
+
+fn internal_is_duplicate_key(err: &Error) -> bool
+{
+    const DUPLICATE_KEY: i32 = 11000;
+
+    match err.kind.as_ref()
+    {
+        mongodb::error::ErrorKind::Write(
+            mongodb::error::WriteFailure::WriteError(write_error),
+        ) => write_error.code == DUPLICATE_KEY,
+        mongodb::error::ErrorKind::Command(command_error) =>
+        {
+            command_error.code == DUPLICATE_KEY
+        },
+        _ => false,
+    }
+}
+
+/// known versions that aren't applied, up to `target`, ascending.
+fn internal_plan_up<'err>(
+    known: &[u32],
+    applied: &[u32],
+    target: Option<u32>,
+) -> error::Result<'err, Vec<u32>>
+{
+    if let Some(target) = target
+    {
+        if !known.contains(&target)
+        {
+            return Err(server_error!(
+                Kind::NotFound,
+                OnType::Migration
+            )
+            .add_debug_info("version", target.to_string()));
+        }
+    }
+
+    let mut plan: Vec<u32> = known
+        .iter()
+        .copied()
+        .filter(|version| !applied.contains(version))
+        .filter(|version| target.is_none_or(|target| *version <= target))
+        .collect();
+    plan.sort_unstable();
+
+    Ok(plan)
+}
+
+/// applied versions above `target`, descending,
+/// without a target only the newest applied one.
+fn internal_plan_down<'err>(
+    known: &[u32],
+    applied: &[u32],
+    target: Option<u32>,
+) -> error::Result<'err, Vec<u32>>
+{
+    let mut plan: Vec<u32> = match target
+    {
+        Some(target) => applied
+            .iter()
+            .copied()
+            .filter(|version| *version > target)
+            .collect(),
+        None => applied.iter().copied().max().into_iter().collect(),
+    };
+    plan.sort_unstable_by(|a, b| b.cmp(a));
+
+    //rolling back needs the code of the migration
+    if let Some(unknown) = plan.iter().find(|version| !known.contains(version))
+    {
+        return Err(server_error!(
+            Kind::NotFound,
+            OnType::Migration
+        )
+        .add_debug_info("version", unknown.to_string()));
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::db::mongol::migration::{
+        internal_plan_down,
+        internal_plan_up,
+    };
+
+    #[test]
+    fn test_plan_up_is_valid()
+    {
+        assert_eq!(
+            vec![2, 3],
+            internal_plan_up(&[3, 1, 2], &[1], None).unwrap()
+        );
+        assert_eq!(
+            vec![2],
+            internal_plan_up(&[1, 2, 3], &[1], Some(2)).unwrap()
+        );
+        assert!(internal_plan_up(&[1, 2], &[1, 2], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_up_unknown_target_is_invalid()
+    {
+        assert!(internal_plan_up(&[1, 2], &[], Some(5)).is_err());
+    }
+
+    #[test]
+    fn test_plan_down_is_valid()
+    {
+        assert_eq!(
+            vec![3],
+            internal_plan_down(&[1, 2, 3], &[1, 2, 3], None).unwrap()
+        );
+        assert_eq!(
+            vec![3, 2],
+            internal_plan_down(
+                &[1, 2, 3],
+                &[1, 2, 3],
+                Some(1)
+            )
+            .unwrap()
+        );
+        assert!(internal_plan_down(&[1], &[], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plan_down_unknown_version_is_invalid()
+    {
+        assert!(internal_plan_down(&[1, 2], &[1, 2, 3], None).is_err());
+    }
+}
@@ -0,0 +1,407 @@
+use std::time::Duration;
+
+use axum::async_trait;
+use bson::{
+    doc,
+    Bson,
+    Document,
+};
+use mongodb::error::Error;
+use mongodb::options::IndexOptions;
+use mongodb::{
+    Database,
+    IndexModel,
+};
+
+use super::Migration;
+
+/// the indexes that used to be created on every start,
+/// creating an existing index is a no-op so this is safe on older databases.
+///
+/// frozen as they were released, a new index is a new migration.
+pub struct Indexes;
+
+#[async_trait]
+impl Migration for Indexes
+{
+    fn version(&self) -> u32
+    {
+        1
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "indexes"
+    }
+
+    async fn up(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>
+    {
+        for (collection, index) in internal_indexes()
+        {
+            db.collection::<Document>(collection)
+                .create_index(index)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    //only the indexes created above, not the ones of later migrations
+    async fn down(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>
+    {
+        for (collection, index) in internal_indexes()
+        {
+            db.collection::<Document>(collection)
+                .drop_index(internal_index_name(
+                    &index.keys,
+                ))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// the name mongo gives an index without one, `username_1` for `{ username: 1 }`.
+fn internal_index_name(keys: &Document) -> String
+{
+    keys.iter()
+        .map(
+            |(key, direction)| match direction
+            {
+                Bson::Int32(direction) => format!("{key}_{direction}"),
+                Bson::Int64(direction) => format!("{key}_{direction}"),
+                direction => format!("{key}_{direction}"),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn internal_index(
+    keys: Document,
+    options: Option<IndexOptions>,
+) -> IndexModel
+{
+    IndexModel::builder().keys(keys).options(options).build()
+}
+
+fn internal_unique() -> IndexOptions
+{
+    IndexOptions::builder().unique(true).build()
+}
+
+fn internal_unique_sparse() -> IndexOptions
+{
+    IndexOptions::builder().unique(true).sparse(true).build()
+}
+
+fn internal_sparse() -> IndexOptions
+{
+    IndexOptions::builder().sparse(true).build()
+}
+
+fn internal_ttl() -> IndexOptions
+{
+    IndexOptions::builder()
+        .expire_after(Duration::from_secs(0))
+        .build()
+}
+
+#[allow(clippy::too_many_lines)]
+fn internal_indexes() -> Vec<(&'static str, IndexModel)>
+{
+    vec![
+        (
+            "users",
+            internal_index(
+                doc! { "username": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "users",
+            internal_index(
+                doc! { "email": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "chats",
+            internal_index(
+                doc! { "Private._id": 1 },
+                Some(internal_unique_sparse()),
+            ),
+        ),
+        (
+            "chats",
+            internal_index(
+                doc! { "Private.owner_ids": 1 },
+                Some(internal_unique_sparse()),
+            ),
+        ),
+        (
+            "chats",
+            internal_index(
+                doc! { "Group._id": 1 },
+                Some(internal_unique_sparse()),
+            ),
+        ),
+        (
+            "chats",
+            internal_index(
+                doc! { "Group.owner_id": 1 },
+                Some(internal_unique_sparse()),
+            ),
+        ),
+        (
+            "chats",
+            internal_index(
+                doc! { "Group.user_ids": 1 },
+                Some(internal_unique_sparse()),
+            ),
+        ),
+        (
+            "servers",
+            internal_index(doc! { "owner_id": 1 }, None),
+        ),
+        (
+            "servers",
+            internal_index(doc! { "user_ids": 1 }, None),
+        ),
+        (
+            "servers",
+            internal_index(
+                doc! { "channel_ids": 1 },
+                None,
+            ),
+        ),
+        (
+            "servers",
+            internal_index(
+                doc! { "discoverable": 1, "name": 1 },
+                None,
+            ),
+        ),
+        (
+            "buckets",
+            internal_index(
+                doc! { "channel_id": 1, "date": -1 },
+                None,
+            ),
+        ),
+        (
+            "messages",
+            internal_index(
+                doc! { "channel_id": 1, "timestamp": -1, "flag": 1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(
+                doc! { "device_id": 1, "owner_id": 1, "flag": -1, "expiration_date": -1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(
+                doc! { "device_id": 1, "flag": -1, "expiration_date": -1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(
+                doc! { "owner_id": 1, "flag": -1, "expiration_date": -1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(
+                doc! { "ip_addr": 1, "owner_id": 1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(
+                doc! { "value": 1, "owner_id": 1 },
+                None,
+            ),
+        ),
+        (
+            "refresh_tokens",
+            internal_index(doc! { "family_id": 1 }, None),
+        ),
+        (
+            "relations",
+            internal_index(
+                doc! { "user_id": 1, "friend_ids": 1 },
+                None,
+            ),
+        ),
+        (
+            "relations",
+            internal_index(
+                doc! { "user_id": 1, "pending_incoming_friend_ids": 1 },
+                None,
+            ),
+        ),
+        (
+            "relations",
+            internal_index(
+                doc! { "user_id": 1, "pending_outgoing_friend_ids": 1 },
+                None,
+            ),
+        ),
+        (
+            "relations",
+            internal_index(
+                doc! { "user_id": 1, "blocked_ids": 1 },
+                None,
+            ),
+        ),
+        (
+            "logs",
+            internal_index(
+                doc! { "req_id": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "logs",
+            internal_index(
+                doc! { "user_info.user_id": 1 },
+                Some(internal_sparse()),
+            ),
+        ),
+        (
+            "presences",
+            internal_index(
+                doc! { "user_id": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "typings",
+            internal_index(
+                doc! { "channel_id": 1, "user_id": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "typings",
+            internal_index(
+                doc! { "expiration_date": 1 },
+                Some(internal_ttl()),
+            ),
+        ),
+        (
+            "webhooks",
+            internal_index(doc! { "server_id": 1 }, None),
+        ),
+        (
+            "webhook_deliveries",
+            internal_index(
+                doc! { "webhook_id": 1, "timestamp": -1 },
+                None,
+            ),
+        ),
+        (
+            "incoming_webhooks",
+            internal_index(doc! { "server_id": 1 }, None),
+        ),
+        //two factors are keyed by user id, no extra indexes needed
+        (
+            "mail_tokens",
+            internal_index(
+                doc! { "token_hash": 1, "purpose": 1 },
+                None,
+            ),
+        ),
+        (
+            "mail_tokens",
+            internal_index(
+                doc! { "user_id": 1, "purpose": 1 },
+                None,
+            ),
+        ),
+        (
+            "mail_tokens",
+            internal_index(
+                doc! { "expiration_date": 1 },
+                Some(internal_ttl()),
+            ),
+        ),
+        (
+            "external_identities",
+            internal_index(
+                doc! { "provider": 1, "subject": 1 },
+                Some(internal_unique()),
+            ),
+        ),
+        (
+            "external_identities",
+            internal_index(doc! { "user_id": 1 }, None),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashSet;
+
+    use bson::doc;
+
+    use crate::db::mongol::migration::m0001_indexes::{
+        internal_index_name,
+        internal_indexes,
+    };
+
+    #[test]
+    fn test_internal_index_name_is_valid()
+    {
+        assert_eq!(
+            "username_1",
+            internal_index_name(&doc! { "username": 1 })
+        );
+        assert_eq!(
+            "device_id_1_owner_id_1_flag_-1_expiration_date_-1",
+            internal_index_name(
+                &doc! { "device_id": 1, "owner_id": 1, "flag": -1, "expiration_date": -1 }
+            )
+        );
+        assert_eq!(
+            "user_info.user_id_1",
+            internal_index_name(&doc! { "user_info.user_id": 1 })
+        );
+    }
+
+    #[test]
+    fn test_internal_indexes_is_valid()
+    {
+        let indexes = internal_indexes();
+        let names: HashSet<(&str, String)> = indexes
+            .iter()
+            .map(|(collection, index)| {
+                (
+                    *collection,
+                    internal_index_name(&index.keys),
+                )
+            })
+            .collect();
+
+        //`down` drops by name, so no two may share one
+        assert_eq!(indexes.len(), names.len());
+    }
+}
@@ -0,0 +1,55 @@
+use axum::async_trait;
+use bson::{
+    doc,
+    Document,
+};
+use mongodb::error::Error;
+use mongodb::Database;
+
+use super::Migration;
+
+/// emails are stored lowercase since lookups normalize them,
+/// see `User::normalize_email`.
+///
+/// fails on the unique email index when two accounts only differ in casing,
+/// those have to be merged by hand first.
+pub struct LowercaseEmails;
+
+#[async_trait]
+impl Migration for LowercaseEmails
+{
+    fn version(&self) -> u32
+    {
+        2
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "lowercase_emails"
+    }
+
+    async fn up(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>
+    {
+        let pipeline = vec![doc! {
+            "$set": { "email": { "$toLower": { "$trim": { "input": "$email" } } } },
+        }];
+
+        db.collection::<Document>("users")
+            .update_many(doc! {}, pipeline)
+            .await?;
+
+        Ok(())
+    }
+
+    //the original casing is gone, and lowercase emails work either way
+    async fn down(
+        &self,
+        _db: &Database,
+    ) -> Result<(), Error>
+    {
+        Ok(())
+    }
+}
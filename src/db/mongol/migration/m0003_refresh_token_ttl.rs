@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use axum::async_trait;
+use bson::{
+    doc,
+    Document,
+};
+use mongodb::error::Error;
+use mongodb::options::IndexOptions;
+use mongodb::{
+    Database,
+    IndexModel,
+};
+
+use super::Migration;
+
+const INDEX_NAME: &str = "expiration_date_1";
+
+/// deletes refresh tokens once they expire,
+/// every refresh leaves the rotated token behind and nothing else removes them.
+pub struct RefreshTokenTtl;
+
+#[async_trait]
+impl Migration for RefreshTokenTtl
+{
+    fn version(&self) -> u32
+    {
+        3
+    }
+
+    fn name(&self) -> &'static str
+    {
+        "refresh_token_ttl"
+    }
+
+    async fn up(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>
+    {
+        let opts_ttl = IndexOptions::builder()
+            .name(INDEX_NAME.to_string())
+            .expire_after(Duration::from_secs(0))
+            .build();
+
+        let expiration_index = IndexModel::builder()
+            .keys(doc! { "expiration_date": 1 })
+            .options(opts_ttl)
+            .build();
+
+        db.collection::<Document>("refresh_tokens")
+            .create_index(expiration_index)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(
+        &self,
+        db: &Database,
+    ) -> Result<(), Error>
+    {
+        db.collection::<Document>("refresh_tokens")
+            .drop_index(INDEX_NAME)
+            .await
+    }
+}
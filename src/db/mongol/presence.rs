@@ -0,0 +1,131 @@
+mod repository;
+
+use bson::{
+    DateTime,
+    Uuid,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::helper::{
+    self,
+    MongolHelper,
+};
+use crate::model::error;
+use crate::model::presence::{
+    self,
+    Presence,
+    Typing,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MongolPresence
+{
+    pub user_id: Uuid,
+    pub status: presence::Status,
+    pub last_seen: Option<DateTime>,
+}
+
+impl TryFrom<&Presence> for MongolPresence
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &Presence) -> Result<Self, Self::Error>
+    {
+        let user_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.user_id))?;
+
+        let last_seen = value
+            .last_seen
+            .map(|last_seen| {
+                last_seen.convert_to_bson_datetime().map_err(|_| {
+                    server_error!(
+                        error::Kind::InValid,
+                        error::OnType::Date
+                    )
+                    .add_debug_info(
+                        "presence last seen",
+                        last_seen.to_rfc3339(),
+                    )
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            user_id,
+            status: value.status.clone(),
+            last_seen,
+        })
+    }
+}
+
+impl From<&MongolPresence> for Presence
+{
+    fn from(value: &MongolPresence) -> Self
+    {
+        Presence::convert(
+            value.user_id.to_string(),
+            value.status.clone(),
+            value.last_seen.map(DateTime::to_chrono),
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MongolTyping
+{
+    pub user_id: Uuid,
+    pub channel_id: Uuid,
+    pub expiration_date: DateTime,
+}
+
+impl TryFrom<&Typing> for MongolTyping
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &Typing) -> Result<Self, Self::Error>
+    {
+        let user_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.user_id))?;
+        let channel_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.channel_id))?;
+
+        let expiration_date = value
+            .expiration_date
+            .convert_to_bson_datetime()
+            .map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "typing expiration",
+                    value.expiration_date.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            user_id,
+            channel_id,
+            expiration_date,
+        })
+    }
+}
+
+impl From<&MongolTyping> for Typing
+{
+    fn from(value: &MongolTyping) -> Self
+    {
+        Typing::convert(
+            value.user_id.to_string(),
+            value.channel_id.to_string(),
+            value.expiration_date.to_chrono(),
+        )
+    }
+}
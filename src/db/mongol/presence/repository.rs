@@ -0,0 +1,206 @@
+use axum::async_trait;
+use bson::{
+    doc,
+    DateTime,
+};
+use futures_util::StreamExt;
+
+use crate::db::mongol::{
+    helper,
+    MongolDB,
+    MongolPresence,
+    MongolTyping,
+};
+use crate::model::error;
+use crate::model::presence::{
+    self,
+    Presence,
+    Typing,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[async_trait]
+impl presence::Repository for MongolDB
+{
+    async fn upsert_presence<'input, 'err>(
+        &'input self,
+        presence: Presence,
+    ) -> error::Result<'err, Presence>
+    {
+        let db_presence = bubble!(MongolPresence::try_from(
+            &presence
+        ))?;
+
+        let filter = doc! {
+            "user_id": db_presence.user_id,
+        };
+
+        match self
+            .presences()
+            .replace_one(filter, &db_presence)
+            .upsert(true)
+            .await
+        {
+            Ok(_) => Ok(presence),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::Presence
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn touch_presence<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let status =
+            bson::to_bson(&presence::Status::Online).map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Presence
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        //the status stays whatever the user set, only new presences get one
+        let update = doc! {
+            "$set": { "last_seen": DateTime::now() },
+            "$setOnInsert": { "status": status },
+        };
+
+        match self
+            .presences()
+            .update_one(
+                doc! { "user_id": user_id_local },
+                update,
+            )
+            .upsert(true)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::Presence
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_presences<'input, 'err>(
+        &'input self,
+        user_ids: Vec<&'input str>,
+    ) -> error::Result<'err, Vec<Presence>>
+    {
+        let user_ids_local =
+            bubble!(helper::convert_domain_ids_to_mongol(&user_ids))?;
+
+        let filter = doc! {
+            "user_id": { "$in": user_ids_local },
+        };
+
+        let mut cursor =
+            self.presences().find(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Presence
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut presences = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_presence = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Presence
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            presences.push(Presence::from(
+                &mongol_presence,
+            ));
+        }
+
+        Ok(presences)
+    }
+
+    async fn upsert_typing<'input, 'err>(
+        &'input self,
+        typing: Typing,
+    ) -> error::Result<'err, ()>
+    {
+        let db_typing = bubble!(MongolTyping::try_from(
+            &typing
+        ))?;
+
+        let filter = doc! {
+            "user_id": db_typing.user_id,
+            "channel_id": db_typing.channel_id,
+        };
+
+        match self
+            .typings()
+            .replace_one(filter, &db_typing)
+            .upsert(true)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::Typing
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_typing<'input, 'err>(
+        &'input self,
+        channel_id: &'input str,
+    ) -> error::Result<'err, Vec<Typing>>
+    {
+        let channel_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(channel_id))?;
+
+        //ttl index cleanup isnt instant, so filter the stale ones out here
+        let filter = doc! {
+            "channel_id": channel_id_local,
+            "expiration_date": { "$gte": DateTime::now() },
+        };
+
+        let mut cursor = self.typings().find(filter).await.map_err(|err| {
+            server_error!(
+                error::Kind::Fetch,
+                error::OnType::Typing
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let mut typings = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_typing = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Typing
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            typings.push(Typing::from(&mongol_typing));
+        }
+
+        Ok(typings)
+    }
+}
@@ -30,8 +30,11 @@ pub struct MongolRefreshToken
 {
     pub value: String,
     pub device_id: Uuid,
+    pub family_id: Uuid,
     pub ip_addr: String,
+    pub user_agent: Option<String>,
     pub expiration_date: DateTime,
+    pub last_used: DateTime,
     #[serde(serialize_with = "as_string")]
     pub flag: refresh_token::Flag,
     pub owner_id: Uuid,
@@ -45,6 +48,8 @@ impl TryFrom<&RefreshToken> for MongolRefreshToken
     {
         let device_id =
             bubble!(helper::convert_domain_id_to_mongol(&value.device_id))?;
+        let family_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.family_id))?;
         let owner_id =
             bubble!(helper::convert_domain_id_to_mongol(&value.owner.id))?;
 
@@ -62,11 +67,26 @@ impl TryFrom<&RefreshToken> for MongolRefreshToken
                 )
             })?;
 
+        let last_used =
+            value.last_used.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "refresh last used",
+                    value.last_used.to_rfc3339(),
+                )
+            })?;
+
         Ok(Self {
             value: value.value.clone(),
             device_id,
+            family_id,
             ip_addr: value.ip_addr.to_string(),
+            user_agent: value.user_agent.clone(),
             expiration_date,
+            last_used,
             flag: value.flag.clone(),
             owner_id,
         })
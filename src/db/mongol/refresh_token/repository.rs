@@ -57,93 +57,163 @@ impl refresh_token::Repository for MongolDB
         let user_id_local =
             bubble!(helper::convert_domain_id_to_mongol(user_id))?;
 
-        let pipelines = vec![
-            //filter
-            doc! {
-                "$match":
-                {
-                    "device_id": device_id_local,
-                    "owner_id": user_id_local,
-                    "expiration_date": { "$gte": DateTime::now() },
-                    "flag": internal_valid_refresh_token_filter(),
-                }
-            },
-            //join with owners
-            doc! {
-                "$lookup":
-                {
-                    "from": "users",
-                    "localField": "owner_id",
-                    "foreignField": "_id",
-                    "as": "owner"
-                },
-            },
-            //join with users
-            doc! {
-                "$unwind":
-                {
-                    "path": "$owner"
-                },
-            },
-            //rename fields
-            doc! {
-                "$addFields":
-                {
-                    "device_id": map_mongo_key_to_string!("$device_id", "uuid"),
-                    "owner.id": map_mongo_key_to_string!("$owner._id", "uuid"),
-                }
-            },
-            //hide fields
-            doc! {
-                "$unset": ["_id", "owner_id", "owner._id"]
-            },
-        ];
-
-        let mut cursor =
-            self.refresh_tokens().aggregate(pipelines).await.map_err(
-                |err| {
-                    server_error!(
-                        error::Kind::Fetch,
-                        error::OnType::RefreshToken
-                    )
-                    .add_debug_info("error", err.to_string())
-                },
-            )?;
+        let filter = doc! {
+            "device_id": device_id_local,
+            "owner_id": user_id_local,
+            "expiration_date": { "$gte": DateTime::now() },
+            "flag": internal_valid_refresh_token_filter(),
+        };
 
-        let document_option =
-            cursor.next().await.transpose().map_err(|err| {
+        internal_get_tokens(self, filter)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
                 server_error!(
-                    error::Kind::Unexpected,
+                    error::Kind::NotFound,
+                    error::OnType::RefreshToken
+                )
+                .add_debug_info(
+                    "device id",
+                    device_id.to_string(),
+                )
+            })
+    }
+
+    async fn get_token_by_value<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        value: &'input str,
+    ) -> error::Result<'err, RefreshToken>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! {
+            "value": value,
+            "owner_id": user_id_local,
+        };
+
+        internal_get_tokens(self, filter)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                server_error!(
+                    error::Kind::NotFound,
+                    error::OnType::RefreshToken
+                )
+                .add_debug_info("user id", user_id.to_string())
+            })
+    }
+
+    async fn rotate_token<'input, 'err>(
+        &'input self,
+        old: &'input RefreshToken,
+        new: RefreshToken,
+    ) -> error::Result<'err, RefreshToken>
+    {
+        let owner_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(&old.owner.id))?;
+
+        //the flag check makes sure only one concurrent refresh wins
+        let filter = doc! {
+            "value": &old.value,
+            "owner_id": owner_id_local,
+            "flag": internal_valid_refresh_token_filter(),
+        };
+
+        let update = doc! {
+            "$set":
+            {
+                "flag": refresh_token::Flag::Rotated,
+                "last_used": DateTime::now(),
+            }
+        };
+
+        let result = self
+            .refresh_tokens()
+            .update_one(filter, update)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Update,
                     error::OnType::RefreshToken
                 )
                 .add_debug_info("error", err.to_string())
             })?;
 
-        match document_option
+        if result.modified_count == 0
         {
-            Some(document) =>
-            {
-                let refresh_token = from_document(document).map_err(|err| {
-                    server_error!(
-                        error::Kind::Parse,
-                        error::OnType::RefreshToken
-                    )
-                    .add_debug_info("error", err.to_string())
-                })?;
-
-                Ok(refresh_token)
-            },
-            None => Err(server_error!(
-                error::Kind::NotFound,
+            return Err(server_error!(
+                error::Kind::NotAllowed,
                 error::OnType::RefreshToken
             )
             .add_debug_info(
                 "device id",
-                device_id.to_string(),
+                old.device_id.clone(),
+            ));
+        }
+
+        bubble!(self.create_token(new).await)
+    }
+
+    async fn flag_token_family<'input, 'err>(
+        &'input self,
+        family_id: &'input str,
+        flag: refresh_token::Flag,
+    ) -> error::Result<'err, ()>
+    {
+        let family_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(family_id))?;
+
+        //tokens from before families existed use their device id as family
+        let filter = doc! {
+            "$or": [
+                { "family_id": family_id_local },
+                {
+                    "family_id": { "$exists": false },
+                    "device_id": family_id_local,
+                },
+            ]
+        };
+
+        let update = doc! {
+            "$set": { "flag": flag }
+        };
+
+        match self.refresh_tokens().update_many(filter, update).await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Revoke,
+                error::OnType::RefreshToken
+            )
+            .add_debug_info("error", err.to_string())
+            .add_debug_info(
+                "family id",
+                family_id.to_string(),
             )),
         }
     }
 
+    async fn get_valid_tokens<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, Vec<RefreshToken>>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! {
+            "owner_id": user_id_local,
+            "expiration_date": { "$gte": DateTime::now() },
+            "flag": internal_valid_refresh_token_filter(),
+        };
+
+        internal_get_tokens(self, filter).await
+    }
+
     async fn revoke_token<'input, 'err>(
         &'input self,
         user_id: &'input str,
@@ -156,16 +226,18 @@ impl refresh_token::Repository for MongolDB
         let device_id_local =
             bubble!(helper::convert_domain_id_to_mongol(device_id))?;
 
+        //keeps the rotated history of the device intact
         let filter = doc! {
             "owner_id": user_id_local,
             "device_id": device_id_local,
+            "flag": internal_valid_refresh_token_filter(),
         };
 
         let update = doc! {
             "$set": { "flag": refresh_token::Flag::Revoked }
         };
 
-        match self.refresh_tokens().update_one(filter, update).await
+        match self.refresh_tokens().update_many(filter, update).await
         {
             Ok(_) => Ok(()),
             Err(err) => Err(server_error!(
@@ -205,7 +277,28 @@ impl refresh_token::Repository for MongolDB
         }
     }
 
-    async fn update_expiration<'input, 'err>(
+    async fn count_valid_tokens<'input, 'err>(
+        &'input self
+    ) -> error::Result<'err, u64>
+    {
+        let filter = doc! {
+            "flag": internal_valid_refresh_token_filter(),
+            "expiration_date": { "$gte": DateTime::now() },
+        };
+
+        self.refresh_tokens()
+            .count_documents(filter)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::RefreshToken
+                )
+                .add_debug_info("error", err.to_string())
+            })
+    }
+
+    async fn update_token<'input, 'err>(
         &'input self,
         token: &'input RefreshToken,
     ) -> error::Result<'err, ()>
@@ -222,7 +315,13 @@ impl refresh_token::Repository for MongolDB
         };
 
         let update = doc! {
-            "$set": { "expiration_date": db_token.expiration_date }
+            "$set":
+            {
+                "expiration_date": db_token.expiration_date,
+                "last_used": db_token.last_used,
+                "ip_addr": db_token.ip_addr,
+                "user_agent": db_token.user_agent,
+            }
         };
 
         match self.refresh_tokens().update_one(filter, update).await
@@ -241,3 +340,94 @@ fn internal_valid_refresh_token_filter() -> Document
 {
     doc! { "$in": [ refresh_token::Flag::None ] }
 }
+
+async fn internal_get_tokens<'err>(
+    repo: &MongolDB,
+    filter: Document,
+) -> error::Result<'err, Vec<RefreshToken>>
+{
+    let pipelines = vec![
+        //filter
+        doc! {
+            "$match": filter
+        },
+        //most recently used first
+        doc! {
+            "$sort":
+            {
+                "last_used": -1
+            }
+        },
+        //join with owners
+        doc! {
+            "$lookup":
+            {
+                "from": "users",
+                "localField": "owner_id",
+                "foreignField": "_id",
+                "as": "owner"
+            },
+        },
+        //join with users
+        doc! {
+            "$unwind":
+            {
+                "path": "$owner"
+            },
+        },
+        //rename fields
+        doc! {
+            "$addFields":
+            {
+                "device_id": map_mongo_key_to_string!("$device_id", "uuid"),
+                //tokens from before families existed use their device id
+                "family_id": map_mongo_key_to_string!(
+                    doc! { "$ifNull": ["$family_id", "$device_id"] },
+                    "uuid"
+                ),
+                "owner.id": map_mongo_key_to_string!("$owner._id", "uuid"),
+            }
+        },
+        //hide fields
+        doc! {
+            "$unset": ["_id", "owner_id", "owner._id"]
+        },
+    ];
+
+    let mut cursor =
+        repo.refresh_tokens()
+            .aggregate(pipelines)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::RefreshToken
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+    let mut refresh_tokens = Vec::new();
+
+    while let Some(result) = cursor.next().await
+    {
+        let document = result.map_err(|err| {
+            server_error!(
+                error::Kind::Unexpected,
+                error::OnType::RefreshToken
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let refresh_token = from_document(document).map_err(|err| {
+            server_error!(
+                error::Kind::Parse,
+                error::OnType::RefreshToken
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        refresh_tokens.push(refresh_token);
+    }
+
+    Ok(refresh_tokens)
+}
@@ -139,6 +139,38 @@ impl relation::Repository for MongolDB
         does_user_relation_exist(self, filter).await
     }
 
+    async fn get_friend_ids<'input, 'err>(
+        &'input self,
+        current_user_id: &'input str,
+    ) -> error::Result<'err, Vec<String>>
+    {
+        let current_user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(current_user_id))?;
+
+        let filter = doc! {
+            "user_id" : current_user_id_local,
+        };
+
+        let mongol_relation_option =
+            self.relations().find_one(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::RelationFriend
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        Ok(
+            mongol_relation_option.map_or_else(Vec::new, |relation| {
+                relation
+                    .friend_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            }),
+        )
+    }
+
     async fn does_blocked_exist<'input, 'err>(
         &'input self,
         current_user_id: &'input str,
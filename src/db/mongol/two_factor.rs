@@ -0,0 +1,82 @@
+mod repository;
+
+use bson::{
+    DateTime,
+    Uuid,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::helper::{
+    self,
+    MongolHelper,
+};
+use crate::model::error;
+use crate::model::two_factor::TwoFactor;
+use crate::{
+    bubble,
+    server_error,
+};
+
+//one per user, so the user id is the key
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolTwoFactor
+{
+    pub _id: Uuid,
+    pub secret: String,
+    pub is_enabled: bool,
+    pub recovery_code_hashes: Vec<String>,
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime,
+}
+
+impl TryFrom<&TwoFactor> for MongolTwoFactor
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &TwoFactor) -> Result<Self, Self::Error>
+    {
+        let user_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.user_id))?;
+
+        let created_at =
+            value.created_at.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "two factor created at",
+                    value.created_at.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: user_id,
+            secret: value.secret.clone(),
+            is_enabled: value.is_enabled,
+            recovery_code_hashes: value.recovery_code_hashes.clone(),
+            last_used_step: value.last_used_step,
+            created_at,
+        })
+    }
+}
+
+impl From<&MongolTwoFactor> for TwoFactor
+{
+    fn from(value: &MongolTwoFactor) -> Self
+    {
+        TwoFactor::convert(
+            value._id.to_string(),
+            value.secret.clone(),
+            value.is_enabled,
+            value.recovery_code_hashes.clone(),
+            value.last_used_step,
+            value.created_at.to_chrono(),
+        )
+    }
+}
@@ -0,0 +1,202 @@
+use axum::async_trait;
+use bson::doc;
+
+use crate::db::mongol::{
+    helper,
+    MongolDB,
+    MongolTwoFactor,
+};
+use crate::model::error;
+use crate::model::two_factor::{
+    self,
+    TwoFactor,
+    UsedCode,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[async_trait]
+impl two_factor::Repository for MongolDB
+{
+    async fn create_two_factor<'input, 'err>(
+        &'input self,
+        two_factor: TwoFactor,
+    ) -> error::Result<'err, TwoFactor>
+    {
+        let db_two_factor = bubble!(MongolTwoFactor::try_from(
+            &two_factor
+        ))?;
+
+        //an enabled one has to be disabled first, so it is never overwritten
+        let filter = doc! {
+            "_id": db_two_factor._id,
+            "is_enabled": false,
+        };
+
+        match self
+            .two_factors()
+            .replace_one(filter, &db_two_factor)
+            .upsert(true)
+            .await
+        {
+            Ok(_) => Ok(two_factor),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_two_factor<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, TwoFactor>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! {
+            "_id": user_id_local,
+        };
+
+        let two_factor_option =
+            self.two_factors().find_one(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::TwoFactor
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match two_factor_option
+        {
+            Some(two_factor) => Ok(TwoFactor::from(&two_factor)),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info("user id", user_id.to_string())),
+        }
+    }
+
+    async fn enable_two_factor<'input, 'err>(
+        &'input self,
+        two_factor: &'input TwoFactor,
+    ) -> error::Result<'err, ()>
+    {
+        let db_two_factor = bubble!(MongolTwoFactor::try_from(
+            two_factor
+        ))?;
+
+        let filter = doc! {
+            "_id": db_two_factor._id,
+            "is_enabled": false,
+        };
+
+        let update = doc! {
+            "$set":
+            {
+                "is_enabled": true,
+                "recovery_code_hashes": db_two_factor.recovery_code_hashes,
+                "last_used_step": db_two_factor.last_used_step,
+            }
+        };
+
+        match self.two_factors().update_one(filter, update).await
+        {
+            Ok(result) if result.matched_count == 0 => Err(server_error!(
+                error::Kind::AlreadyExists,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info(
+                "user id",
+                two_factor.user_id.clone(),
+            )),
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn consume_two_factor_code<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        used_code: &'input UsedCode,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        //the check is part of the write, two requests with the same code
+        //cant both match
+        let (filter, update) = match used_code
+        {
+            UsedCode::Step(step) => (
+                doc! {
+                    "_id": user_id_local,
+                    "is_enabled": true,
+                    "$or": [
+                        { "last_used_step": null },
+                        { "last_used_step": { "$lt": step } },
+                    ],
+                },
+                doc! { "$set": { "last_used_step": step } },
+            ),
+            UsedCode::RecoveryCode(hash) => (
+                doc! {
+                    "_id": user_id_local,
+                    "is_enabled": true,
+                    "recovery_code_hashes": hash,
+                },
+                doc! { "$pull": { "recovery_code_hashes": hash } },
+            ),
+        };
+
+        match self.two_factors().update_one(filter, update).await
+        {
+            Ok(result) if result.matched_count == 0 => Err(server_error!(
+                error::Kind::NoChange,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info(
+                "reason",
+                String::from("code already used"),
+            )),
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Update,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn delete_two_factor<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! {
+            "_id": user_id_local,
+        };
+
+        match self.two_factors().delete_one(filter).await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Delete,
+                error::OnType::TwoFactor
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 mod repository;
 
-use bson::Bson;
+use bson::{
+    Bson,
+    DateTime,
+};
 use mongodb::bson::Uuid;
 use serde::{
     Deserialize,
@@ -30,6 +33,8 @@ pub struct MongolUser
     pub hashed_password: String,
     #[serde(serialize_with = "as_string")]
     pub flag: user::Flag,
+    #[serde(default)]
+    pub sessions_valid_after: Option<DateTime>,
 }
 
 impl TryFrom<&User> for MongolUser
@@ -46,6 +51,9 @@ impl TryFrom<&User> for MongolUser
             email: value.email.clone(),
             hashed_password: value.hashed_password.clone(),
             flag: value.flag.clone(),
+            sessions_valid_after: value
+                .sessions_valid_after
+                .map(DateTime::from_chrono),
         })
     }
 }
@@ -81,6 +89,7 @@ impl From<&MongolUser> for User
             value.email.clone(),
             value.hashed_password.clone(),
             value.flag.clone(),
+            value.sessions_valid_after.map(DateTime::to_chrono),
         )
     }
 }
@@ -1,5 +1,9 @@
 use axum::async_trait;
 use bson::Document;
+use chrono::{
+    DateTime,
+    Utc,
+};
 use futures_util::StreamExt;
 use mongodb::bson::{
     doc,
@@ -48,7 +52,7 @@ impl user::Repository for MongolDB
         user_mail: &'input str,
     ) -> error::Result<'err, bool>
     {
-        let filter = doc! { "email" : user_mail };
+        let filter = doc! { "email" : User::normalize_email(user_mail) };
 
         internal_does_user_exist(self, filter).await
     }
@@ -121,7 +125,7 @@ impl user::Repository for MongolDB
         email: &'input str,
     ) -> error::Result<'err, User>
     {
-        let filter = doc! { "email": email };
+        let filter = doc! { "email": User::normalize_email(email) };
 
         internal_get_user(self, filter).await.map_err(|err| {
             server_error!(err).add_debug_info("email", email.to_string())
@@ -189,7 +193,10 @@ impl user::Repository for MongolDB
                     })?;
                     users.push(user);
                 },
-                Err(err) => println!("{err}"),
+                Err(err) =>
+                {
+                    tracing::error!(error = %err, "couldnt read user");
+                },
             }
         }
 
@@ -249,12 +256,77 @@ impl user::Repository for MongolDB
                     })?;
                     users.push(user);
                 },
-                Err(err) => println!("{err}"),
+                Err(err) =>
+                {
+                    tracing::error!(error = %err, "couldnt read user");
+                },
             }
         }
 
         Ok(users)
     }
+
+    async fn update_user_flag<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        flag: user::Flag,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! { "_id": user_id_local };
+        let update = doc! { "$set": { "flag": flag } };
+
+        internal_update_user(self, filter, update)
+            .await
+            .map_err(|err| {
+                server_error!(err)
+                    .add_debug_info("user id", user_id.to_string())
+            })
+    }
+
+    async fn update_user_hashed_password<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        hashed_password: String,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! { "_id": user_id_local };
+        let update = doc! { "$set": { "hashed_password": hashed_password } };
+
+        internal_update_user(self, filter, update)
+            .await
+            .map_err(|err| {
+                server_error!(err)
+                    .add_debug_info("user id", user_id.to_string())
+            })
+    }
+
+    async fn update_user_sessions_valid_after<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        sessions_valid_after: DateTime<Utc>,
+    ) -> error::Result<'err, ()>
+    {
+        let user_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(user_id))?;
+
+        let filter = doc! { "_id": user_id_local };
+        let update = doc! { "$set": {
+            "sessions_valid_after": bson::DateTime::from_chrono(sessions_valid_after)
+        } };
+
+        internal_update_user(self, filter, update)
+            .await
+            .map_err(|err| {
+                server_error!(err)
+                    .add_debug_info("user id", user_id.to_string())
+            })
+    }
 }
 
 async fn internal_does_user_exist<'input, 'err>(
@@ -296,6 +368,35 @@ async fn internal_get_user<'input, 'err>(
     }
 }
 
+async fn internal_update_user<'err>(
+    repo: &MongolDB,
+    filter: Document,
+    update: Document,
+) -> error::Result<'err, ()>
+{
+    let result =
+        repo.users()
+            .update_one(filter, update)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Update,
+                    error::OnType::User
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+    if result.matched_count == 0
+    {
+        return Err(server_error!(
+            error::Kind::NotFound,
+            error::OnType::User
+        ));
+    }
+
+    Ok(())
+}
+
 fn _internal_wrap_valid_user_filter(filter: Document) -> Document
 {
     doc! {
@@ -0,0 +1,238 @@
+mod repository;
+
+use bson::{
+    DateTime,
+    Uuid,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::helper::{
+    self,
+    MongolHelper,
+};
+use crate::model::error;
+use crate::model::webhook::{
+    Delivery,
+    EventType,
+    Incoming,
+    Webhook,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolWebhook
+{
+    pub _id: Uuid,
+    pub server_id: Uuid,
+    pub target_url: String,
+    pub channel_ids: Vec<Uuid>,
+    pub event_types: Vec<EventType>,
+    pub secret: String,
+    pub created_at: DateTime,
+}
+
+impl TryFrom<&Webhook> for MongolWebhook
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &Webhook) -> Result<Self, Self::Error>
+    {
+        let webhook_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.id))?;
+        let server_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.server_id))?;
+
+        let channel_ids: Vec<&str> =
+            value.channel_ids.iter().map(String::as_str).collect();
+        let channel_ids =
+            bubble!(helper::convert_domain_ids_to_mongol(&channel_ids))?;
+
+        let created_at =
+            value.created_at.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "webhook created at",
+                    value.created_at.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: webhook_id,
+            server_id,
+            target_url: value.target_url.clone(),
+            channel_ids,
+            event_types: value.event_types.clone(),
+            secret: value.secret.clone(),
+            created_at,
+        })
+    }
+}
+
+impl From<&MongolWebhook> for Webhook
+{
+    fn from(value: &MongolWebhook) -> Self
+    {
+        Webhook::convert(
+            value._id.to_string(),
+            value.server_id.to_string(),
+            value.target_url.clone(),
+            value.channel_ids.iter().map(Uuid::to_string).collect(),
+            value.event_types.clone(),
+            value.secret.clone(),
+            value.created_at.to_chrono(),
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolWebhookDelivery
+{
+    pub _id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: String,
+    pub event_type: EventType,
+    pub attempt: u32,
+    pub succeeded: bool,
+    pub response_status: Option<u32>,
+    pub error: Option<String>,
+    pub timestamp: DateTime,
+}
+
+impl TryFrom<&Delivery> for MongolWebhookDelivery
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &Delivery) -> Result<Self, Self::Error>
+    {
+        let delivery_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.id))?;
+        let webhook_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.webhook_id))?;
+
+        let timestamp =
+            value.timestamp.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "webhook delivery timestamp",
+                    value.timestamp.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: delivery_id,
+            webhook_id,
+            event_id: value.event_id.clone(),
+            event_type: value.event_type.clone(),
+            attempt: value.attempt,
+            succeeded: value.succeeded,
+            response_status: value.response_status.map(u32::from),
+            error: value.error.clone(),
+            timestamp,
+        })
+    }
+}
+
+impl From<&MongolWebhookDelivery> for Delivery
+{
+    fn from(value: &MongolWebhookDelivery) -> Self
+    {
+        Delivery::convert(
+            value._id.to_string(),
+            value.webhook_id.to_string(),
+            value.event_id.clone(),
+            value.event_type.clone(),
+            value.attempt,
+            value.succeeded,
+            value
+                .response_status
+                .and_then(|status| u16::try_from(status).ok()),
+            value.error.clone(),
+            value.timestamp.to_chrono(),
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::pub_underscore_fields)]
+#[allow(clippy::used_underscore_binding)]
+pub struct MongolIncomingWebhook
+{
+    pub _id: Uuid,
+    pub server_id: Uuid,
+    pub channel_id: Uuid,
+    pub name: String,
+    pub token_hash: String,
+    pub creator_id: Uuid,
+    pub created_at: DateTime,
+}
+
+impl TryFrom<&Incoming> for MongolIncomingWebhook
+{
+    type Error = error::Server<'static>;
+
+    fn try_from(value: &Incoming) -> Result<Self, Self::Error>
+    {
+        let webhook_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.id))?;
+        let server_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.server_id))?;
+        let channel_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.channel_id))?;
+        let creator_id =
+            bubble!(helper::convert_domain_id_to_mongol(&value.creator_id))?;
+
+        let created_at =
+            value.created_at.convert_to_bson_datetime().map_err(|_| {
+                server_error!(
+                    error::Kind::InValid,
+                    error::OnType::Date
+                )
+                .add_debug_info(
+                    "incoming webhook created at",
+                    value.created_at.to_rfc3339(),
+                )
+            })?;
+
+        Ok(Self {
+            _id: webhook_id,
+            server_id,
+            channel_id,
+            name: value.name.clone(),
+            token_hash: value.token_hash.clone(),
+            creator_id,
+            created_at,
+        })
+    }
+}
+
+impl From<&MongolIncomingWebhook> for Incoming
+{
+    fn from(value: &MongolIncomingWebhook) -> Self
+    {
+        Incoming::convert(
+            value._id.to_string(),
+            value.server_id.to_string(),
+            value.channel_id.to_string(),
+            value.name.clone(),
+            value.token_hash.clone(),
+            value.creator_id.to_string(),
+            value.created_at.to_chrono(),
+        )
+    }
+}
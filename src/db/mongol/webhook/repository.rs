@@ -0,0 +1,376 @@
+use axum::async_trait;
+use bson::doc;
+use futures_util::StreamExt;
+use mongodb::options::FindOptions;
+
+use crate::db::mongol::{
+    helper,
+    MongolDB,
+    MongolIncomingWebhook,
+    MongolWebhook,
+    MongolWebhookDelivery,
+};
+use crate::model::webhook::{
+    self,
+    Delivery,
+    Incoming,
+    Webhook,
+};
+use crate::model::{
+    error,
+    Pagination,
+};
+use crate::{
+    bubble,
+    server_error,
+};
+
+#[async_trait]
+impl webhook::Repository for MongolDB
+{
+    async fn create_webhook<'input, 'err>(
+        &'input self,
+        webhook: Webhook,
+    ) -> error::Result<'err, Webhook>
+    {
+        let db_webhook = bubble!(MongolWebhook::try_from(
+            &webhook
+        ))?;
+
+        match self.webhooks().insert_one(&db_webhook).await
+        {
+            Ok(_) => Ok(webhook),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::Webhook
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, Webhook>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+        let webhook_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(webhook_id))?;
+
+        let filter = doc! {
+            "_id": webhook_id_local,
+            "server_id": server_id_local,
+        };
+
+        let webhook_option =
+            self.webhooks().find_one(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match webhook_option
+        {
+            Some(webhook) => Ok(Webhook::from(&webhook)),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            )
+            .add_debug_info(
+                "webhook id",
+                webhook_id.to_string(),
+            )),
+        }
+    }
+
+    async fn get_webhooks_by_server<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+    ) -> error::Result<'err, Vec<Webhook>>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+
+        let filter = doc! {
+            "server_id": server_id_local,
+        };
+
+        let mut cursor = self.webhooks().find(filter).await.map_err(|err| {
+            server_error!(
+                error::Kind::Fetch,
+                error::OnType::Webhook
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let mut webhooks = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_webhook = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            webhooks.push(Webhook::from(&mongol_webhook));
+        }
+
+        Ok(webhooks)
+    }
+
+    async fn delete_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, ()>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+        let webhook_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(webhook_id))?;
+
+        let filter = doc! {
+            "_id": webhook_id_local,
+            "server_id": server_id_local,
+        };
+
+        let result =
+            self.webhooks().delete_one(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Delete,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        if result.deleted_count == 0
+        {
+            return Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            )
+            .add_debug_info(
+                "webhook id",
+                webhook_id.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn create_delivery<'input, 'err>(
+        &'input self,
+        delivery: Delivery,
+    ) -> error::Result<'err, ()>
+    {
+        let db_delivery = bubble!(MongolWebhookDelivery::try_from(&delivery))?;
+
+        match self.webhook_deliveries().insert_one(&db_delivery).await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::WebhookDelivery
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_deliveries<'input, 'err>(
+        &'input self,
+        webhook_id: &'input str,
+        pagination: Pagination,
+    ) -> error::Result<'err, Vec<Delivery>>
+    {
+        let webhook_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(webhook_id))?;
+
+        let filter = doc! {
+            "webhook_id": webhook_id_local,
+        };
+
+        let options = FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .skip(u64::try_from(pagination.get_skip_size()).ok())
+            .limit(i64::try_from(pagination.page_size).ok())
+            .build();
+
+        let mut cursor = self
+            .webhook_deliveries()
+            .find(filter)
+            .with_options(options)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::WebhookDelivery
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut deliveries = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_delivery = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::WebhookDelivery
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            deliveries.push(Delivery::from(
+                &mongol_delivery,
+            ));
+        }
+
+        Ok(deliveries)
+    }
+
+    async fn create_incoming_webhook<'input, 'err>(
+        &'input self,
+        webhook: Incoming,
+    ) -> error::Result<'err, Incoming>
+    {
+        let db_webhook = bubble!(MongolIncomingWebhook::try_from(&webhook))?;
+
+        match self.incoming_webhooks().insert_one(&db_webhook).await
+        {
+            Ok(_) => Ok(webhook),
+            Err(err) => Err(server_error!(
+                error::Kind::Insert,
+                error::OnType::Webhook
+            )
+            .add_debug_info("error", err.to_string())),
+        }
+    }
+
+    async fn get_incoming_webhook<'input, 'err>(
+        &'input self,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, Incoming>
+    {
+        let webhook_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(webhook_id))?;
+
+        let filter = doc! {
+            "_id": webhook_id_local,
+        };
+
+        let webhook_option = self
+            .incoming_webhooks()
+            .find_one(filter)
+            .await
+            .map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        match webhook_option
+        {
+            Some(webhook) => Ok(Incoming::from(&webhook)),
+            None => Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            )
+            .add_debug_info(
+                "webhook id",
+                webhook_id.to_string(),
+            )),
+        }
+    }
+
+    async fn get_incoming_webhooks_by_server<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+    ) -> error::Result<'err, Vec<Incoming>>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+
+        let filter = doc! {
+            "server_id": server_id_local,
+        };
+
+        let mut cursor =
+            self.incoming_webhooks().find(filter).await.map_err(|err| {
+                server_error!(
+                    error::Kind::Fetch,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let mut webhooks = Vec::new();
+
+        while let Some(result) = cursor.next().await
+        {
+            let mongol_webhook = result.map_err(|err| {
+                server_error!(
+                    error::Kind::Parse,
+                    error::OnType::Webhook
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            webhooks.push(Incoming::from(
+                &mongol_webhook,
+            ));
+        }
+
+        Ok(webhooks)
+    }
+
+    async fn delete_incoming_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, ()>
+    {
+        let server_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(server_id))?;
+        let webhook_id_local =
+            bubble!(helper::convert_domain_id_to_mongol(webhook_id))?;
+
+        let filter = doc! {
+            "_id": webhook_id_local,
+            "server_id": server_id_local,
+        };
+
+        let result =
+            self.incoming_webhooks().delete_one(filter).await.map_err(
+                |err| {
+                    server_error!(
+                        error::Kind::Delete,
+                        error::OnType::Webhook
+                    )
+                    .add_debug_info("error", err.to_string())
+                },
+            )?;
+
+        if result.deleted_count == 0
+        {
+            return Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            )
+            .add_debug_info(
+                "webhook id",
+                webhook_id.to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -1,14 +1,20 @@
+mod auth;
 mod channel;
 mod chat;
 mod message;
+mod presence;
 mod server;
 mod user;
+mod webhook;
 
+pub use auth::*;
 pub use channel::*;
 pub use chat::*;
 pub use message::*;
+pub use presence::*;
 pub use server::*;
 pub use user::*;
+pub use webhook::*;
 
 pub trait ObjectToDTO<Input>
 {
@@ -0,0 +1,123 @@
+use serde::Serialize;
+
+use crate::handlers::logic::auth::{
+    LoginStatus,
+    TokenPair,
+};
+use crate::model::refresh_token::RefreshToken;
+use crate::model::two_factor::TwoFactor;
+
+use super::ObjectToDTO;
+
+#[derive(Serialize)]
+pub struct TokenPairResponse
+{
+    acces_token: String,
+    refresh_token: String,
+    device_id: String,
+    token_type: &'static str,
+    //seconds
+    expires_in: i64,
+    refresh_expiration_date: String,
+}
+
+impl ObjectToDTO<TokenPair> for TokenPairResponse
+{
+    fn obj_to_dto(token_pair: TokenPair) -> Self
+    {
+        Self {
+            acces_token: token_pair.acces_token,
+            refresh_token: token_pair.refresh_token.value,
+            device_id: token_pair.refresh_token.device_id,
+            token_type: "Bearer",
+            expires_in: token_pair.expires_in,
+            refresh_expiration_date: token_pair
+                .refresh_token
+                .expiration_date
+                .to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SessionGetResponse
+{
+    device_id: String,
+    ip_addr: String,
+    user_agent: Option<String>,
+    last_used: String,
+    expiration_date: String,
+    //the device making the request
+    current: bool,
+}
+
+impl ObjectToDTO<(RefreshToken, bool)> for SessionGetResponse
+{
+    fn obj_to_dto((refresh_token, current): (RefreshToken, bool)) -> Self
+    {
+        Self {
+            device_id: refresh_token.device_id,
+            ip_addr: refresh_token.ip_addr,
+            user_agent: refresh_token.user_agent,
+            last_used: refresh_token.last_used.to_rfc3339(),
+            expiration_date: refresh_token.expiration_date.to_rfc3339(),
+            current,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LoginResponse
+{
+    //when true, post a code to `/auth/login/two-factor` to get the session
+    two_factor_required: bool,
+}
+
+impl ObjectToDTO<LoginStatus> for LoginResponse
+{
+    fn obj_to_dto(login_status: LoginStatus) -> Self
+    {
+        Self {
+            two_factor_required: matches!(
+                login_status,
+                LoginStatus::TwoFactorRequired
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TwoFactorEnrollResponse
+{
+    secret: String,
+    //render as qr code for authenticator apps
+    provisioning_uri: String,
+}
+
+impl ObjectToDTO<(TwoFactor, String)> for TwoFactorEnrollResponse
+{
+    fn obj_to_dto((two_factor, provisioning_uri): (TwoFactor, String)) -> Self
+    {
+        Self {
+            secret: two_factor.secret,
+            provisioning_uri,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TwoFactorRecoveryCodesResponse
+{
+    //only shown this once
+    recovery_codes: Vec<String>,
+}
+
+impl ObjectToDTO<Vec<String>> for TwoFactorRecoveryCodesResponse
+{
+    fn obj_to_dto(recovery_codes: Vec<String>) -> Self
+    {
+        Self {
+            recovery_codes,
+        }
+    }
+}
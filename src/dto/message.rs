@@ -1,6 +1,9 @@
 use serde::Serialize;
 
-use crate::model::message::Message;
+use crate::model::message::{
+    Bot,
+    Message,
+};
 
 use super::ObjectToDTO;
 
@@ -11,6 +14,8 @@ pub struct MessageCreateResponse
     value: String,
     timestamp: String,
     owner_id: String,
+    //clients should show the bot as author when set
+    bot: Option<MessageBotResponse>,
     channel_id: String,
     bucket_id: String,
     //we actually gonna delete stuff?
@@ -27,6 +32,7 @@ impl ObjectToDTO<Message> for MessageCreateResponse
             value: message.value,
             timestamp: message.timestamp.to_rfc3339(),
             owner_id: message.owner.id,
+            bot: message.bot.map(MessageBotResponse::obj_to_dto),
             channel_id: message.channel.id,
             bucket_id: message.bucket_id.map_or(String::new(), |bucket| bucket),
             flag: message.flag.to_string(),
@@ -41,6 +47,8 @@ pub struct MessageGetResponse
     value: String,
     timestamp: String,
     owner_id: String,
+    //clients should show the bot as author when set
+    bot: Option<MessageBotResponse>,
     channel_id: String,
     bucket_id: String,
     //we actually gonna delete stuff?
@@ -57,9 +65,28 @@ impl ObjectToDTO<Message> for MessageGetResponse
             value: message.value,
             timestamp: message.timestamp.to_rfc3339(),
             owner_id: message.owner.id,
+            bot: message.bot.map(MessageBotResponse::obj_to_dto),
             channel_id: message.channel.id,
             bucket_id: message.bucket_id.map_or(String::new(), |bucket| bucket),
             flag: message.flag.to_string(),
         }
     }
 }
+
+#[derive(Serialize)]
+pub struct MessageBotResponse
+{
+    webhook_id: String,
+    name: String,
+}
+
+impl ObjectToDTO<Bot> for MessageBotResponse
+{
+    fn obj_to_dto(bot: Bot) -> Self
+    {
+        Self {
+            webhook_id: bot.webhook_id,
+            name: bot.name,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::model::presence::{
+    Presence,
+    Typing,
+};
+
+use super::ObjectToDTO;
+
+#[derive(Serialize)]
+pub struct PresenceGetResponse
+{
+    user_id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_seen: Option<String>,
+}
+
+impl ObjectToDTO<Presence> for PresenceGetResponse
+{
+    fn obj_to_dto(presence: Presence) -> Self
+    {
+        Self {
+            status: presence.effective_status(Utc::now()).to_string(),
+            user_id: presence.user_id,
+            last_seen: presence.last_seen.map(|date| date.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TypingGetResponse
+{
+    user_id: String,
+    expiration_date: String,
+}
+
+impl ObjectToDTO<Typing> for TypingGetResponse
+{
+    fn obj_to_dto(typing: Typing) -> Self
+    {
+        Self {
+            user_id: typing.user_id,
+            expiration_date: typing.expiration_date.to_rfc3339(),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use serde::Serialize;
 
+use crate::model::channel_parent::server::Listing;
 use crate::model::channel_parent::{
     Role,
     Server,
@@ -77,3 +78,51 @@ impl ObjectToDTO<Server> for ServerGetResponse
         }
     }
 }
+
+#[derive(Serialize)]
+pub struct ServerDirectoryResponse
+{
+    id: String,
+    name: String,
+    member_count: usize,
+}
+
+impl ObjectToDTO<Listing> for ServerDirectoryResponse
+{
+    fn obj_to_dto(model_input: Listing) -> Self
+    {
+        Self {
+            id: model_input.id,
+            name: model_input.name,
+            member_count: model_input.member_count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ServerPreviewResponse
+{
+    id: String,
+    r#type: String,
+    name: String,
+    owner: String,
+    member_count: usize,
+    channels: Vec<ChannelGetResponse>,
+}
+
+impl ObjectToDTO<Server> for ServerPreviewResponse
+{
+    fn obj_to_dto(model_input: Server) -> Self
+    {
+        let member_count = model_input.member_count();
+
+        Self {
+            id: model_input.id,
+            r#type: String::from("Server"),
+            name: model_input.name,
+            owner: model_input.owner.id,
+            member_count,
+            channels: vec_to_dto(model_input.channels.into_values().collect()),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use serde::Serialize;
+use serde_json::{
+    json,
+    Value,
+};
+
+use crate::model::event::{
+    Event,
+    Kind,
+};
+use crate::model::webhook::{
+    Delivery,
+    EventType,
+    Incoming,
+    Webhook,
+};
+
+use super::{
+    MessageCreateResponse,
+    ObjectToDTO,
+};
+
+//only returned once, the secret is needed to verify the signatures
+#[derive(Serialize)]
+pub struct WebhookCreateResponse
+{
+    id: String,
+    server_id: String,
+    target_url: String,
+    channel_ids: Vec<String>,
+    event_types: Vec<EventType>,
+    secret: String,
+}
+
+impl ObjectToDTO<Webhook> for WebhookCreateResponse
+{
+    fn obj_to_dto(webhook: Webhook) -> Self
+    {
+        Self {
+            id: webhook.id,
+            server_id: webhook.server_id,
+            target_url: webhook.target_url,
+            channel_ids: webhook.channel_ids,
+            event_types: webhook.event_types,
+            secret: webhook.secret,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookGetResponse
+{
+    id: String,
+    server_id: String,
+    target_url: String,
+    channel_ids: Vec<String>,
+    event_types: Vec<EventType>,
+    created_at: String,
+}
+
+impl ObjectToDTO<Webhook> for WebhookGetResponse
+{
+    fn obj_to_dto(webhook: Webhook) -> Self
+    {
+        Self {
+            id: webhook.id,
+            server_id: webhook.server_id,
+            target_url: webhook.target_url,
+            channel_ids: webhook.channel_ids,
+            event_types: webhook.event_types,
+            created_at: webhook.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryGetResponse
+{
+    id: String,
+    event_id: String,
+    event_type: EventType,
+    attempt: u32,
+    succeeded: bool,
+    response_status: Option<u16>,
+    error: Option<String>,
+    timestamp: String,
+}
+
+impl ObjectToDTO<Delivery> for WebhookDeliveryGetResponse
+{
+    fn obj_to_dto(delivery: Delivery) -> Self
+    {
+        Self {
+            id: delivery.id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            attempt: delivery.attempt,
+            succeeded: delivery.succeeded,
+            response_status: delivery.response_status,
+            error: delivery.error,
+            timestamp: delivery.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+//only returned once, the token is part of the url to post to
+#[derive(Serialize)]
+pub struct IncomingWebhookCreateResponse
+{
+    id: String,
+    server_id: String,
+    channel_id: String,
+    name: String,
+    token: String,
+}
+
+impl ObjectToDTO<(Incoming, String)> for IncomingWebhookCreateResponse
+{
+    fn obj_to_dto((webhook, token): (Incoming, String)) -> Self
+    {
+        Self {
+            id: webhook.id,
+            server_id: webhook.server_id,
+            channel_id: webhook.channel_id,
+            name: webhook.name,
+            token,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct IncomingWebhookGetResponse
+{
+    id: String,
+    server_id: String,
+    channel_id: String,
+    name: String,
+    creator_id: String,
+    created_at: String,
+}
+
+impl ObjectToDTO<Incoming> for IncomingWebhookGetResponse
+{
+    fn obj_to_dto(webhook: Incoming) -> Self
+    {
+        Self {
+            id: webhook.id,
+            server_id: webhook.server_id,
+            channel_id: webhook.channel_id,
+            name: webhook.name,
+            creator_id: webhook.creator_id,
+            created_at: webhook.created_at.to_rfc3339(),
+        }
+    }
+}
+
+//body that gets posted to the target url
+#[derive(Serialize)]
+pub struct WebhookPayload
+{
+    id: String,
+    event: &'static str,
+    timestamp: String,
+    data: Value,
+}
+
+impl ObjectToDTO<Event> for WebhookPayload
+{
+    fn obj_to_dto(event: Event) -> Self
+    {
+        let name = event.kind.name();
+
+        let data = match event.kind
+        {
+            Kind::MessageCreated {
+                server_id,
+                message,
+            }
+            | Kind::MessageUpdated {
+                server_id,
+                message,
+            } => json!({
+                "server_id": server_id,
+                "message": MessageCreateResponse::obj_to_dto(message),
+            }),
+            Kind::MemberJoined {
+                server_id,
+                user_id,
+            } => json!({
+                "server_id": server_id,
+                "user_id": user_id,
+            }),
+            Kind::FriendRequested {
+                user_id,
+                other_user_id,
+            }
+            | Kind::FriendConfirmed {
+                user_id,
+                other_user_id,
+            } => json!({
+                "user_id": user_id,
+                "other_user_id": other_user_id,
+            }),
+            Kind::ChatUsersAdded {
+                chat_id,
+                user_ids,
+            } => json!({
+                "chat_id": chat_id,
+                "user_ids": user_ids,
+            }),
+        };
+
+        Self {
+            id: event.id,
+            event: name,
+            timestamp: event.timestamp.to_rfc3339(),
+            data,
+        }
+    }
+}
@@ -1,16 +1,24 @@
 mod api;
+mod health;
 pub mod logic;
+mod metrics;
+mod redirect;
 mod web;
 
 use axum::http::StatusCode;
 use axum::middleware;
 use axum::response::IntoResponse;
-use axum::routing::Router;
+use axum::routing::{
+    get,
+    Router,
+};
 use std::sync::Arc;
 use tower_cookies::CookieManagerLayer;
 
 use crate::middleware::auth::mw_ctx_resolver;
 use crate::middleware::logging::main_response_mapper;
+use crate::middleware::metrics::mw_metrics;
+use crate::middleware::request_id::mw_request_id;
 use crate::model::AppState;
 
 pub fn new(state: Arc<AppState>) -> Router
@@ -24,17 +32,47 @@ pub fn new(state: Arc<AppState>) -> Router
             "/api",
             api::routes(state.clone()),
         )
+        .route(
+            "/metrics",
+            get(metrics::get_metrics).with_state(state.clone()),
+        )
+        .route_layer(
+            middleware::from_fn_with_state(
+                state.metrics.clone(),
+                mw_metrics,
+            ),
+        )
         .layer(
             middleware::map_response_with_state(
                 state.logs.clone(),
                 main_response_mapper,
             ),
         )
-        .layer(middleware::from_fn_with_state(state, mw_ctx_resolver))
+        .layer(middleware::from_fn_with_state(state.clone(), mw_ctx_resolver))
         .layer(CookieManagerLayer::new())
+        .layer(middleware::from_fn(
+            mw_request_id,
+        ))
+        //after the layers, probes dont need a session and would flood the logs
+        .route(
+            "/health/live",
+            get(health::get_live),
+        )
+        .route(
+            "/health/ready",
+            get(health::get_ready).with_state(state),
+        )
         .fallback(page_not_found)
 }
 
+/// the app of `HTTP_REDIRECT_SOCKET`, every request goes to `public_url`.
+pub fn https_redirect(public_url: &str) -> Router
+{
+    Router::new()
+        .fallback(redirect::to_public_url)
+        .with_state(Arc::<str>::from(public_url))
+}
+
 async fn page_not_found() -> impl IntoResponse
 {
     (
@@ -1,4 +1,3 @@
-use axum::error_handling::HandleErrorLayer;
 use axum::extract::{
     FromRequestParts,
     Path,
@@ -14,6 +13,7 @@ use axum::routing::{
     get,
     patch,
     post,
+    put,
 };
 use axum::{
     async_trait,
@@ -23,26 +23,24 @@ use axum::{
 };
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
-use tower::buffer::BufferLayer;
-use tower::limit::RateLimitLayer;
-use tower::{
-    BoxError,
-    ServiceBuilder,
-};
 
 use crate::middleware::auth::{
     mw_require_admin_authentication,
     mw_require_authentication,
 };
+use crate::middleware::rate_limit::mw_rate_limit;
+use crate::model::rate_limit::Group;
 use crate::model::AppState;
 
 mod auth;
 mod chat;
+mod log;
 mod message;
+mod presence;
 mod relation;
 mod server;
 mod user;
+mod webhook;
 
 pub fn routes(state: Arc<AppState>) -> Router
 {
@@ -56,39 +54,37 @@ pub fn routes(state: Arc<AppState>) -> Router
             "/admin/users",
             get(user::admin::get_users),
         )
+        //logs
+        .route(
+            "/admin/logs",
+            get(log::admin::get_logs),
+        )
+        .route(
+            "/admin/logs/export",
+            get(log::admin::export_logs),
+        )
         .with_state(state.clone())
         .route_layer(middleware::from_fn(
             mw_require_admin_authentication,
         ));
 
-    let routes_with_regular_middleware = Router::new()
-        //auth
-        .route(
-            "/auth/revoke",
-            delete(auth::authenticated::revoke_token),
-        )
-        .route(
-            "/auth/revoke/all",
-            delete(auth::authenticated::revoke_all_tokens),
-        )
-        //chat
+    let chat_routes = Router::new()
         .route(
             "/chat",
             post(chat::authenticated::create_chat),
         )
-        .route(
-            "/chat/:chat_id",
-            get(chat::authenticated::get_chat),
-        )
         .route(
             "/chat/:chat_id/users",
             post(chat::authenticated::add_users_to_chat),
         )
-        //messages
-        .route(
-            "/channels/:channel_id/messages",
-            get(message::authenticated::get_messages),
-        )
+        .route_layer(
+            middleware::from_fn_with_state(
+                (state.clone(), Group::Chat),
+                mw_rate_limit,
+            ),
+        );
+
+    let message_routes = Router::new()
         .route(
             "/channels/:channel_id/messages",
             post(message::authenticated::create_message),
@@ -97,7 +93,14 @@ pub fn routes(state: Arc<AppState>) -> Router
             "/channels/:channel_id/messages/:message_id",
             patch(message::authenticated::update_message),
         )
-        //relations
+        .route_layer(
+            middleware::from_fn_with_state(
+                (state.clone(), Group::Message),
+                mw_rate_limit,
+            ),
+        );
+
+    let relation_routes = Router::new()
         .route(
             "/users/friends",
             post(relation::authenticated::add_friend),
@@ -118,24 +121,145 @@ pub fn routes(state: Arc<AppState>) -> Router
             "/users/blocked",
             delete(relation::authenticated::remove_blocked),
         )
-        //servers
+        .route_layer(
+            middleware::from_fn_with_state(
+                (state.clone(), Group::Relation),
+                mw_rate_limit,
+            ),
+        );
+
+    let server_routes = Router::new()
         .route(
             "/servers",
             post(server::authenticated::create_server),
         )
         .route(
-            "/servers/:server_id",
-            get(server::authenticated::get_server),
+            "/servers/:server_id/discoverable",
+            patch(server::authenticated::update_server_discoverable),
         )
         .route(
             "/servers/:server_id/join",
             post(server::authenticated::join_server),
         )
+        .route_layer(
+            middleware::from_fn_with_state(
+                (state.clone(), Group::Server),
+                mw_rate_limit,
+            ),
+        );
+
+    let routes_with_regular_middleware = Router::new()
+        //auth
+        .route(
+            "/auth/revoke",
+            delete(auth::authenticated::revoke_token),
+        )
+        .route(
+            "/auth/revoke/all",
+            delete(auth::authenticated::revoke_all_tokens),
+        )
+        .route(
+            "/auth/sessions",
+            get(auth::authenticated::get_sessions),
+        )
+        .route(
+            "/auth/sessions/:device_id",
+            delete(auth::authenticated::revoke_session),
+        )
+        .route(
+            "/auth/two-factor",
+            post(auth::authenticated::enroll_two_factor),
+        )
+        .route(
+            "/auth/two-factor",
+            delete(auth::authenticated::disable_two_factor),
+        )
+        .route(
+            "/auth/two-factor/confirm",
+            post(auth::authenticated::confirm_two_factor),
+        )
+        //chat
+        .route(
+            "/chat/:chat_id",
+            get(chat::authenticated::get_chat),
+        )
+        //messages
+        .route(
+            "/channels/:channel_id/messages",
+            get(message::authenticated::get_messages),
+        )
+        //presence
+        .route(
+            "/users/presence",
+            put(presence::authenticated::update_presence),
+        )
+        .route(
+            "/users/friends/presence",
+            get(presence::authenticated::get_friends_presence),
+        )
+        .route(
+            "/servers/:server_id/presence",
+            get(presence::authenticated::get_server_presence),
+        )
+        .route(
+            "/channels/:channel_id/typing",
+            get(presence::authenticated::get_typing),
+        )
+        .route(
+            "/channels/:channel_id/typing",
+            post(presence::authenticated::start_typing),
+        )
+        //servers
+        .route(
+            "/servers/discover",
+            get(server::authenticated::get_server_directory),
+        )
+        .route(
+            "/servers/:server_id",
+            get(server::authenticated::get_server),
+        )
+        .route(
+            "/servers/:server_id/preview",
+            get(server::authenticated::get_server_preview),
+        )
+        //webhooks
+        .route(
+            "/servers/:server_id/webhooks",
+            post(webhook::authenticated::create_webhook),
+        )
+        .route(
+            "/servers/:server_id/webhooks",
+            get(webhook::authenticated::get_webhooks),
+        )
+        .route(
+            "/servers/:server_id/webhooks/:webhook_id",
+            delete(webhook::authenticated::delete_webhook),
+        )
+        .route(
+            "/servers/:server_id/webhooks/:webhook_id/deliveries",
+            get(webhook::authenticated::get_webhook_deliveries),
+        )
+        .route(
+            "/servers/:server_id/incoming-webhooks",
+            post(webhook::authenticated::create_incoming_webhook),
+        )
+        .route(
+            "/servers/:server_id/incoming-webhooks",
+            get(webhook::authenticated::get_incoming_webhooks),
+        )
+        .route(
+            "/servers/:server_id/incoming-webhooks/:webhook_id",
+            delete(webhook::authenticated::delete_incoming_webhook),
+        )
         //users
         .route(
             "/users/current",
             get(user::authenticated::get_ctx_user_auth),
         )
+        .merge(chat_routes)
+        .merge(message_routes)
+        .merge(relation_routes)
+        .merge(server_routes)
         .route_layer(middleware::from_fn(
             mw_require_authentication,
         ))
@@ -145,67 +269,66 @@ pub fn routes(state: Arc<AppState>) -> Router
         //auth
         .route(
             "/auth/login",
-            post(auth::login).layer(
-                ServiceBuilder::new()
-                    .layer(HandleErrorLayer::new(
-                        handle_too_many_requests,
-                    ))
-                    .layer(BufferLayer::new(1024))
-                    .layer(RateLimitLayer::new(
-                        Limit::Login.attempts(),
-                        Limit::Login.duration(),
-                    )),
-            ),
+            post(auth::login),
         )
         .route(
             "/auth/refresh",
             post(auth::refresh_token),
         )
+        .route(
+            "/auth/login/two-factor",
+            post(auth::login_two_factor),
+        )
+        .route(
+            "/auth/token",
+            post(auth::login_token),
+        )
+        .route(
+            "/auth/token/refresh",
+            post(auth::refresh_token_pair),
+        )
+        .route(
+            "/auth/jwks.json",
+            get(auth::get_jwks),
+        )
+        .route(
+            "/auth/verify-email",
+            post(auth::verify_email),
+        )
+        .route(
+            "/auth/verify-email/resend",
+            post(auth::resend_verification),
+        )
+        .route(
+            "/auth/password/forgot",
+            post(auth::request_password_reset),
+        )
+        .route(
+            "/auth/password/reset",
+            post(auth::reset_password),
+        )
         //users
         .route(
             "/users",
             post(user::create_user),
         )
-        .with_state(state);
+        //webhooks
+        .route(
+            "/webhooks/:webhook_id/:token",
+            post(webhook::execute_incoming_webhook),
+        )
+        .with_state(state.clone());
 
     Router::new()
         .merge(routes_with_admin_middleware)
         .merge(routes_with_regular_middleware)
         .merge(routes_without_middleware)
-}
-
-enum Limit
-{
-    Login,
-}
-
-impl Limit
-{
-    const MIN: u64 = 60;
-
-    fn attempts(&self) -> u64
-    {
-        match self
-        {
-            Limit::Login => 5,
-        }
-    }
-
-    fn duration(&self) -> Duration
-    {
-        match self
-        {
-            Limit::Login => Duration::from_secs(5 * Self::MIN),
-        }
-    }
-}
-
-async fn handle_too_many_requests(err: BoxError) -> (StatusCode, String)
-{
-    (
-        StatusCode::TOO_MANY_REQUESTS,
-        format!("To many requests: {err}"),
-    )
+        .route_layer(
+            middleware::from_fn_with_state(
+                (state, Group::Default),
+                mw_rate_limit,
+            ),
+        )
 }
 
 #[derive(Debug)]
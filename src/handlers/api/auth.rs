@@ -1,6 +1,14 @@
 pub mod authenticated;
+mod jwks;
 mod login;
+mod password_reset;
 mod refresh;
+mod token;
+mod verify_email;
 
+pub use jwks::*;
 pub use login::*;
+pub use password_reset::*;
 pub use refresh::*;
+pub use token::*;
+pub use verify_email::*;
@@ -1,5 +1,15 @@
+mod confirm_two_factor;
+mod disable_two_factor;
+mod enroll_two_factor;
+mod get_sessions;
 mod revoke_all_tokens;
+mod revoke_session;
 mod revoke_token;
 
+pub use confirm_two_factor::*;
+pub use disable_two_factor::*;
+pub use enroll_two_factor::*;
+pub use get_sessions::*;
 pub use revoke_all_tokens::*;
+pub use revoke_session::*;
 pub use revoke_token::*;
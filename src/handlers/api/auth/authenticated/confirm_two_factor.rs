@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::dto::{
+    ObjectToDTO,
+    TwoFactorRecoveryCodesResponse,
+};
+use crate::handlers::logic;
+use crate::handlers::logic::auth::TwoFactorCodeRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn confirm_two_factor(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse
+{
+    match logic::auth::authenticated::confirm_two_factor(&state, &ctx, &payload)
+        .await
+    {
+        Ok(recovery_codes) => Ok(Json(
+            TwoFactorRecoveryCodesResponse::obj_to_dto(recovery_codes),
+        )),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::TwoFactorCodeRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn disable_two_factor(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::authenticated::disable_two_factor(&state, &ctx, &payload).await
+}
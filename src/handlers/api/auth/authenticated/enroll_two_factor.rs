@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+
+use crate::dto::{
+    ObjectToDTO,
+    TwoFactorEnrollResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn enroll_two_factor(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+) -> impl IntoResponse
+{
+    match logic::auth::authenticated::enroll_two_factor(&state, &ctx).await
+    {
+        Ok(enrollment) => Ok(Json(
+            TwoFactorEnrollResponse::obj_to_dto(enrollment),
+        )),
+        Err(err) => Err(err),
+    }
+}
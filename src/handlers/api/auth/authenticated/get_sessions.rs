@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use tower_cookies::Cookies;
+
+use crate::dto::{
+    ObjectToDTO,
+    SessionGetResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn get_sessions(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    jar: Cookies,
+) -> impl IntoResponse
+{
+    let repo_refresh = &state.refresh_tokens;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let current_device_id =
+        logic::auth::authenticated::current_device_id(&ctx, &jar);
+
+    match repo_refresh.get_valid_tokens(ctx_user_id).await
+    {
+        Ok(refresh_tokens) => Ok(Json(
+            refresh_tokens
+                .into_iter()
+                .map(|refresh_token| {
+                    let is_current = current_device_id.as_deref()
+                        == Some(refresh_token.device_id.as_str());
+
+                    SessionGetResponse::obj_to_dto((refresh_token, is_current))
+                })
+                .collect::<Vec<_>>(),
+        )),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use tower_cookies::Cookies;
+
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn revoke_session(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    jar: Cookies,
+    Path(device_id): Path<String>,
+) -> impl IntoResponse
+{
+    logic::auth::authenticated::revoke_session(&state, &ctx, &jar, &device_id)
+        .await
+}
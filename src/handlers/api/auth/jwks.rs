@@ -0,0 +1,12 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::model::AppState;
+
+//public keys of the acces tokens, already in the standard jwks format
+pub async fn get_jwks(State(state): State<Arc<AppState>>) -> impl IntoResponse
+{
+    Json(state.acces_token_keys.jwks())
+}
@@ -1,29 +1,43 @@
-use axum::extract::{
-    ConnectInfo,
-    State,
-};
+use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Json;
-use std::net::SocketAddr;
 use std::sync::Arc;
 use tower_cookies::Cookies;
 
+use crate::dto::{
+    LoginResponse,
+    ObjectToDTO,
+};
 use crate::handlers::logic;
-use crate::handlers::logic::auth::LoginRequest;
+use crate::handlers::logic::auth::{
+    LoginRequest,
+    TwoFactorCodeRequest,
+};
+use crate::middleware::auth::ClientInfo;
 use crate::model::AppState;
 
 pub async fn login(
     State(state): State<Arc<AppState>>,
     jar: Cookies,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    client: ClientInfo,
     Json(payload): Json<LoginRequest>,
 ) -> impl IntoResponse
 {
-    logic::auth::login(
-        &state,
-        &jar,
-        addr.to_string(),
-        &payload,
-    )
-    .await
+    match logic::auth::login(&state, &jar, client, &payload).await
+    {
+        Ok(login_status) => Ok(Json(
+            LoginResponse::obj_to_dto(login_status),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+pub async fn login_two_factor(
+    State(state): State<Arc<AppState>>,
+    jar: Cookies,
+    client: ClientInfo,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::login_two_factor(&state, &jar, client, &payload).await
 }
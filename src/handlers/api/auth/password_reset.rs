@@ -0,0 +1,29 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::{
+    MailRequest,
+    ResetPasswordRequest,
+};
+use crate::middleware::auth::ClientInfo;
+use crate::model::AppState;
+
+pub async fn request_password_reset(
+    State(state): State<Arc<AppState>>,
+    client: ClientInfo,
+    Json(payload): Json<MailRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::request_password_reset(&state, &client, &payload).await
+}
+
+pub async fn reset_password(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::reset_password(&state, &payload).await
+}
@@ -4,13 +4,17 @@ use std::sync::Arc;
 use tower_cookies::Cookies;
 
 use crate::handlers;
-use crate::middleware::auth::Ctx;
+use crate::middleware::auth::{
+    ClientInfo,
+    Ctx,
+};
 use crate::model::AppState;
 
 pub async fn refresh_token(
     State(state): State<Arc<AppState>>,
     ctx_option: Option<Ctx>,
     jar: Cookies,
+    client: ClientInfo,
 ) -> impl IntoResponse
 {
     if ctx_option.is_some()
@@ -18,5 +22,5 @@ pub async fn refresh_token(
         return Ok(());
     }
 
-    handlers::logic::auth::refresh_token(&state, &jar).await
+    handlers::logic::auth::refresh_token(&state, &jar, client).await
 }
@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    ObjectToDTO,
+    TokenPairResponse,
+};
+use crate::handlers::logic;
+use crate::handlers::logic::auth::{
+    TokenLoginRequest,
+    TokenRefreshRequest,
+};
+use crate::middleware::auth::ClientInfo;
+use crate::model::AppState;
+
+pub async fn login_token(
+    State(state): State<Arc<AppState>>,
+    client: ClientInfo,
+    Json(payload): Json<TokenLoginRequest>,
+) -> impl IntoResponse
+{
+    match logic::auth::login_token_pair(&state, client, payload).await
+    {
+        Ok(token_pair) => Ok(Json(
+            TokenPairResponse::obj_to_dto(token_pair),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+pub async fn refresh_token_pair(
+    State(state): State<Arc<AppState>>,
+    client: ClientInfo,
+    Json(payload): Json<TokenRefreshRequest>,
+) -> impl IntoResponse
+{
+    match logic::auth::refresh_token_pair(&state, client, payload).await
+    {
+        Ok(token_pair) => Ok(Json(
+            TokenPairResponse::obj_to_dto(token_pair),
+        )),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,29 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::{
+    MailRequest,
+    MailTokenRequest,
+};
+use crate::middleware::auth::ClientInfo;
+use crate::model::AppState;
+
+pub async fn verify_email(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<MailTokenRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::verify_email(&state, &payload).await
+}
+
+pub async fn resend_verification(
+    State(state): State<Arc<AppState>>,
+    client: ClientInfo,
+    Json(payload): Json<MailRequest>,
+) -> impl IntoResponse
+{
+    logic::auth::resend_verification(&state, &client, &payload).await
+}
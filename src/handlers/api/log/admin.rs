@@ -0,0 +1,13 @@
+mod export_logs;
+mod get_logs;
+
+pub use export_logs::*;
+pub use get_logs::*;
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct LogLimit
+{
+    limit: usize,
+}
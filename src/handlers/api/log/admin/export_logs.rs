@@ -0,0 +1,61 @@
+use axum::extract::{
+    Query,
+    State,
+};
+use axum::http::header;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+use crate::model::log::Filter;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::LogLimit;
+
+/// same search as `get_logs`, as newline delimited json to download.
+pub async fn export_logs(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<Filter>,
+    limit: Option<Query<LogLimit>>,
+) -> impl IntoResponse
+{
+    let limit = limit.map_or(
+        Filter::MAX_EXPORT_LIMIT,
+        |Query(limit)| limit.limit.min(Filter::MAX_EXPORT_LIMIT),
+    );
+
+    let entries = state.log_reader.get_logs(&filter, limit).await?;
+
+    let mut body = String::new();
+
+    for entry in entries
+    {
+        let line = serde_json::to_string(&entry).map_err(|err| {
+            server_error!(
+                error::Kind::Parse,
+                error::OnType::Log
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        body.push_str(&line);
+        body.push('\n');
+    }
+
+    Ok::<_, error::Server<'static>>((
+        [
+            (
+                header::CONTENT_TYPE,
+                "application/x-ndjson",
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"logs.ndjson\"",
+            ),
+        ],
+        body,
+    ))
+}
@@ -0,0 +1,30 @@
+use axum::extract::{
+    Query,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::model::log::Filter;
+use crate::model::AppState;
+
+use super::LogLimit;
+
+pub async fn get_logs(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<Filter>,
+    limit: Option<Query<LogLimit>>,
+) -> impl IntoResponse
+{
+    let limit = limit.map_or(
+        Filter::DEFAULT_LIMIT,
+        |Query(limit)| limit.limit.min(Filter::MAX_LIMIT),
+    );
+
+    match state.log_reader.get_logs(&filter, limit).await
+    {
+        Ok(entries) => Ok(Json(entries)),
+        Err(e) => Err(e),
+    }
+}
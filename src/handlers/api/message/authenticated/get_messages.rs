@@ -31,7 +31,10 @@ pub async fn get_messages(
     let repo_message = &state.messages;
     let repo_parent = &state.channel_parents;
 
-    let pagination = Pagination::new(pagination);
+    let pagination = Pagination::new(
+        pagination,
+        state.config.pagination,
+    );
     let current_user_id = ctx.user_id_ref();
 
     let chat = repo_parent.get_channel_parent(&channel_id).await?;
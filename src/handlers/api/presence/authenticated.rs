@@ -0,0 +1,11 @@
+mod get_friends_presence;
+mod get_server_presence;
+mod get_typing;
+mod start_typing;
+mod update_presence;
+
+pub use get_friends_presence::*;
+pub use get_server_presence::*;
+pub use get_typing::*;
+pub use start_typing::*;
+pub use update_presence::*;
@@ -0,0 +1,36 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    PresenceGetResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::presence::Presence;
+use crate::model::AppState;
+
+pub async fn get_friends_presence(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+) -> impl IntoResponse
+{
+    let repo_relation = &state.relations;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let friend_ids = repo_relation.get_friend_ids(ctx_user_id).await?;
+
+    let friend_ids: Vec<&str> = friend_ids.iter().map(AsRef::as_ref).collect();
+
+    match logic::presence::get_presences(&state, friend_ids).await
+    {
+        Ok(presences) => Ok(Json(vec_to_dto::<
+            Presence,
+            PresenceGetResponse,
+        >(presences))),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,55 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    PresenceGetResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::presence::Presence;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+pub async fn get_server_presence(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path(server_id): Path<String>,
+) -> impl IntoResponse
+{
+    let repo_server = &state.servers;
+
+    let server = repo_server.get_server_by_id(&server_id).await?;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    if !server.is_user_part_of_server(ctx_user_id)
+    {
+        return Err(server_error!(
+            error::Kind::NotPartOf,
+            error::OnType::Server
+        )
+        .add_client(error::Client::SERVER_CTX_NOT_PART_OF_SERVER));
+    }
+
+    let member_ids: Vec<&str> = std::iter::once(server.owner.id.as_str())
+        .chain(server.users.keys().map(AsRef::as_ref))
+        .collect();
+
+    match logic::presence::get_presences(&state, member_ids).await
+    {
+        Ok(presences) => Ok(Json(vec_to_dto::<
+            Presence,
+            PresenceGetResponse,
+        >(presences))),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,58 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    TypingGetResponse,
+};
+use crate::middleware::auth::Ctx;
+use crate::model::channel::Parent;
+use crate::model::presence::Typing;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+pub async fn get_typing(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<String>,
+    ctx: Ctx,
+) -> impl IntoResponse
+{
+    let repo_presence = &state.presences;
+    let repo_parent = &state.channel_parents;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let channel_parent = repo_parent.get_channel_parent(&channel_id).await?;
+
+    if !channel_parent.is_user_part_of_channel_parent(ctx_user_id)
+        || !channel_parent.can_read(ctx_user_id, Some(&channel_id))?
+    {
+        return Err(server_error!(
+            error::Kind::NotPartOf,
+            error::OnType::ChannelParent
+        )
+        .add_client(error::Client::CHAT_PARENT_CTX_NOT_PART_OF_PARENT));
+    }
+
+    match repo_presence.get_typing(&channel_id).await
+    {
+        Ok(typings) => Ok(Json(vec_to_dto::<
+            Typing,
+            TypingGetResponse,
+        >(
+            typings
+                .into_iter()
+                .filter(|typing| typing.user_id != ctx_user_id)
+                .collect(),
+        ))),
+        Err(err) => Err(err),
+    }
+}
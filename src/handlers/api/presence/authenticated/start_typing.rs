@@ -0,0 +1,49 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::channel::Parent;
+use crate::model::presence::Typing;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+pub async fn start_typing(
+    State(state): State<Arc<AppState>>,
+    Path(channel_id): Path<String>,
+    ctx: Ctx,
+) -> impl IntoResponse
+{
+    let repo_presence = &state.presences;
+    let repo_parent = &state.channel_parents;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let channel_parent = repo_parent.get_channel_parent(&channel_id).await?;
+
+    if !channel_parent.can_write(ctx_user_id, Some(&channel_id))?
+    {
+        return Err(server_error!(
+            error::Kind::NotAllowed,
+            error::OnType::ChannelParent
+        )
+        .add_client(error::Client::MESSAGE_CREATE_FAIL));
+    }
+
+    let typing = Typing::new(
+        ctx_user_id.to_string(),
+        channel_id,
+    );
+
+    match repo_presence.upsert_typing(typing).await
+    {
+        Ok(()) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,40 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::dto::{
+    ObjectToDTO,
+    PresenceGetResponse,
+};
+use crate::middleware::auth::Ctx;
+use crate::model::presence::{
+    self,
+    Presence,
+};
+use crate::model::AppState;
+
+#[derive(Deserialize)]
+pub struct UpdatePresenceRequest
+{
+    status: presence::Status,
+}
+pub async fn update_presence(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Json(payload): Json<UpdatePresenceRequest>,
+) -> impl IntoResponse
+{
+    let repo_presence = &state.presences;
+
+    let presence = Presence::new(ctx.user_id(), payload.status);
+
+    match repo_presence.upsert_presence(presence).await
+    {
+        Ok(presence) => Ok(Json(
+            PresenceGetResponse::obj_to_dto(presence),
+        )),
+        Err(err) => Err(err),
+    }
+}
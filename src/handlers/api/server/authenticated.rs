@@ -1,7 +1,13 @@
 mod create_server;
 mod get_server;
+mod get_server_directory;
+mod get_server_preview;
 mod join_server;
+mod update_server_discoverable;
 
 pub use create_server::*;
 pub use get_server::*;
+pub use get_server_directory::*;
+pub use get_server_preview::*;
 pub use join_server::*;
+pub use update_server_discoverable::*;
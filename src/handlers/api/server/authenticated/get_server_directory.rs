@@ -0,0 +1,51 @@
+use axum::extract::{
+    Query,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    ServerDirectoryResponse,
+};
+use crate::model::channel_parent::server::Listing;
+use crate::model::{
+    AppState,
+    Pagination,
+};
+
+#[derive(Deserialize)]
+pub struct ServerDirectoryQuery
+{
+    name: Option<String>,
+}
+pub async fn get_server_directory(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ServerDirectoryQuery>,
+    pagination: Option<Query<Pagination>>,
+) -> impl IntoResponse
+{
+    let repo_server = &state.servers;
+
+    let pagination = Pagination::new(
+        pagination,
+        state.config.pagination,
+    );
+
+    match repo_server
+        .get_discoverable_servers(
+            query.name.as_deref(),
+            pagination,
+        )
+        .await
+    {
+        Ok(listings) => Ok(Json(vec_to_dto::<
+            Listing,
+            ServerDirectoryResponse,
+        >(listings))),
+        Err(err) => Err(err),
+    }
+}
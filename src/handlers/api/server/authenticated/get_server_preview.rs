@@ -0,0 +1,43 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    ObjectToDTO,
+    ServerPreviewResponse,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+pub async fn get_server_preview(
+    State(state): State<Arc<AppState>>,
+    Path(server_id): Path<String>,
+) -> impl IntoResponse
+{
+    let repo_server = &state.servers;
+
+    let server = repo_server.get_server_by_id(&server_id).await?;
+
+    if !server.discoverable
+    {
+        return Err(server_error!(
+            error::Kind::NotAllowed,
+            error::OnType::Server
+        )
+        .add_client(error::Client::SERVER_NOT_DISCOVERABLE)
+        .add_debug_info("server id", server_id));
+    }
+
+    let server = server.filter_channels_for_preview();
+
+    Ok(Json(
+        ServerPreviewResponse::obj_to_dto(server),
+    ))
+}
@@ -0,0 +1,56 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct UpdateServerDiscoverableRequest
+{
+    discoverable: bool,
+}
+pub async fn update_server_discoverable(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path(server_id): Path<String>,
+    Json(payload): Json<UpdateServerDiscoverableRequest>,
+) -> impl IntoResponse
+{
+    let repo_server = &state.servers;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let mut server = repo_server.get_server_by_id(&server_id).await?;
+
+    if !server.set_discoverable(
+        ctx_user_id,
+        payload.discoverable,
+    )?
+    {
+        return Err(server_error!(
+            error::Kind::NoChange,
+            error::OnType::Server
+        ));
+    }
+
+    match repo_server
+        .update_server_discoverable(
+            &server_id,
+            server.discoverable,
+        )
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
@@ -23,7 +23,10 @@ pub async fn get_users(
 {
     let repo_user = &state.users;
 
-    let pagination = Pagination::new(pagination);
+    let pagination = Pagination::new(
+        pagination,
+        state.config.pagination,
+    );
 
     match repo_user.get_users(pagination).await
     {
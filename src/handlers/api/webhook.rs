@@ -0,0 +1,4 @@
+pub mod authenticated;
+mod execute_incoming_webhook;
+
+pub use execute_incoming_webhook::*;
@@ -0,0 +1,15 @@
+mod create_incoming_webhook;
+mod create_webhook;
+mod delete_incoming_webhook;
+mod delete_webhook;
+mod get_incoming_webhooks;
+mod get_webhook_deliveries;
+mod get_webhooks;
+
+pub use create_incoming_webhook::*;
+pub use create_webhook::*;
+pub use delete_incoming_webhook::*;
+pub use delete_webhook::*;
+pub use get_incoming_webhooks::*;
+pub use get_webhook_deliveries::*;
+pub use get_webhooks::*;
@@ -0,0 +1,38 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    IncomingWebhookCreateResponse,
+    ObjectToDTO,
+};
+use crate::handlers::logic;
+use crate::handlers::logic::webhook::CreateIncomingWebhookRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn create_incoming_webhook(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path(server_id): Path<String>,
+    Json(payload): Json<CreateIncomingWebhookRequest>,
+) -> impl IntoResponse
+{
+    match logic::webhook::create_incoming_webhook(
+        &state,
+        &ctx,
+        &server_id,
+        payload,
+    )
+    .await
+    {
+        Ok(webhook_and_token) => Ok(Json(
+            IncomingWebhookCreateResponse::obj_to_dto(webhook_and_token),
+        )),
+        Err(err) => Err(err),
+    }
+}
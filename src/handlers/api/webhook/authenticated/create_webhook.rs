@@ -0,0 +1,38 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    ObjectToDTO,
+    WebhookCreateResponse,
+};
+use crate::handlers::logic;
+use crate::handlers::logic::webhook::CreateWebhookRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path(server_id): Path<String>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> impl IntoResponse
+{
+    match logic::webhook::create_webhook(
+        &state,
+        &ctx,
+        &server_id,
+        payload,
+    )
+    .await
+    {
+        Ok(webhook) => Ok(Json(
+            WebhookCreateResponse::obj_to_dto(webhook),
+        )),
+        Err(err) => Err(err),
+    }
+}
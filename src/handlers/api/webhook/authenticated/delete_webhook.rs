@@ -0,0 +1,27 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::AppState;
+
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path((server_id, webhook_id)): Path<(String, String)>,
+) -> impl IntoResponse
+{
+    let repo_webhook = &state.webhooks;
+
+    logic::webhook::get_server_as_owner(&state, &ctx, &server_id).await?;
+
+    match repo_webhook.delete_webhook(&server_id, &webhook_id).await
+    {
+        Ok(()) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
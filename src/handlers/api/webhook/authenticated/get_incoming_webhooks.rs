@@ -0,0 +1,38 @@
+use axum::extract::{
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    IncomingWebhookGetResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::webhook::Incoming;
+use crate::model::AppState;
+
+pub async fn get_incoming_webhooks(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path(server_id): Path<String>,
+) -> impl IntoResponse
+{
+    let repo_webhook = &state.webhooks;
+
+    logic::webhook::get_server_as_owner(&state, &ctx, &server_id).await?;
+
+    match repo_webhook
+        .get_incoming_webhooks_by_server(&server_id)
+        .await
+    {
+        Ok(webhooks) => Ok(Json(vec_to_dto::<
+            Incoming,
+            IncomingWebhookGetResponse,
+        >(webhooks))),
+        Err(err) => Err(err),
+    }
+}
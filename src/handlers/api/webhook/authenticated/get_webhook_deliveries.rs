@@ -0,0 +1,49 @@
+use axum::extract::{
+    Path,
+    Query,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    vec_to_dto,
+    WebhookDeliveryGetResponse,
+};
+use crate::handlers::logic;
+use crate::middleware::auth::Ctx;
+use crate::model::webhook::Delivery;
+use crate::model::{
+    AppState,
+    Pagination,
+};
+
+pub async fn get_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    ctx: Ctx,
+    Path((server_id, webhook_id)): Path<(String, String)>,
+    pagination: Option<Query<Pagination>>,
+) -> impl IntoResponse
+{
+    let repo_webhook = &state.webhooks;
+
+    let pagination = Pagination::new(
+        pagination,
+        state.config.pagination,
+    );
+
+    logic::webhook::get_server_as_owner(&state, &ctx, &server_id).await?;
+
+    //makes sure the webhook belongs to the server
+    repo_webhook.get_webhook(&server_id, &webhook_id).await?;
+
+    match repo_webhook.get_deliveries(&webhook_id, pagination).await
+    {
+        Ok(deliveries) => Ok(Json(vec_to_dto::<
+            Delivery,
+            WebhookDeliveryGetResponse,
+        >(deliveries))),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,38 @@
+use axum::extract::{
+    self,
+    Path,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Json;
+use std::sync::Arc;
+
+use crate::dto::{
+    MessageCreateResponse,
+    ObjectToDTO,
+};
+use crate::handlers::logic;
+use crate::handlers::logic::webhook::ExecuteIncomingWebhookRequest;
+use crate::model::AppState;
+
+//no session, the token in the path is the authentication
+pub async fn execute_incoming_webhook(
+    State(state): State<Arc<AppState>>,
+    Path((webhook_id, token)): Path<(String, String)>,
+    extract::Json(payload): extract::Json<ExecuteIncomingWebhookRequest>,
+) -> impl IntoResponse
+{
+    match logic::webhook::execute_incoming_webhook(
+        &state,
+        &webhook_id,
+        &token,
+        payload,
+    )
+    .await
+    {
+        Ok(message) => Ok(Json(
+            MessageCreateResponse::obj_to_dto(message),
+        )),
+        Err(err) => Err(err),
+    }
+}
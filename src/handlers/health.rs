@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde_json::json;
+
+use crate::model::health::{
+    Report,
+    Status,
+};
+use crate::model::AppState;
+
+const CHECK_TIMEOUT_SEC: u64 = 2;
+
+/// the process is up and serving, restart it when this fails.
+pub async fn get_live() -> impl IntoResponse
+{
+    Json(json!({ "status": Status::Ok }))
+}
+
+/// every dependency answers, only route traffic here when this succeeds.
+pub async fn get_ready(State(state): State<Arc<AppState>>)
+    -> impl IntoResponse
+{
+    let report = Report::run(
+        &state.health_checks,
+        Duration::from_secs(CHECK_TIMEOUT_SEC),
+    )
+    .await;
+
+    let status_code = match report.status
+    {
+        Status::Ok => StatusCode::OK,
+        Status::Failing => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    (status_code, Json(report))
+}
@@ -1,2 +1,8 @@
 pub mod auth;
+pub mod chat;
+pub mod message;
+pub mod presence;
+pub mod relation;
+pub mod server;
 pub mod user;
+pub mod webhook;
@@ -1,7 +1,17 @@
 pub mod authenticated;
 pub mod cookies;
 mod login;
+mod oidc;
+mod password_reset;
 mod refresh;
+mod token;
+mod two_factor;
+mod verification;
 
 pub use login::*;
+pub use oidc::*;
+pub use password_reset::*;
 pub use refresh::*;
+pub use token::*;
+pub use two_factor::*;
+pub use verification::*;
@@ -1,3 +1,31 @@
+mod confirm_two_factor;
+mod disable_two_factor;
+mod enroll_two_factor;
+mod revoke_session;
 mod revoke_token;
 
+pub use confirm_two_factor::*;
+pub use disable_two_factor::*;
+pub use enroll_two_factor::*;
+pub use revoke_session::*;
 pub use revoke_token::*;
+
+use tower_cookies::Cookies;
+
+use crate::middleware::auth::{
+    self,
+    Ctx,
+};
+use crate::middleware::cookies::Manager;
+
+//older acces tokens dont carry the device, the cookie still knows it
+#[must_use]
+pub fn current_device_id(
+    ctx: &Ctx,
+    jar: &Cookies,
+) -> Option<String>
+{
+    ctx.device_id_ref()
+        .map(str::to_string)
+        .or_else(|| jar.get_cookie(auth::CookieNames::DEVICE_ID.as_str()).ok())
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::TwoFactorCodeRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::two_factor::TwoFactor;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+/// enables two factor once the authenticator app proved to have the secret.
+///
+/// returns the plain recovery codes, only their hashes are kept.
+pub async fn confirm_two_factor<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    payload: &TwoFactorCodeRequest,
+) -> error::Result<'err, Vec<String>>
+{
+    let repo_two_factor = &state.two_factors;
+
+    let mut two_factor = repo_two_factor
+        .get_two_factor(ctx.user_id_ref())
+        .await
+        .map_err(|err| {
+            server_error!(err).add_client(error::Client::TWO_FACTOR_NOT_ENABLED)
+        })?;
+
+    if two_factor.is_enabled
+    {
+        return Err(server_error!(
+            error::Kind::AlreadyExists,
+            error::OnType::TwoFactor
+        )
+        .add_client(error::Client::TWO_FACTOR_ALREADY_ENABLED));
+    }
+
+    logic::auth::check_two_factor_code(
+        state,
+        &mut two_factor,
+        payload.code_ref(),
+    )
+    .await?;
+
+    let recovery_codes = TwoFactor::generate_recovery_codes();
+
+    let mut recovery_code_hashes = Vec::with_capacity(recovery_codes.len());
+    for recovery_code in &recovery_codes
+    {
+        recovery_code_hashes
+            .push(state.hashing.hash_text(recovery_code).await?);
+    }
+
+    two_factor.recovery_code_hashes = recovery_code_hashes;
+    two_factor.is_enabled = true;
+
+    //a concurrent confirm would hand out recovery codes that get overwritten
+    repo_two_factor
+        .enable_two_factor(&two_factor)
+        .await
+        .map_err(|err| match err.kind
+        {
+            error::Kind::AlreadyExists => server_error!(err)
+                .add_client(error::Client::TWO_FACTOR_ALREADY_ENABLED),
+            _ => err,
+        })?;
+
+    Ok(recovery_codes)
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::TwoFactorCodeRequest;
+use crate::middleware::auth::Ctx;
+use crate::model::{
+    error,
+    AppState,
+};
+
+//asks for a code, a stolen session alone shouldnt be able to turn it off
+pub async fn disable_two_factor<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    payload: &TwoFactorCodeRequest,
+) -> error::Result<'err, ()>
+{
+    let ctx_user_id = ctx.user_id_ref();
+
+    logic::auth::verify_two_factor(
+        state,
+        ctx_user_id,
+        payload.code_ref(),
+    )
+    .await?;
+
+    state.two_factors.delete_two_factor(ctx_user_id).await
+}
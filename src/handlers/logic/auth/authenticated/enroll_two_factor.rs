@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::two_factor::TwoFactor;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+/// starts over with a new secret, only counts once confirmed with a code.
+///
+/// returns the provisioning uri next to the two factor.
+pub async fn enroll_two_factor<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+) -> error::Result<'err, (TwoFactor, String)>
+{
+    let repo_user = &state.users;
+    let repo_two_factor = &state.two_factors;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    if let Ok(two_factor) = repo_two_factor.get_two_factor(ctx_user_id).await
+    {
+        if two_factor.is_enabled
+        {
+            return Err(server_error!(
+                error::Kind::AlreadyExists,
+                error::OnType::TwoFactor
+            )
+            .add_client(error::Client::TWO_FACTOR_ALREADY_ENABLED));
+        }
+    }
+
+    let user = repo_user.get_user_by_id(ctx_user_id).await?;
+
+    let two_factor = repo_two_factor
+        .create_two_factor(TwoFactor::new(user.id))
+        .await?;
+
+    let provisioning_uri = two_factor.provisioning_uri(&user.email);
+
+    Ok((two_factor, provisioning_uri))
+}
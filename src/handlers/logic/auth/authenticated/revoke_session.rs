@@ -0,0 +1,38 @@
+use std::sync::Arc;
+use tower_cookies::Cookies;
+
+use crate::middleware::auth::Ctx;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::current_device_id;
+
+//logging out the current device goes through `revoke_token`
+pub async fn revoke_session<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    jar: &Cookies,
+    device_id: &str,
+) -> error::Result<'err, ()>
+{
+    let repo_refresh = &state.refresh_tokens;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    if current_device_id(ctx, jar).as_deref() == Some(device_id)
+    {
+        return Err(server_error!(
+            error::Kind::NotAllowed,
+            error::OnType::RefreshToken
+        )
+        .add_client(error::Client::SESSION_REVOKE_CURRENT));
+    }
+
+    //makes sure the device is an active session of the user
+    repo_refresh.get_valid_token(device_id, ctx_user_id).await?;
+
+    repo_refresh.revoke_token(ctx_user_id, device_id).await
+}
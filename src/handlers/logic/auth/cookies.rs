@@ -2,8 +2,10 @@ use std::sync::Arc;
 
 use tower_cookies::Cookies;
 
+use crate::handlers::logic;
 use crate::middleware::auth::{
     self,
+    ClientInfo,
     CreateAccesTokenRequest,
 };
 use crate::middleware::cookies::Manager;
@@ -15,17 +17,23 @@ use crate::model::{
 };
 
 pub fn create_auth_cookies<'err>(
+    state: &Arc<AppState>,
     jar: &Cookies,
     refresh_token: RefreshToken,
 ) -> error::Result<'err, ()>
 {
-    let user = refresh_token.owner;
+    let user = &refresh_token.owner;
     let create_token_request = CreateAccesTokenRequest::new(
         &user.id,
         user.flag.is_mogcord_admin_or_owner(),
+        &refresh_token.device_id,
     );
 
-    match auth::create_acces_token(&create_token_request)
+    match auth::create_acces_token(
+        &state.acces_token_keys,
+        &create_token_request,
+        state.config.acces_token_ttl(),
+    )
     {
         Ok(acces_token) =>
         {
@@ -60,7 +68,7 @@ pub fn create_auth_cookies<'err>(
 pub async fn get_refresh_token<'err>(
     state: &Arc<AppState>,
     jar: &Cookies,
-    ip_addr: String,
+    client: ClientInfo,
     user: User,
 ) -> error::Result<'err, RefreshToken>
 {
@@ -68,31 +76,14 @@ pub async fn get_refresh_token<'err>(
     //1: if user has a device id, db lookup for token and use that if it exists.
     //2: say frog it and keep genning new ones
     //more compelled to use option 1 since gives more control to suspend accounts
-    let repo_refresh = &state.refresh_tokens;
-
-    let device_id_cookie_result =
-        jar.get_cookie(auth::CookieNames::DEVICE_ID.as_str());
-
-    match device_id_cookie_result
-    {
-        Ok(cookie_id) => match repo_refresh
-            .get_valid_token(&cookie_id, &user.id)
-            .await
-        {
-            Ok(db_refresh_token) => Ok(db_refresh_token),
-            Err(_) =>
-            {
-                let refresh_token =
-                    RefreshToken::create_token(user, ip_addr, Some(cookie_id));
+    let device_id_option =
+        jar.get_cookie(auth::CookieNames::DEVICE_ID.as_str()).ok();
 
-                repo_refresh.create_token(refresh_token).await
-            },
-        },
-        Err(_) =>
-        {
-            let refresh_token = RefreshToken::create_token(user, ip_addr, None);
-
-            repo_refresh.create_token(refresh_token).await
-        },
-    }
+    logic::auth::get_or_create_refresh_token(
+        state,
+        device_id_option,
+        client,
+        user,
+    )
+    .await
 }
@@ -3,10 +3,19 @@ use std::sync::Arc;
 use tower_cookies::Cookies;
 
 use crate::handlers::logic;
+use crate::middleware::auth::{
+    self,
+    ClientInfo,
+};
+use crate::middleware::cookies::Manager;
+use crate::model::refresh_token::RefreshToken;
+use crate::model::user::{
+    Flag,
+    User,
+};
 use crate::model::{
     error,
     AppState,
-    Hashing,
 };
 use crate::server_error;
 
@@ -32,22 +41,118 @@ impl LoginRequest
     }
 }
 
+pub enum LoginStatus
+{
+    LoggedIn,
+    //continue with `login_two_factor`
+    TwoFactorRequired,
+}
+
 pub async fn login<'err>(
     state: &Arc<AppState>,
     jar: &Cookies,
-    ip_addr: String,
+    client: ClientInfo,
     payload: &LoginRequest,
-) -> error::Result<'err, ()>
+) -> error::Result<'err, LoginStatus>
+{
+    let user = verify_login(
+        state,
+        &client,
+        &payload.email,
+        &payload.password,
+    )
+    .await?;
+
+    start_session(state, jar, client, user).await
+}
+
+/// the user is who they claim to be, hands out the session cookies
+/// or the two factor cookie when a second step is needed.
+pub(crate) async fn start_session<'err>(
+    state: &Arc<AppState>,
+    jar: &Cookies,
+    client: ClientInfo,
+    user: User,
+) -> error::Result<'err, LoginStatus>
+{
+    if logic::auth::is_two_factor_enabled(state, &user.id).await?
+    {
+        let two_factor_token = auth::create_two_factor_token(
+            &state.acces_token_keys,
+            &user.id,
+        )?;
+
+        let cookie_name = auth::CookieNames::TWO_FACTOR;
+        jar.create_cookie(
+            cookie_name.to_string(),
+            two_factor_token,
+            cookie_name.ttl_in_mins(),
+        );
+
+        return Ok(LoginStatus::TwoFactorRequired);
+    }
+
+    let refresh_token =
+        logic::auth::cookies::get_refresh_token(state, jar, client, user)
+            .await?;
+
+    logic::auth::cookies::create_auth_cookies(state, jar, refresh_token)?;
+
+    Ok(LoginStatus::LoggedIn)
+}
+
+//shared by the cookie and token login
+pub(super) async fn verify_login<'err>(
+    state: &Arc<AppState>,
+    client: &ClientInfo,
+    email: &str,
+    password: &str,
+) -> error::Result<'err, User>
 {
     let repo_user = &state.users;
 
-    let user =
-        repo_user
-            .get_user_by_mail(&payload.email)
-            .await
-            .map_err(|err| {
+    let lockout_key = User::normalize_email(email);
+
+    check_login_attempt(state, client, &lockout_key)?;
+
+    let user = match repo_user.get_user_by_mail(email).await
+    {
+        Ok(user) => user,
+        Err(err) =>
+        {
+            //unknown mails count too, or the lockout tells which ones exist
+            state.login_lockout.register_failure(&lockout_key);
+
+            return Err(
                 server_error!(err).add_client(error::Client::INVALID_PARAMS)
-            })?;
+            );
+        },
+    };
+
+    state
+        .hashing
+        .verify_hash(
+            password,
+            &user.hashed_password,
+        )
+        .await
+        .map_err(|err| {
+            state.login_lockout.register_failure(&lockout_key);
+
+            server_error!(err).add_client(error::Client::INVALID_PARAMS)
+        })?;
+
+    state.login_lockout.reset(&lockout_key);
+
+    //only after the password, or the flag tells anyone who has an account
+    if user.flag == Flag::Unverified
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::User
+        )
+        .add_client(error::Client::EMAIL_NOT_VERIFIED));
+    }
 
     if !user.flag.is_allowed_on_mogcord()
     {
@@ -62,18 +167,98 @@ pub async fn login<'err>(
         ));
     }
 
-    Hashing::verify_hash(
-        &payload.password,
-        &user.hashed_password,
-    )
-    .await
-    .map_err(|err| {
-        server_error!(err).add_client(error::Client::INVALID_PARAMS)
-    })?;
+    //only now the clear password is around to upgrade an outdated hash
+    if state.hashing.needs_rehash(&user.hashed_password)
+    {
+        let rehash_result = match state.hashing.hash_text(password).await
+        {
+            Ok(hashed_password) =>
+            {
+                repo_user
+                    .update_user_hashed_password(&user.id, hashed_password)
+                    .await
+            },
+            Err(err) => Err(err),
+        };
 
-    let refresh_token =
-        logic::auth::cookies::get_refresh_token(state, jar, ip_addr, user)
-            .await?;
+        if let Err(err) = rehash_result
+        {
+            tracing::error!(error = %err, "couldnt rehash password");
+        }
+    }
+
+    Ok(user)
+}
+
+/// sliding window per ip and per email, on top of the lockout per email.
+fn check_login_attempt<'err>(
+    state: &Arc<AppState>,
+    client: &ClientInfo,
+    lockout_key: &str,
+) -> error::Result<'err, ()>
+{
+    if let Err(retry_after) = state.login_lockout.check(lockout_key)
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::User
+        )
+        .add_client(error::Client::LOGIN_LOCKED)
+        .add_retry_after(retry_after));
+    }
+
+    let limited = state
+        .login_ip_limiter
+        .hit(&client.ip_addr)
+        .and_then(|()| state.login_email_limiter.hit(lockout_key));
+
+    if let Err(retry_after) = limited
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::User
+        )
+        .add_client(error::Client::LOGIN_RATE_LIMITED)
+        .add_debug_info("ip", client.ip_addr.clone())
+        .add_retry_after(retry_after));
+    }
+
+    Ok(())
+}
+
+/// reuses the valid refresh token of the device if there is one,
+/// otherwise creates a new one (for a new device when no id is given).
+pub async fn get_or_create_refresh_token<'err>(
+    state: &Arc<AppState>,
+    device_id_option: Option<String>,
+    client: ClientInfo,
+    user: User,
+) -> error::Result<'err, RefreshToken>
+{
+    let repo_refresh = &state.refresh_tokens;
+
+    if let Some(device_id) = &device_id_option
+    {
+        if let Ok(mut db_refresh_token) =
+            repo_refresh.get_valid_token(device_id, &user.id).await
+        {
+            db_refresh_token.mark_used(
+                client.ip_addr,
+                client.user_agent,
+            );
+            repo_refresh.update_token(&db_refresh_token).await?;
+
+            return Ok(db_refresh_token);
+        }
+    }
+
+    let refresh_token = RefreshToken::create_token(
+        user,
+        client.ip_addr,
+        client.user_agent,
+        device_id_option,
+        state.config.refresh_token_ttl(),
+    );
 
-    logic::auth::cookies::create_auth_cookies(jar, refresh_token)
+    repo_refresh.create_token(refresh_token).await
 }
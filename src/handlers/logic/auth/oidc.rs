@@ -0,0 +1,359 @@
+use serde::Deserialize;
+use std::sync::Arc;
+use tower_cookies::Cookies;
+use uuid::Uuid;
+
+use crate::io::oidc::{
+    AuthorizationRequest,
+    IdentityClaims,
+    Provider,
+};
+use crate::middleware::auth::{
+    self,
+    ClientInfo,
+};
+use crate::middleware::cookies::Manager;
+use crate::model::external_identity::ExternalIdentity;
+use crate::model::user::{
+    Flag,
+    User,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::login::{
+    start_session,
+    LoginStatus,
+};
+
+const USERNAME_MAX_LENGTH: usize = 32;
+const USERNAME_ATTEMPTS: usize = 5;
+
+//what the provider appends to the redirect uri
+#[derive(Deserialize)]
+pub struct OidcCallbackRequest
+{
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    //set instead of the code when the user cancelled at the provider
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// remembers the attempt in a cookie and returns where to send the browser.
+pub async fn start_oidc_login<'err>(
+    state: &Arc<AppState>,
+    jar: &Cookies,
+    provider_name: &str,
+) -> error::Result<'err, String>
+{
+    let provider = get_provider(state, provider_name)?;
+
+    let request = AuthorizationRequest::new();
+
+    let url = provider
+        .authorization_url(
+            &redirect_uri(state, provider),
+            &request,
+        )
+        .await?;
+
+    let oidc_token = auth::create_oidc_token(
+        &state.acces_token_keys,
+        &provider.name,
+        &request,
+    )?;
+
+    let cookie_name = auth::CookieNames::OIDC;
+    jar.create_cookie(
+        cookie_name.to_string(),
+        oidc_token,
+        cookie_name.ttl_in_mins(),
+    );
+
+    Ok(url)
+}
+
+/// the provider sent the browser back, trades the code for an identity
+/// and logs in the user linked to it, creating one on first login.
+pub async fn finish_oidc_login<'err>(
+    state: &Arc<AppState>,
+    jar: &Cookies,
+    client: ClientInfo,
+    provider_name: &str,
+    payload: &OidcCallbackRequest,
+) -> error::Result<'err, LoginStatus>
+{
+    let provider = get_provider(state, provider_name)?;
+
+    let oidc_cookie = jar
+        .get_cookie(auth::CookieNames::OIDC.as_str())
+        .map_err(|err| {
+            server_error!(
+                err,
+                error::Kind::NoAuth,
+                error::OnType::Cookie
+            )
+            .add_client(error::Client::OIDC_LOGIN_FAILED)
+        })?;
+
+    //single use, a retry has to start over
+    jar.remove_cookie(auth::CookieNames::OIDC.to_string());
+
+    let claims = auth::extract_oidc_token(
+        &state.acces_token_keys,
+        &oidc_cookie,
+    )
+    .map_err(|err| {
+        server_error!(err).add_client(error::Client::OIDC_LOGIN_FAILED)
+    })?;
+
+    if let Some(provider_error) = &payload.error
+    {
+        return Err(server_error!(
+            error::Kind::NoAuth,
+            error::OnType::Oidc
+        )
+        .add_client(error::Client::OIDC_LOGIN_FAILED)
+        .add_debug_info(
+            "error",
+            provider_error.clone(),
+        ));
+    }
+
+    //the state ties the callback to the browser that started the login
+    let (Some(code), true) = (
+        &payload.code,
+        claims.provider == provider.name
+            && payload.state.as_deref() == Some(claims.state.as_str()),
+    )
+    else
+    {
+        return Err(server_error!(
+            error::Kind::InValid,
+            error::OnType::Oidc
+        )
+        .add_client(error::Client::OIDC_LOGIN_FAILED)
+        .add_debug_info(
+            "provider",
+            provider.name.clone(),
+        ));
+    };
+
+    let id_token = provider
+        .exchange_code(
+            code,
+            &redirect_uri(state, provider),
+            &claims.code_verifier,
+        )
+        .await
+        .map_err(|err| {
+            server_error!(err).add_client(error::Client::OIDC_LOGIN_FAILED)
+        })?;
+
+    let identity = provider
+        .verify_id_token(&id_token, &claims.nonce)
+        .await
+        .map_err(|err| {
+            server_error!(err).add_client(error::Client::OIDC_LOGIN_FAILED)
+        })?;
+
+    let user = get_or_create_linked_user(state, provider, &identity).await?;
+
+    if !user.flag.is_allowed_on_mogcord()
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::User
+        )
+        .add_client(error::Client::NOT_ALLOWED_PLATFORM)
+        .add_debug_info(
+            "user flag",
+            user.flag.to_string(),
+        ));
+    }
+
+    start_session(state, jar, client, user).await
+}
+
+async fn get_or_create_linked_user<'err>(
+    state: &Arc<AppState>,
+    provider: &Provider,
+    identity: &IdentityClaims,
+) -> error::Result<'err, User>
+{
+    let repo_user = &state.users;
+
+    match state
+        .external_identities
+        .get_external_identity(&provider.name, &identity.sub)
+        .await
+    {
+        Ok(external_identity) =>
+        {
+            return repo_user.get_user_by_id(&external_identity.user_id).await;
+        },
+        Err(err)
+            if !matches!(
+                err.kind,
+                error::Kind::NotFound
+            ) =>
+        {
+            return Err(err);
+        },
+        Err(_) =>
+        {},
+    }
+
+    //linking by an address the provider didnt check would hand out accounts
+    let email = match &identity.email
+    {
+        Some(email) if identity.email_verified => User::normalize_email(email),
+        _ =>
+        {
+            return Err(server_error!(
+                error::Kind::IncorrectPermissions,
+                error::OnType::Oidc
+            )
+            .add_client(error::Client::OIDC_EMAIL_NOT_VERIFIED)
+            .add_debug_info(
+                "provider",
+                provider.name.clone(),
+            ));
+        },
+    };
+
+    let user = match repo_user.get_user_by_mail(&email).await
+    {
+        //someone else could have registered the address without owning it
+        Ok(user) if user.flag == Flag::Unverified =>
+        {
+            return Err(server_error!(
+                error::Kind::IncorrectPermissions,
+                error::OnType::User
+            )
+            .add_client(error::Client::EMAIL_NOT_VERIFIED));
+        },
+        Ok(user) => user,
+        Err(err)
+            if matches!(
+                err.kind,
+                error::Kind::NotFound
+            ) =>
+        {
+            create_oidc_user(state, identity, email).await?
+        },
+        Err(err) => return Err(err),
+    };
+
+    state
+        .external_identities
+        .create_external_identity(ExternalIdentity::new(
+            provider.name.clone(),
+            identity.sub.clone(),
+            user.id.clone(),
+        ))
+        .await?;
+
+    Ok(user)
+}
+
+async fn create_oidc_user<'err>(
+    state: &Arc<AppState>,
+    identity: &IdentityClaims,
+    email: String,
+) -> error::Result<'err, User>
+{
+    let repo_user = &state.users;
+
+    let base_username = username_from_identity(identity, &email);
+    let mut username = base_username.clone();
+
+    for _ in 0..USERNAME_ATTEMPTS
+    {
+        if !repo_user.does_user_exist_by_username(&username).await?
+        {
+            break;
+        }
+
+        username = format!(
+            "{base_username}{}",
+            &Uuid::new_v4().simple().to_string()[..4]
+        );
+    }
+
+    //nobody knows it, a password can still be set with a reset mail
+    let hashed_password = state
+        .hashing
+        .hash_text(&Uuid::new_v4().simple().to_string())
+        .await?;
+
+    repo_user
+        .create_user(User::new(
+            username,
+            email,
+            hashed_password,
+        ))
+        .await
+}
+
+fn username_from_identity(
+    identity: &IdentityClaims,
+    email: &str,
+) -> String
+{
+    let candidate = identity
+        .preferred_username
+        .as_deref()
+        .or(identity.name.as_deref())
+        .unwrap_or_else(|| email.split('@').next().unwrap_or_default());
+
+    let username: String = candidate
+        .chars()
+        .filter(|char| {
+            char.is_alphanumeric() || matches!(char, '_' | '.' | '-')
+        })
+        .take(USERNAME_MAX_LENGTH)
+        .collect();
+
+    if username.is_empty()
+    {
+        return String::from("user");
+    }
+
+    username
+}
+
+fn get_provider<'state, 'err>(
+    state: &'state Arc<AppState>,
+    provider_name: &str,
+) -> error::Result<'err, &'state Provider>
+{
+    state.oidc_providers.get(provider_name).ok_or_else(|| {
+        server_error!(
+            error::Kind::NotFound,
+            error::OnType::Oidc
+        )
+        .add_client(error::Client::OIDC_PROVIDER_NOT_FOUND)
+        .add_debug_info(
+            "provider",
+            provider_name.to_string(),
+        )
+    })
+}
+
+fn redirect_uri(
+    state: &Arc<AppState>,
+    provider: &Provider,
+) -> String
+{
+    format!(
+        "{}/login/oidc/{}/callback",
+        state.config.public_url, provider.name
+    )
+}
@@ -0,0 +1,145 @@
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::{
+    check_mail_request,
+    create_mail_link,
+    MailRequest,
+};
+use crate::middleware::auth::ClientInfo;
+use crate::model::mail::Mail;
+use crate::model::mail_token::{
+    MailToken,
+    Purpose,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct ResetPasswordRequest
+{
+    token: String,
+    password: String,
+    confirm_password: String,
+}
+
+impl ResetPasswordRequest
+{
+    #[must_use]
+    pub fn new(
+        token: String,
+        password: String,
+        confirm_password: String,
+    ) -> Self
+    {
+        Self {
+            token,
+            password,
+            confirm_password,
+        }
+    }
+}
+
+/// always succeeds, so it can't be used to find out who has an account.
+pub async fn request_password_reset<'err>(
+    state: &Arc<AppState>,
+    client: &ClientInfo,
+    payload: &MailRequest,
+) -> error::Result<'err, ()>
+{
+    check_mail_request(
+        state,
+        client,
+        payload.email_ref(),
+    )?;
+
+    let Ok(user) = state.users.get_user_by_mail(payload.email_ref()).await
+    else
+    {
+        return Ok(());
+    };
+
+    //banned users should not get a way back in
+    if !user.flag.is_allowed_on_mogcord()
+    {
+        return Ok(());
+    }
+
+    let link = create_mail_link(
+        state,
+        &user,
+        Purpose::PasswordReset,
+    )
+    .await?;
+
+    state
+        .mailer
+        .send_mail(Mail::password_reset(
+            user.email.clone(),
+            &link,
+        ))
+        .await
+}
+
+/// sets the new password and logs out every device,
+/// the acces tokens already handed out included.
+pub async fn reset_password<'err>(
+    state: &Arc<AppState>,
+    payload: &ResetPasswordRequest,
+) -> error::Result<'err, ()>
+{
+    if payload.password != payload.confirm_password
+    {
+        return Err(server_error!(
+            error::Kind::InValid,
+            error::OnType::Password
+        )
+        .add_client(error::Client::PASSWORD_CONFIRM_NOT_MATCH));
+    }
+
+    let token_hash = MailToken::hash_value(&payload.token);
+
+    //only looked up, a password the policy rejects shouldnt burn the link
+    let token = state
+        .mail_tokens
+        .get_mail_token(
+            Purpose::PasswordReset,
+            &token_hash,
+        )
+        .await?;
+
+    let user = state.users.get_user_by_id(&token.user_id).await?;
+
+    state.password_policy.validate(
+        &payload.password,
+        &user.username,
+        &user.email,
+    )?;
+
+    let hashed_password = state.hashing.hash_text(&payload.password).await?;
+
+    //single use, a concurrent reset with the same link loses here
+    state
+        .mail_tokens
+        .consume_mail_token(
+            Purpose::PasswordReset,
+            &token_hash,
+        )
+        .await?;
+
+    state
+        .users
+        .update_user_hashed_password(&user.id, hashed_password)
+        .await?;
+
+    state
+        .users
+        .update_user_sessions_valid_after(&user.id, Utc::now())
+        .await?;
+
+    state.refresh_tokens.revoke_all_tokens(&user.id).await
+}
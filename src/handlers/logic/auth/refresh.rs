@@ -1,12 +1,19 @@
+use chrono::Duration;
 use std::sync::Arc;
 use tower_cookies::Cookies;
 
 use crate::handlers::logic;
 use crate::middleware::auth::{
     self,
+    ClientInfo,
     TokenStatus,
 };
 use crate::middleware::cookies::Manager;
+use crate::model::refresh_token::{
+    self,
+    Flag,
+    RefreshToken,
+};
 use crate::model::{
     error,
     AppState,
@@ -16,10 +23,9 @@ use crate::server_error;
 pub async fn refresh_token<'err>(
     state: &Arc<AppState>,
     jar: &Cookies,
+    client: ClientInfo,
 ) -> error::Result<'err, ()>
 {
-    let repo_refresh = &state.refresh_tokens;
-
     let acces_token_cookie = jar
         .get_cookie(auth::CookieNames::AUTH_ACCES.as_str())
         .map_err(|err| {
@@ -31,6 +37,7 @@ pub async fn refresh_token<'err>(
         })?;
 
     let claims = auth::extract_acces_token(
+        &state.acces_token_keys,
         &acces_token_cookie,
         &TokenStatus::AllowExpired,
     )
@@ -62,14 +69,107 @@ pub async fn refresh_token<'err>(
             )
         })?;
 
+    let refresh_token = validate_refresh_token(
+        state,
+        &claims.sub,
+        &device_id_cookie,
+        &refresh_token_cookie,
+        client,
+    )
+    .await
+    .inspect_err(|err| {
+        if matches!(
+            err.kind,
+            error::Kind::IncorrectPermissions
+        ) || matches!(
+            err.client,
+            error::Client::SESSION_REUSE_DETECTED
+        )
+        {
+            jar.remove_cookie(auth::CookieNames::AUTH_ACCES.to_string());
+            jar.remove_cookie(auth::CookieNames::AUTH_REFRESH.to_string());
+        }
+    })?;
+
+    logic::auth::cookies::create_auth_cookies(state, jar, refresh_token)
+}
+
+//shared by the cookie and token refresh
+pub(super) async fn validate_refresh_token<'err>(
+    state: &Arc<AppState>,
+    user_id: &str,
+    device_id: &str,
+    refresh_token_value: &str,
+    client: ClientInfo,
+) -> error::Result<'err, RefreshToken>
+{
+    internal_validate_refresh_token(
+        state.refresh_tokens.as_ref(),
+        state.config.refresh_token_ttl(),
+        user_id,
+        device_id,
+        refresh_token_value,
+        client,
+    )
+    .await
+}
+
+async fn internal_validate_refresh_token<'err>(
+    repo_refresh: &dyn refresh_token::Repository,
+    ttl: Duration,
+    user_id: &str,
+    device_id: &str,
+    refresh_token_value: &str,
+    client: ClientInfo,
+) -> error::Result<'err, RefreshToken>
+{
     let refresh_token = repo_refresh
-        .get_valid_token(&device_id_cookie, &claims.sub)
-        .await?;
+        .get_token_by_value(user_id, refresh_token_value)
+        .await
+        .map_err(|err| {
+            server_error!(
+                err,
+                error::Kind::NoAuth,
+                error::OnType::RefreshToken
+            )
+        })?;
+
+    //an old value came back after it got rotated, so it leaked somewhere.
+    //burn every token of the login, the thief and the victim both have to log in again
+    if refresh_token.is_reused()
+    {
+        repo_refresh
+            .flag_token_family(
+                &refresh_token.family_id,
+                Flag::ReuseDetected,
+            )
+            .await?;
+
+        return Err(server_error!(
+            error::Kind::NoAuth,
+            error::OnType::RefreshToken
+        )
+        .add_client(error::Client::SESSION_REUSE_DETECTED)
+        .add_debug_info(
+            "family id",
+            refresh_token.family_id.clone(),
+        ));
+    }
+
+    if refresh_token.device_id != device_id
+    {
+        return Err(server_error!(
+            error::Kind::NoAuth,
+            error::OnType::RefreshToken
+        )
+        .add_debug_info(
+            "device id",
+            device_id.to_string(),
+        ));
+    }
 
     if !refresh_token.owner.flag.is_allowed_on_mogcord()
     {
-        jar.remove_cookie(auth::CookieNames::AUTH_ACCES.to_string());
-        jar.remove_cookie(auth::CookieNames::AUTH_REFRESH.to_string());
         return Err(server_error!(
             error::Kind::IncorrectPermissions,
             error::OnType::User
@@ -81,15 +181,303 @@ pub async fn refresh_token<'err>(
         ));
     }
 
-    if refresh_token.value != refresh_token_cookie
-    {
-        return Err(server_error!(
+    //a rotated token within the grace window ends up here, and is refused
+    let mut rotated_token = refresh_token.rotate(ttl).map_err(|err| {
+        server_error!(
+            err,
             error::Kind::NoAuth,
             error::OnType::RefreshToken
+        )
+    })?;
+    rotated_token.mark_used(
+        client.ip_addr,
+        client.user_agent,
+    );
+
+    repo_refresh
+        .rotate_token(&refresh_token, rotated_token)
+        .await
+        .map_err(|err| {
+            server_error!(
+                err,
+                error::Kind::NoAuth,
+                error::OnType::RefreshToken
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Mutex;
+
+    use axum::async_trait;
+    use chrono::{
+        Duration,
+        Utc,
+    };
+
+    use crate::handlers::logic::auth::refresh::internal_validate_refresh_token;
+    use crate::middleware::auth::ClientInfo;
+    use crate::model::error;
+    use crate::model::refresh_token::{
+        self,
+        Flag,
+        RefreshToken,
+    };
+    use crate::model::user::User;
+    use crate::server_error;
+
+    #[derive(Default)]
+    struct MemoryRefreshTokens
+    {
+        tokens: Mutex<Vec<RefreshToken>>,
+    }
+
+    impl MemoryRefreshTokens
+    {
+        fn flag_of(
+            &self,
+            value: &str,
+        ) -> Flag
+        {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.value == value)
+                .map(|token| token.flag.clone())
+                .unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl refresh_token::Repository for MemoryRefreshTokens
+    {
+        async fn create_token<'input, 'err>(
+            &'input self,
+            token: RefreshToken,
+        ) -> error::Result<'err, RefreshToken>
+        {
+            self.tokens.lock().unwrap().push(token.clone());
+            Ok(token)
+        }
+
+        async fn update_token<'input, 'err>(
+            &'input self,
+            _token: &'input RefreshToken,
+        ) -> error::Result<'err, ()>
+        {
+            Ok(())
+        }
+
+        async fn get_valid_token<'input, 'err>(
+            &'input self,
+            _device_id: &'input str,
+            _user_id: &'input str,
+        ) -> error::Result<'err, RefreshToken>
+        {
+            Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::RefreshToken
+            ))
+        }
+
+        async fn get_token_by_value<'input, 'err>(
+            &'input self,
+            _user_id: &'input str,
+            value: &'input str,
+        ) -> error::Result<'err, RefreshToken>
+        {
+            self.tokens
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|token| token.value == value)
+                .cloned()
+                .ok_or(server_error!(
+                    error::Kind::NotFound,
+                    error::OnType::RefreshToken
+                ))
+        }
+
+        async fn rotate_token<'input, 'err>(
+            &'input self,
+            old: &'input RefreshToken,
+            new: RefreshToken,
+        ) -> error::Result<'err, RefreshToken>
+        {
+            let mut tokens = self.tokens.lock().unwrap();
+            let stored = tokens
+                .iter_mut()
+                .find(|token| {
+                    token.value == old.value && token.flag == Flag::None
+                })
+                .ok_or(server_error!(
+                    error::Kind::NotAllowed,
+                    error::OnType::RefreshToken
+                ))?;
+
+            stored.flag = Flag::Rotated;
+            stored.last_used = Utc::now();
+            tokens.push(new.clone());
+
+            Ok(new)
+        }
+
+        async fn flag_token_family<'input, 'err>(
+            &'input self,
+            family_id: &'input str,
+            flag: Flag,
+        ) -> error::Result<'err, ()>
+        {
+            for token in self.tokens.lock().unwrap().iter_mut()
+            {
+                if token.family_id == family_id
+                {
+                    token.flag = flag.clone();
+                }
+            }
+
+            Ok(())
+        }
+
+        async fn get_valid_tokens<'input, 'err>(
+            &'input self,
+            _user_id: &'input str,
+        ) -> error::Result<'err, Vec<RefreshToken>>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn revoke_token<'input, 'err>(
+            &'input self,
+            _user_id: &'input str,
+            _device_id: &'input str,
+        ) -> error::Result<'err, ()>
+        {
+            Ok(())
+        }
+
+        async fn revoke_all_tokens<'input, 'err>(
+            &'input self,
+            _user_id: &'input str,
+        ) -> error::Result<'err, ()>
+        {
+            Ok(())
+        }
+
+        async fn count_valid_tokens<'input, 'err>(
+            &'input self
+        ) -> error::Result<'err, u64>
+        {
+            Ok(0)
+        }
+    }
+
+    fn internal_client() -> ClientInfo
+    {
+        ClientInfo {
+            ip_addr: String::from("203.0.113.7"),
+            user_agent: None,
+        }
+    }
+
+    async fn internal_login(repo: &MemoryRefreshTokens) -> RefreshToken
+    {
+        let owner = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+
+        refresh_token::Repository::create_token(
+            repo,
+            RefreshToken::create_token(
+                owner,
+                String::from("203.0.113.7"),
+                None,
+                None,
+                Duration::days(1),
+            ),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn internal_validate<'err>(
+        repo: &MemoryRefreshTokens,
+        token: &RefreshToken,
+    ) -> error::Result<'err, RefreshToken>
+    {
+        internal_validate_refresh_token(
+            repo,
+            Duration::days(1),
+            &token.owner.id,
+            &token.device_id,
+            &token.value,
+            internal_client(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_validate_refresh_token_rotated_within_grace_is_invalid()
+    {
+        let repo = MemoryRefreshTokens::default();
+        let token = internal_login(&repo).await;
+
+        let successor = internal_validate(&repo, &token).await.unwrap();
+
+        //the tab that lost the race is refused, the family is left alone
+        let Err(err) = internal_validate(&repo, &token).await
+        else
+        {
+            panic!("a rotated token got refreshed");
+        };
+
+        assert!(!matches!(
+            err.client,
+            error::Client::SESSION_REUSE_DETECTED
         ));
+        assert_eq!(
+            Flag::Rotated,
+            repo.flag_of(&token.value)
+        );
+        assert_eq!(
+            Flag::None,
+            repo.flag_of(&successor.value)
+        );
+        assert!(internal_validate(&repo, &successor).await.is_ok());
     }
 
-    let updated_refresh_token = refresh_token.refresh_expiration()?;
+    #[tokio::test]
+    async fn test_validate_refresh_token_rotated_after_grace_is_invalid()
+    {
+        let repo = MemoryRefreshTokens::default();
+        let token = internal_login(&repo).await;
+
+        let successor = internal_validate(&repo, &token).await.unwrap();
+
+        let mut stale = token.clone();
+        stale.flag = Flag::Rotated;
+        stale.last_used = Utc::now()
+            - Duration::seconds(refresh_token::ROTATION_GRACE_SEC + 1);
+        repo.tokens.lock().unwrap()[0] = stale;
 
-    logic::auth::cookies::create_auth_cookies(jar, updated_refresh_token)
+        let Err(err) = internal_validate(&repo, &token).await
+        else
+        {
+            panic!("a rotated token got refreshed");
+        };
+
+        assert!(matches!(
+            err.client,
+            error::Client::SESSION_REUSE_DETECTED
+        ));
+        assert_eq!(
+            Flag::ReuseDetected,
+            repo.flag_of(&successor.value)
+        );
+    }
 }
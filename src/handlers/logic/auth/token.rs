@@ -0,0 +1,149 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::handlers::logic;
+use crate::middleware::auth::{
+    self,
+    ClientInfo,
+    CreateAccesTokenRequest,
+    TokenStatus,
+};
+use crate::model::refresh_token::RefreshToken;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::login::verify_login;
+use super::refresh::validate_refresh_token;
+
+//acces token together with the refresh token it was made from
+pub struct TokenPair
+{
+    pub acces_token: String,
+    pub refresh_token: RefreshToken,
+    //seconds until the acces token expires
+    pub expires_in: i64,
+}
+
+impl TokenPair
+{
+    fn new<'err>(
+        state: &Arc<AppState>,
+        refresh_token: RefreshToken,
+    ) -> error::Result<'err, Self>
+    {
+        let create_token_request = CreateAccesTokenRequest::new(
+            &refresh_token.owner.id,
+            refresh_token.owner.flag.is_mogcord_admin_or_owner(),
+            &refresh_token.device_id,
+        );
+
+        let acces_token = auth::create_acces_token(
+            &state.acces_token_keys,
+            &create_token_request,
+            state.config.acces_token_ttl(),
+        )?;
+
+        Ok(Self {
+            acces_token,
+            refresh_token,
+            expires_in: state.config.acces_token_ttl().num_seconds(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TokenLoginRequest
+{
+    email: String,
+    password: String,
+    //lets a client keep its session when logging in again
+    device_id: Option<String>,
+    //required once the user enabled two factor, totp or recovery code
+    #[serde(default)]
+    two_factor_code: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenRefreshRequest
+{
+    //may be expired, only used to know who is refreshing
+    acces_token: String,
+    refresh_token: String,
+    device_id: String,
+}
+
+//same as the cookie login, but hands the tokens to the client
+pub async fn login_token_pair<'err>(
+    state: &Arc<AppState>,
+    client: ClientInfo,
+    payload: TokenLoginRequest,
+) -> error::Result<'err, TokenPair>
+{
+    let user = verify_login(
+        state,
+        &client,
+        &payload.email,
+        &payload.password,
+    )
+    .await?;
+
+    //api clients have no cookie to carry over, so both steps are one request
+    if logic::auth::is_two_factor_enabled(state, &user.id).await?
+    {
+        let Some(code) = &payload.two_factor_code
+        else
+        {
+            return Err(server_error!(
+                error::Kind::NoAuth,
+                error::OnType::TwoFactor
+            )
+            .add_client(error::Client::TWO_FACTOR_REQUIRED));
+        };
+
+        logic::auth::verify_two_factor(state, &user.id, code).await?;
+    }
+
+    let refresh_token = logic::auth::get_or_create_refresh_token(
+        state,
+        payload.device_id,
+        client,
+        user,
+    )
+    .await?;
+
+    TokenPair::new(state, refresh_token)
+}
+
+pub async fn refresh_token_pair<'err>(
+    state: &Arc<AppState>,
+    client: ClientInfo,
+    payload: TokenRefreshRequest,
+) -> error::Result<'err, TokenPair>
+{
+    let claims = auth::extract_acces_token(
+        &state.acces_token_keys,
+        &payload.acces_token,
+        &TokenStatus::AllowExpired,
+    )
+    .map_err(|err| {
+        server_error!(
+            err,
+            error::Kind::NoAuth,
+            error::OnType::AccesToken
+        )
+    })?;
+
+    let refresh_token = validate_refresh_token(
+        state,
+        &claims.sub,
+        &payload.device_id,
+        &payload.refresh_token,
+        client,
+    )
+    .await?;
+
+    TokenPair::new(state, refresh_token)
+}
@@ -0,0 +1,211 @@
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use tower_cookies::Cookies;
+
+use crate::handlers::logic;
+use crate::middleware::auth::{
+    self,
+    ClientInfo,
+};
+use crate::middleware::cookies::Manager;
+use crate::model::two_factor::{
+    TwoFactor,
+    UsedCode,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct TwoFactorCodeRequest
+{
+    //a totp code or one of the recovery codes
+    code: String,
+}
+
+impl TwoFactorCodeRequest
+{
+    #[must_use]
+    pub fn code_ref(&self) -> &str
+    {
+        &self.code
+    }
+}
+
+/// second step of the cookie login, the first one left a two factor cookie.
+pub async fn login_two_factor<'err>(
+    state: &Arc<AppState>,
+    jar: &Cookies,
+    client: ClientInfo,
+    payload: &TwoFactorCodeRequest,
+) -> error::Result<'err, ()>
+{
+    let repo_user = &state.users;
+
+    let two_factor_cookie = jar
+        .get_cookie(auth::CookieNames::TWO_FACTOR.as_str())
+        .map_err(|err| {
+            server_error!(
+                err,
+                error::Kind::NoAuth,
+                error::OnType::Cookie
+            )
+            .add_client(error::Client::PERMISSION_NO_AUTH)
+        })?;
+
+    let claims = auth::extract_two_factor_token(
+        &state.acces_token_keys,
+        &two_factor_cookie,
+    )
+    .map_err(|err| {
+        server_error!(
+            err,
+            error::Kind::NoAuth,
+            error::OnType::TwoFactor
+        )
+        .add_client(error::Client::PERMISSION_NO_AUTH)
+    })?;
+
+    let user = repo_user.get_user_by_id(&claims.sub).await?;
+
+    //could have been banned in between both steps
+    if !user.flag.is_allowed_on_mogcord()
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::User
+        )
+        .add_client(error::Client::NOT_ALLOWED_PLATFORM)
+        .add_debug_info(
+            "user flag",
+            user.flag.to_string(),
+        ));
+    }
+
+    verify_two_factor(
+        state,
+        &user.id,
+        payload.code_ref(),
+    )
+    .await?;
+
+    jar.remove_cookie(auth::CookieNames::TWO_FACTOR.to_string());
+
+    let refresh_token =
+        logic::auth::cookies::get_refresh_token(state, jar, client, user)
+            .await?;
+
+    logic::auth::cookies::create_auth_cookies(state, jar, refresh_token)
+}
+
+pub async fn is_two_factor_enabled<'err>(
+    state: &Arc<AppState>,
+    user_id: &str,
+) -> error::Result<'err, bool>
+{
+    match state.two_factors.get_two_factor(user_id).await
+    {
+        Ok(two_factor) => Ok(two_factor.is_enabled),
+        Err(err)
+            if matches!(
+                err.kind,
+                error::Kind::NotFound
+            ) =>
+        {
+            Ok(false)
+        },
+        Err(err) => Err(err),
+    }
+}
+
+/// accepts a totp code or burns one of the recovery codes.
+pub async fn verify_two_factor<'err>(
+    state: &Arc<AppState>,
+    user_id: &str,
+    code: &str,
+) -> error::Result<'err, ()>
+{
+    let repo_two_factor = &state.two_factors;
+
+    let mut two_factor = repo_two_factor
+        .get_two_factor(user_id)
+        .await
+        .map_err(|err| {
+            server_error!(err).add_client(error::Client::TWO_FACTOR_NOT_ENABLED)
+        })?;
+
+    if !two_factor.is_enabled
+    {
+        return Err(server_error!(
+            error::Kind::NotFound,
+            error::OnType::TwoFactor
+        )
+        .add_client(error::Client::TWO_FACTOR_NOT_ENABLED));
+    }
+
+    let used_code = check_two_factor_code(state, &mut two_factor, code).await?;
+
+    repo_two_factor
+        .consume_two_factor_code(user_id, &used_code)
+        .await
+        .map_err(|err| match err.kind
+        {
+            error::Kind::NoChange => server_error!(err)
+                .add_client(error::Client::TWO_FACTOR_INVALID_CODE),
+            _ => err,
+        })
+}
+
+/// rate limited per user, only 6 digits to guess otherwise.
+///
+/// returns what the code used up, the caller has to consume it.
+pub(crate) async fn check_two_factor_code<'err>(
+    state: &Arc<AppState>,
+    two_factor: &mut TwoFactor,
+    code: &str,
+) -> error::Result<'err, UsedCode>
+{
+    if let Err(retry_after) = state.two_factor_limiter.hit(&two_factor.user_id)
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::TwoFactor
+        )
+        .add_client(error::Client::TWO_FACTOR_RATE_LIMITED)
+        .add_retry_after(retry_after));
+    }
+
+    if let Some(step) = two_factor.verify_code(code, Utc::now())
+    {
+        return Ok(UsedCode::Step(step));
+    }
+
+    let recovery_code = TwoFactor::normalize_recovery_code(code);
+
+    //only worth the argon rounds when it isnt shaped like a totp code
+    if recovery_code.len() > TwoFactor::DIGITS as usize
+    {
+        for hash in &two_factor.recovery_code_hashes
+        {
+            if state
+                .hashing
+                .verify_hash(&recovery_code, hash)
+                .await
+                .is_ok()
+            {
+                return Ok(UsedCode::RecoveryCode(
+                    hash.clone(),
+                ));
+            }
+        }
+    }
+
+    Err(server_error!(
+        error::Kind::NoAuth,
+        error::OnType::TwoFactor
+    )
+    .add_client(error::Client::TWO_FACTOR_INVALID_CODE))
+}
@@ -0,0 +1,183 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::ClientInfo;
+use crate::model::mail::Mail;
+use crate::model::mail_token::{
+    MailToken,
+    Purpose,
+};
+use crate::model::user::{
+    Flag,
+    User,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct MailTokenRequest
+{
+    token: String,
+}
+
+impl MailTokenRequest
+{
+    #[must_use]
+    pub fn new(token: String) -> Self
+    {
+        Self {
+            token,
+        }
+    }
+
+    #[must_use]
+    pub fn token_ref(&self) -> &str
+    {
+        &self.token
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MailRequest
+{
+    email: String,
+}
+
+impl MailRequest
+{
+    #[must_use]
+    pub fn new(email: String) -> Self
+    {
+        Self {
+            email,
+        }
+    }
+
+    #[must_use]
+    pub fn email_ref(&self) -> &str
+    {
+        &self.email
+    }
+}
+
+pub async fn verify_email<'err>(
+    state: &Arc<AppState>,
+    payload: &MailTokenRequest,
+) -> error::Result<'err, ()>
+{
+    let token = state
+        .mail_tokens
+        .consume_mail_token(
+            Purpose::EmailVerification,
+            &MailToken::hash_value(payload.token_ref()),
+        )
+        .await?;
+
+    let user = state.users.get_user_by_id(&token.user_id).await?;
+
+    //a ban in between should not be lifted by clicking an old link
+    if user.flag != Flag::Unverified
+    {
+        return Ok(());
+    }
+
+    state.users.update_user_flag(&user.id, Flag::None).await
+}
+
+/// always succeeds, so it can't be used to find out who has an account.
+pub async fn resend_verification<'err>(
+    state: &Arc<AppState>,
+    client: &ClientInfo,
+    payload: &MailRequest,
+) -> error::Result<'err, ()>
+{
+    check_mail_request(
+        state,
+        client,
+        payload.email_ref(),
+    )?;
+
+    if let Ok(user) = state.users.get_user_by_mail(payload.email_ref()).await
+    {
+        if user.flag == Flag::Unverified
+        {
+            send_verification_mail(state, &user).await?;
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn send_verification_mail<'err>(
+    state: &Arc<AppState>,
+    user: &User,
+) -> error::Result<'err, ()>
+{
+    let link = create_mail_link(
+        state,
+        user,
+        Purpose::EmailVerification,
+    )
+    .await?;
+
+    state
+        .mailer
+        .send_mail(Mail::email_verification(
+            user.email.clone(),
+            &link,
+        ))
+        .await
+}
+
+/// per ip and per email, or the mail endpoints could flood any inbox.
+///
+/// counted for unknown emails too, so being limited tells nothing either.
+pub(crate) fn check_mail_request<'err>(
+    state: &Arc<AppState>,
+    client: &ClientInfo,
+    email: &str,
+) -> error::Result<'err, ()>
+{
+    let limited = state.mail_ip_limiter.hit(&client.ip_addr).and_then(|()| {
+        state.mail_email_limiter.hit(&User::normalize_email(email))
+    });
+
+    if let Err(retry_after) = limited
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::Mail
+        )
+        .add_client(error::Client::RATE_LIMITED)
+        .add_debug_info("ip", client.ip_addr.clone())
+        .add_retry_after(retry_after));
+    }
+
+    Ok(())
+}
+
+/// stores a new token for the user and returns the link to mail.
+pub(crate) async fn create_mail_link<'err>(
+    state: &Arc<AppState>,
+    user: &User,
+    purpose: Purpose,
+) -> error::Result<'err, String>
+{
+    let path = match purpose
+    {
+        Purpose::EmailVerification => "verify-email",
+        Purpose::PasswordReset => "password/reset",
+    };
+
+    let (token, value) = MailToken::new(user.id.clone(), purpose);
+
+    state.mail_tokens.create_mail_token(token).await?;
+
+    Ok(format!(
+        "{}/{path}?token={value}",
+        state.config.public_url
+    ))
+}
@@ -0,0 +1,3 @@
+mod add_users_to_chat;
+
+pub use add_users_to_chat::*;
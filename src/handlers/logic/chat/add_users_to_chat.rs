@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::{
+    error,
+    event,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct AddUsersRequest
+{
+    user_ids: Vec<String>,
+}
+
+pub async fn add_users_to_chat<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    chat_id: &str,
+    payload: AddUsersRequest,
+) -> error::Result<'err, ()>
+{
+    let repo_chat = &state.chats;
+    let repo_relation = &state.relations;
+    let repo_user = &state.users;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let mut chat = repo_chat.get_chat_by_id(chat_id).await?;
+
+    if !chat.is_group()
+    {
+        return Err(server_error!(
+            error::Kind::CantGainUsers,
+            error::OnType::ChatPrivate
+        )
+        .add_client(error::Client::CHAT_CANT_GAIN_USERS));
+    }
+
+    if !chat.is_owner(ctx_user_id)
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::Chat
+        )
+        .add_client(error::Client::CHAT_EDIT_NOT_OWNER));
+    }
+
+    let user_ids: Vec<&str> =
+        payload.user_ids.iter().map(AsRef::as_ref).collect();
+
+    if repo_relation
+        .does_friendships_exist(ctx_user_id, user_ids)
+        .await?
+    {
+        return Err(server_error!(
+            error::Kind::NotFound,
+            error::OnType::RelationFriend
+        )
+        .add_client(error::Client::CHAT_ADD_NON_FRIEND));
+    }
+
+    let users = repo_user.get_users_by_id(payload.user_ids).await?;
+
+    let added_user_ids = users.iter().map(|user| user.id.clone()).collect();
+
+    chat.add_users(users)?;
+
+    repo_chat.update_chat(chat).await?;
+
+    state.events.publish(event::Kind::ChatUsersAdded {
+        chat_id: chat_id.to_string(),
+        user_ids: added_user_ids,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod create_message;
+mod update_message;
+
+pub use create_message::*;
+pub use update_message::*;
@@ -0,0 +1,85 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::channel::Parent;
+use crate::model::channel_parent::ChannelParent;
+use crate::model::message::Message;
+use crate::model::{
+    error,
+    event,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct CreateMessageRequest
+{
+    value: String,
+}
+
+impl CreateMessageRequest
+{
+    #[must_use]
+    pub fn new(value: String) -> Self
+    {
+        Self {
+            value,
+        }
+    }
+}
+
+pub async fn create_message<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    channel_id: &str,
+    payload: CreateMessageRequest,
+) -> error::Result<'err, Message>
+{
+    let repo_message = &state.messages;
+    let repo_user = &state.users;
+    let repo_parent = &state.channel_parents;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let channel_parent = repo_parent.get_channel_parent(channel_id).await?;
+
+    if !channel_parent.can_write(ctx_user_id, Some(channel_id))?
+    {
+        return Err(server_error!(
+            error::Kind::NotAllowed,
+            error::OnType::ChannelParent
+        )
+        .add_client(error::Client::MESSAGE_CREATE_FAIL));
+    }
+
+    let owner = repo_user.get_user_by_id(ctx_user_id).await?;
+
+    let channel = channel_parent.get_channel(Some(channel_id))?;
+
+    let message = Message::new(
+        payload.value,
+        owner,
+        channel.clone(),
+    )?;
+
+    let message = repo_message.create_message(message).await?;
+
+    state.events.publish(event::Kind::MessageCreated {
+        server_id: internal_server_id(&channel_parent),
+        message: message.clone(),
+    });
+
+    Ok(message)
+}
+
+pub(super) fn internal_server_id(
+    channel_parent: &ChannelParent
+) -> Option<String>
+{
+    match channel_parent
+    {
+        ChannelParent::Server(server) => Some(server.id.clone()),
+        ChannelParent::Chat(_) => None,
+    }
+}
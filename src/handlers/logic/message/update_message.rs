@@ -0,0 +1,70 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use super::create_message::internal_server_id;
+use crate::middleware::auth::Ctx;
+use crate::model::channel::Parent;
+use crate::model::message::Message;
+use crate::model::{
+    error,
+    event,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct UpdateMessageRequest
+{
+    value: String,
+}
+
+pub async fn update_message<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    channel_id: &str,
+    message_id: &str,
+    payload: UpdateMessageRequest,
+) -> error::Result<'err, Message>
+{
+    let repo_message = &state.messages;
+    let repo_parent = &state.channel_parents;
+
+    let ctx_user_id = ctx.user_id_ref();
+
+    let mut message = repo_message.get_message(message_id).await?;
+
+    if !message.is_channel_part_of_message(channel_id)
+    {
+        return Err(server_error!(
+            error::Kind::NotPartOf,
+            error::OnType::Channel
+        )
+        .add_client(error::Client::MESSAGE_NOT_PART_CHANNEL));
+    }
+
+    let channel_parent = repo_parent.get_channel_parent(channel_id).await?;
+
+    let user_roles = channel_parent.get_user_roles(ctx_user_id);
+
+    if !message.update_value(
+        payload.value,
+        ctx_user_id,
+        user_roles,
+    )?
+    {
+        return Err(server_error!(
+            error::Kind::NoChange,
+            error::OnType::Message
+        )
+        .add_client(error::Client::MESSAGE_NOT_PART_CHANNEL));
+    }
+
+    let message = repo_message.update_message(message).await?;
+
+    state.events.publish(event::Kind::MessageUpdated {
+        server_id: internal_server_id(&channel_parent),
+        message: message.clone(),
+    });
+
+    Ok(message)
+}
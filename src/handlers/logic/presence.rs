@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::model::presence::Presence;
+use crate::model::{
+    error,
+    AppState,
+};
+
+//users without a stored presence have never been seen, so they're offline
+pub async fn get_presences<'err>(
+    state: &Arc<AppState>,
+    user_ids: Vec<&str>,
+) -> error::Result<'err, Vec<Presence>>
+{
+    let repo_presence = &state.presences;
+
+    let mut stored_presences =
+        repo_presence.get_presences(user_ids.clone()).await?;
+
+    let presences = user_ids
+        .into_iter()
+        .map(|user_id| {
+            stored_presences
+                .iter()
+                .position(|presence| presence.user_id == user_id)
+                .map_or_else(
+                    || Presence::new_offline(user_id.to_string()),
+                    |pos| stored_presences.swap_remove(pos),
+                )
+        })
+        .collect();
+
+    Ok(presences)
+}
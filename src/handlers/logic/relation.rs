@@ -0,0 +1,5 @@
+mod add_friend;
+mod confirm_friend;
+
+pub use add_friend::*;
+pub use confirm_friend::*;
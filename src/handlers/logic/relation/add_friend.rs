@@ -0,0 +1,93 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::{
+    error,
+    event,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct AddFriendRequest
+{
+    user_id: String,
+}
+
+pub async fn add_friend<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    payload: &AddFriendRequest,
+) -> error::Result<'err, ()>
+{
+    let repo_relation = &state.relations;
+    let repo_user = &state.users;
+
+    let ctx_user_id = &ctx.user_id_ref();
+    let other_user_id = &payload.user_id;
+
+    if ctx_user_id == other_user_id
+    {
+        return Err(server_error!(
+            error::Kind::IsSelf,
+            error::OnType::RelationFriend
+        ));
+    }
+
+    if !repo_user.does_user_exist_by_id(other_user_id).await?
+    {
+        return Err(server_error!(
+            error::Kind::NotFound,
+            error::OnType::User
+        )
+        .add_debug_info(
+            "user to be added",
+            other_user_id.clone(),
+        ));
+    }
+
+    if repo_relation
+        .does_blocked_exist(ctx_user_id, other_user_id)
+        .await?
+    {
+        return Err(server_error!(
+            error::Kind::InValid,
+            error::OnType::RelationBlocked
+        )
+        .add_client(error::Client::RELATION_USER_BLOCKED));
+    }
+
+    if repo_relation
+        .does_blocked_exist(other_user_id, ctx_user_id)
+        .await?
+    {
+        return Err(server_error!(
+            error::Kind::NotAllowed,
+            error::OnType::Relation
+        )
+        .add_client(error::Client::RELATION_USER_BLOCKED_YOU));
+    }
+
+    if repo_relation
+        .does_outgoing_friendship_exist(ctx_user_id, other_user_id)
+        .await?
+    {
+        return Err(server_error!(
+            error::Kind::AlreadyExists,
+            error::OnType::RelationFriend
+        )
+        .add_client(error::Client::RELATION_USER_ALREADY_FRIEND));
+    }
+
+    repo_relation
+        .add_user_as_friend(ctx_user_id, other_user_id)
+        .await?;
+
+    state.events.publish(event::Kind::FriendRequested {
+        user_id: (*ctx_user_id).to_string(),
+        other_user_id: other_user_id.clone(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+mod join_server;
+
+pub use join_server::*;
@@ -1,3 +1,5 @@
 mod create_user;
+mod update_user_flag;
 
 pub use create_user::*;
+pub use update_user_flag::*;
@@ -1,11 +1,14 @@
 use serde::Deserialize;
 use std::sync::Arc;
 
-use crate::model::user::User;
+use crate::handlers::logic;
+use crate::model::user::{
+    Flag,
+    User,
+};
 use crate::model::{
     error,
     AppState,
-    Hashing,
 };
 use crate::server_error;
 
@@ -38,11 +41,41 @@ pub async fn create_user<'a, 'err>(
     state: &'a Arc<AppState>,
     payload: &'a CreateUserRequest,
 ) -> error::Result<'err, User>
+{
+    //can't log in until the mailed link is opened
+    let user = create_user_with_flag(
+        state,
+        payload,
+        Flag::Unverified,
+    )
+    .await?;
+
+    //the account exists either way, a new link can be requested
+    if let Err(err) = logic::auth::send_verification_mail(state, &user).await
+    {
+        tracing::error!(error = %err, "couldnt send verification mail");
+    }
+
+    Ok(user)
+}
+
+/// same checks as `create_user`, without the verification mail,
+/// for accounts made by an operator.
+pub async fn create_user_with_flag<'a, 'err>(
+    state: &'a Arc<AppState>,
+    payload: &'a CreateUserRequest,
+    flag: Flag,
+) -> error::Result<'err, User>
 {
     let repo_user = &state.users;
 
     //TODO: add user ban checks
-    //TODO: email verification (never)
+
+    state.password_policy.validate(
+        &payload.password,
+        &payload.username,
+        &payload.email,
+    )?;
 
     if repo_user
         .does_user_exist_by_username(&payload.username)
@@ -64,17 +97,14 @@ pub async fn create_user<'a, 'err>(
         .add_client(error::Client::MAIL_IN_USE));
     }
 
-    let hashed_password = Hashing::hash_text(&payload.password).await?;
+    let hashed_password = state.hashing.hash_text(&payload.password).await?;
 
-    let user = User::new(
+    let mut user = User::new(
         payload.username.to_string(),
         payload.email.to_string(),
         hashed_password,
     );
+    user.flag = flag;
 
-    match repo_user.create_user(user).await
-    {
-        Ok(user) => Ok(user),
-        Err(e) => Err(e),
-    }
+    repo_user.create_user(user).await
 }
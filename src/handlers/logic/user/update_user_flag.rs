@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use crate::model::user::{
+    Flag,
+    User,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+
+/// sets the flag of a user, a flag that keeps them off mogcord
+/// (banned, disabled, ...) also ends every session they have.
+pub async fn update_user_flag<'err>(
+    state: &Arc<AppState>,
+    user_id: &str,
+    flag: Flag,
+) -> error::Result<'err, User>
+{
+    state.users.update_user_flag(user_id, flag.clone()).await?;
+
+    if !flag.is_allowed_on_mogcord()
+    {
+        state.refresh_tokens.revoke_all_tokens(user_id).await?;
+    }
+
+    state.users.get_user_by_id(user_id).await
+}
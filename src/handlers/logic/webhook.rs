@@ -0,0 +1,42 @@
+mod create_incoming_webhook;
+mod create_webhook;
+mod execute_incoming_webhook;
+
+pub use create_incoming_webhook::*;
+pub use create_webhook::*;
+pub use execute_incoming_webhook::*;
+
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::channel_parent::Server;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+//webhooks can leak every message of a server, so only the owner manages them
+pub async fn get_server_as_owner<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    server_id: &str,
+) -> error::Result<'err, Server>
+{
+    let server = state.servers.get_server_by_id(server_id).await?;
+
+    if !server.is_owner(ctx.user_id_ref())
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::Webhook
+        )
+        .add_client(error::Client::SERVER_EDIT_NOT_OWNER)
+        .add_debug_info(
+            "server id",
+            server_id.to_string(),
+        ));
+    }
+
+    Ok(server)
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::webhook::Incoming;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::get_server_as_owner;
+
+#[derive(Deserialize)]
+pub struct CreateIncomingWebhookRequest
+{
+    channel_id: String,
+    name: String,
+}
+
+/// returns the webhook together with its plain token.
+pub async fn create_incoming_webhook<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    server_id: &str,
+    payload: CreateIncomingWebhookRequest,
+) -> error::Result<'err, (Incoming, String)>
+{
+    let repo_webhook = &state.webhooks;
+
+    let server = get_server_as_owner(state, ctx, server_id).await?;
+
+    if !server.channels.contains_key(&payload.channel_id)
+    {
+        return Err(server_error!(
+            error::Kind::NotPartOf,
+            error::OnType::Webhook
+        )
+        .add_client(error::Client::WEBHOOK_CHANNEL_NOT_PART_OF_SERVER)
+        .add_debug_info(
+            "channel id",
+            payload.channel_id,
+        ));
+    }
+
+    let (webhook, token) = Incoming::new(
+        server.id,
+        payload.channel_id,
+        &payload.name,
+        ctx.user_id_ref().to_string(),
+    )?;
+
+    let webhook = repo_webhook.create_incoming_webhook(webhook).await?;
+
+    Ok((webhook, token))
+}
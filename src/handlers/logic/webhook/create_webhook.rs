@@ -0,0 +1,62 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::middleware::auth::Ctx;
+use crate::model::webhook::{
+    EventType,
+    Webhook,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+use super::get_server_as_owner;
+
+#[derive(Deserialize)]
+pub struct CreateWebhookRequest
+{
+    target_url: String,
+    //empty or missing means every channel
+    #[serde(default)]
+    channel_ids: Vec<String>,
+    event_types: Vec<EventType>,
+}
+
+pub async fn create_webhook<'err>(
+    state: &Arc<AppState>,
+    ctx: &Ctx,
+    server_id: &str,
+    payload: CreateWebhookRequest,
+) -> error::Result<'err, Webhook>
+{
+    let repo_webhook = &state.webhooks;
+
+    let server = get_server_as_owner(state, ctx, server_id).await?;
+
+    if let Some(channel_id) = payload
+        .channel_ids
+        .iter()
+        .find(|channel_id| !server.channels.contains_key(*channel_id))
+    {
+        return Err(server_error!(
+            error::Kind::NotPartOf,
+            error::OnType::Webhook
+        )
+        .add_client(error::Client::WEBHOOK_CHANNEL_NOT_PART_OF_SERVER)
+        .add_debug_info(
+            "channel id",
+            channel_id.clone(),
+        ));
+    }
+
+    let webhook = Webhook::new(
+        server.id,
+        &payload.target_url,
+        payload.channel_ids,
+        payload.event_types,
+    )?;
+
+    repo_webhook.create_webhook(webhook).await
+}
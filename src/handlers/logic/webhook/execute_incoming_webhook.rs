@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::model::channel::Parent;
+use crate::model::message::Message;
+use crate::model::{
+    error,
+    event,
+    AppState,
+};
+use crate::server_error;
+
+#[derive(Deserialize)]
+pub struct ExecuteIncomingWebhookRequest
+{
+    value: String,
+}
+
+pub async fn execute_incoming_webhook<'err>(
+    state: &Arc<AppState>,
+    webhook_id: &str,
+    token: &str,
+    payload: ExecuteIncomingWebhookRequest,
+) -> error::Result<'err, Message>
+{
+    let repo_webhook = &state.webhooks;
+    let repo_message = &state.messages;
+    let repo_user = &state.users;
+    let repo_parent = &state.channel_parents;
+
+    //unknown ids and wrong tokens look the same to the caller
+    let webhook = repo_webhook
+        .get_incoming_webhook(webhook_id)
+        .await
+        .ok()
+        .filter(|webhook| webhook.is_token_valid(token))
+        .ok_or_else(|| {
+            server_error!(
+                error::Kind::NoAuth,
+                error::OnType::Webhook
+            )
+            .add_client(error::Client::WEBHOOK_INVALID_TOKEN)
+            .add_debug_info(
+                "webhook id",
+                webhook_id.to_string(),
+            )
+        })?;
+
+    if let Err(retry_after) = state.incoming_webhook_limiter.hit(&webhook.id)
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::Webhook
+        )
+        .add_client(error::Client::WEBHOOK_RATE_LIMITED)
+        .add_retry_after(retry_after));
+    }
+
+    let channel_parent =
+        repo_parent.get_channel_parent(&webhook.channel_id).await?;
+
+    let channel = channel_parent.get_channel(Some(&webhook.channel_id))?;
+
+    let owner = repo_user.get_user_by_id(&webhook.creator_id).await?;
+
+    let message = Message::new_from_bot(
+        payload.value,
+        webhook.bot(),
+        owner,
+        channel.clone(),
+    )?;
+
+    let message = repo_message.create_message(message).await?;
+
+    state.events.publish(event::Kind::MessageCreated {
+        server_id: Some(webhook.server_id),
+        message: message.clone(),
+    });
+
+    Ok(message)
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{
+    header,
+    HeaderMap,
+};
+use axum::response::IntoResponse;
+use sha2::{
+    Digest,
+    Sha256,
+};
+
+use crate::middleware::auth::get_bearer_token;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// prometheus scrape target, behind `METRICS_TOKEN` as bearer unless `METRICS_PUBLIC` is set.
+pub async fn get_metrics(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> error::Result<'static, impl IntoResponse>
+{
+    if let Some(metrics_token) = &state.config.metrics_token
+    {
+        //hashed first, so the comparison doesnt leak the token byte by byte
+        let is_valid = get_bearer_token(&headers).is_some_and(|token| {
+            Sha256::digest(token) == Sha256::digest(metrics_token)
+        });
+
+        if !is_valid
+        {
+            return Err(server_error!(
+                error::Kind::NoAuth,
+                error::OnType::Metrics
+            )
+            .add_client(error::Client::PERMISSION_NO_AUTH));
+        }
+    }
+
+    //cheaper to count on scrape than to track every login and logout
+    match state.refresh_tokens.count_valid_tokens().await
+    {
+        Ok(count) => state.metrics.set_active_sessions(count),
+        Err(err) =>
+        {
+            tracing::warn!(error = %err, "couldnt count active sessions");
+        },
+    }
+
+    let body = state.metrics.encode()?;
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            CONTENT_TYPE,
+        )],
+        body,
+    ))
+}
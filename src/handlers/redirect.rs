@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::uri::PathAndQuery;
+use axum::http::Uri;
+use axum::response::Redirect;
+
+/// sends a request on the plain http listener to the same path on the
+/// https `PUBLIC_URL`.
+pub async fn to_public_url(
+    State(public_url): State<Arc<str>>,
+    uri: Uri,
+) -> Redirect
+{
+    Redirect::permanent(&internal_target(
+        &public_url,
+        &uri,
+    ))
+}
+
+//the host header is ignored, it is whatever the client wants it to be
+fn internal_target(
+    public_url: &str,
+    uri: &Uri,
+) -> String
+{
+    let path_and_query = uri.path_and_query().map_or("/", PathAndQuery::as_str);
+
+    format!("{public_url}{path_and_query}")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use axum::http::Uri;
+
+    use crate::handlers::redirect::internal_target;
+
+    #[test]
+    fn test_redirect_target_is_valid()
+    {
+        assert_eq!(
+            "https://mogcord.example.com/servers?page=2",
+            internal_target(
+                "https://mogcord.example.com",
+                &Uri::from_static("/servers?page=2")
+            )
+        );
+        assert_eq!(
+            "https://mogcord.example.com/login",
+            internal_target(
+                "https://mogcord.example.com",
+                &Uri::from_static("http://evil.example.com/login")
+            )
+        );
+    }
+}
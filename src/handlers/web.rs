@@ -46,6 +46,22 @@ pub fn routes(state: Arc<AppState>) -> Router
             "/login",
             post(auth::post_login),
         )
+        .route(
+            "/login/two-factor",
+            get(auth::get_login_two_factor),
+        )
+        .route(
+            "/login/two-factor",
+            post(auth::post_login_two_factor),
+        )
+        .route(
+            "/login/oidc/:provider",
+            get(auth::get_oidc_login),
+        )
+        .route(
+            "/login/oidc/:provider/callback",
+            get(auth::get_oidc_callback),
+        )
         .route(
             "/register",
             get(auth::get_register),
@@ -54,6 +70,34 @@ pub fn routes(state: Arc<AppState>) -> Router
             "/register",
             post(auth::post_register),
         )
+        .route(
+            "/verify-email",
+            get(auth::get_verify_email),
+        )
+        .route(
+            "/verify-email/sent",
+            get(auth::get_verify_email_sent),
+        )
+        .route(
+            "/password/forgot",
+            get(auth::get_forgot_password),
+        )
+        .route(
+            "/password/forgot",
+            post(auth::post_forgot_password),
+        )
+        .route(
+            "/password/forgot/sent",
+            get(auth::get_forgot_password_sent),
+        )
+        .route(
+            "/password/reset",
+            get(auth::get_reset_password),
+        )
+        .route(
+            "/password/reset",
+            post(auth::post_reset_password),
+        )
         //index
         .route("/", get(misc::index))
         //static files
@@ -156,35 +200,62 @@ impl error::Client
             error::Client::CHAT_ADD_NON_FRIEND => "Cant add strangers to a chat.",
             error::Client::CHAT_ADD_WITH_SELF => "You're already in this chat.",
             error::Client::INVALID_PARAMS => "Invalid parameters.",
+            error::Client::LOGIN_LOCKED => "Too many wrong passwords, this account is locked for a while.",
+            error::Client::LOGIN_RATE_LIMITED => "Too many login attempts, wait a few minutes.",
+            error::Client::EMAIL_NOT_VERIFIED => "Verify your email first, check your inbox for the link.",
             error::Client::MAIL_IN_USE => "email already in use.",
+            error::Client::MAIL_TOKEN_INVALID => "This link is invalid or expired, request a new one.",
             error::Client::MESSAGE_NOT_PART_CHANNEL => "This message doesnt belong here",
             error::Client::NOT_ALLOWED_PLATFORM => "You're not allowed on this platform anymore, contact support for more info.",
+            error::Client::OIDC_EMAIL_NOT_VERIFIED => "Your account at this provider has no verified email.",
+            error::Client::OIDC_LOGIN_FAILED => "Logging in with this provider failed, try again.",
+            error::Client::OIDC_PROVIDER_NOT_FOUND => "This login provider doesn't exist.",
             error::Client::CHAT_EDIT_NOT_OWNER => "You dont have the permissions to edit this chat",
             error::Client::CHAT_PARENT_CTX_NOT_PART_OF_PARENT => "You're not part of this channel parent.",
             error::Client::CHAT_CTX_NOT_PART_OF_CHAT => "You're not part of this chat.",
             error::Client::SERVER_CTX_NOT_PART_OF_SERVER => "You're not part of this server.",
             error::Client::PASSWORD_CONFIRM_NOT_MATCH => "Passwords do not match.",
+            error::Client::PASSWORD_CONTAINS_USER_INFO => "Password can't contain your username or email.",
+            error::Client::PASSWORD_INVALID_LENGTH => "Password is too short or too long.",
+            error::Client::PASSWORD_MISSING_CHARACTERS => "Password needs a mix of lowercase, uppercase, digits and symbols.",
+            error::Client::PASSWORD_TOO_COMMON => "This password is too common, pick another one.",
             error::Client::PERMISSION_NO_ADMIN => "You dont have permissions to acces this resource, please refrain from using this.",
             error::Client::PERMISSION_NO_AUTH => "Please re-authenticate.",
             error::Client::PRIVATE_CHAT_TRY_EDIT => "Private chats cant be edited.",
+            error::Client::RATE_LIMITED => "Slow down, you're doing that too often.",
             error::Client::COOKIES_NOT_FOUND => "You're missing certain cookies.",
             error::Client::MESSAGE_CREATE_FAIL => "Failed to create message.",
             error::Client::MESSAGE_EDIT_FAIL => "Failed to edit message.",
+            error::Client::MESSAGE_INVALID_LENGTH => "Message must be between 1 and 2000 characters.",
             error::Client::SERVER_BLOCKED_YOU => "Server has you blocked.",
+            error::Client::SERVER_EDIT_NOT_OWNER => "You dont have the permissions to edit this server.",
+            error::Client::SERVER_NOT_DISCOVERABLE => "This server isn't listed publicly.",
             error::Client::SERVER_NOT_FOUND => "Server you're trying to reach doesn't exist.",
             error::Client::SERVICE_ERROR => "Eh oh.",
+            error::Client::SESSION_REVOKE_CURRENT => "Use logout to end the current session.",
+            error::Client::SESSION_REUSE_DETECTED => "This session was used from somewhere else, please log in again.",
             error::Client::RELATION_NO_INCOMING_FRIEND => "There seems to be no incoming friend request from that user.",
             error::Client::RELATION_DUPLICATE_OUTGOING_FRIEND => "You've already send a friend request.",
             error::Client::RELATION_SELF_TRY_BLOCK_SELF => "Can't block yourself.",
             error::Client::RELATION_SELF_TRY_FRIEND_SELF => "Can't add yourself as a friend.",
             error::Client::RELATION_SELF_TRY_UNBLOCK_SELF => "Can't unblock yourself.",
             error::Client::RELATION_SELF_TRY_UNFRIEND_SELF => "Can't unfriend yourself.",
+            error::Client::TWO_FACTOR_ALREADY_ENABLED => "Two-factor authentication is already enabled.",
+            error::Client::TWO_FACTOR_INVALID_CODE => "That code isn't valid, try again.",
+            error::Client::TWO_FACTOR_NOT_ENABLED => "Two-factor authentication isn't enabled.",
+            error::Client::TWO_FACTOR_RATE_LIMITED => "Too many attempts, wait a few minutes.",
+            error::Client::TWO_FACTOR_REQUIRED => "Enter the code of your authenticator app.",
             error::Client::USER_ALREADY_LOGGED_IN => "User already logged in.",
             error::Client::USERNAME_IN_USE => "Username is already in use.",
             error::Client::RELATION_USER_ALREADY_BLOCKED => "This user is already blocked.",
             error::Client::RELATION_USER_ALREADY_FRIEND => "This user is already your friend.",
             error::Client::RELATION_USER_BLOCKED => "This user is blocked.",
             error::Client::RELATION_USER_BLOCKED_YOU => "This user has you blocked.",
+            error::Client::WEBHOOK_INVALID_URL => "Webhook url must be a valid http(s) url.",
+            error::Client::WEBHOOK_CHANNEL_NOT_PART_OF_SERVER => "Webhook channels must be part of the server.",
+            error::Client::WEBHOOK_INVALID_NAME => "Webhook name must be between 1 and 32 characters.",
+            error::Client::WEBHOOK_INVALID_TOKEN => "Webhook token is invalid.",
+            error::Client::WEBHOOK_RATE_LIMITED => "Webhook is sending too many messages, try again later.",
         }
     }
 }
@@ -1,6 +1,12 @@
 pub mod authenticate;
 mod login;
+mod oidc;
+mod password_reset;
 mod register;
+mod verify_email;
 
 pub use login::*;
+pub use oidc::*;
+pub use password_reset::*;
 pub use register::*;
+pub use verify_email::*;
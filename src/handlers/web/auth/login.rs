@@ -1,20 +1,23 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
 use askama::Template;
-use axum::extract::{
-    ConnectInfo,
-    State,
-};
+use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Form;
 use axum_htmx::HxRedirect;
 use tower_cookies::Cookies;
 
 use crate::handlers::logic;
-use crate::handlers::logic::auth::LoginRequest;
+use crate::handlers::logic::auth::{
+    LoginRequest,
+    LoginStatus,
+    TwoFactorCodeRequest,
+};
 use crate::handlers::web::HtmxError;
-use crate::middleware::auth::Ctx;
+use crate::middleware::auth::{
+    ClientInfo,
+    Ctx,
+};
 use crate::model::AppState;
 
 #[derive(Template)]
@@ -25,9 +28,12 @@ pub struct Login<'a>
     nav_button_value: &'a str,
     nav_button_crud_type: &'a str,
     nav_button_route: &'a str,
+    //name and display name of every oidc provider
+    providers: Vec<(&'a str, &'a str)>,
 }
 pub async fn get_login(
-    ctx_option: Option<Ctx>
+    State(state): State<Arc<AppState>>,
+    ctx_option: Option<Ctx>,
 ) -> Result<impl IntoResponse, HtmxError>
 {
     if ctx_option.is_some()
@@ -42,6 +48,16 @@ pub async fn get_login(
         nav_button_value: "Register",
         nav_button_crud_type: "get",
         nav_button_route: "/register",
+        providers: state
+            .oidc_providers
+            .iter()
+            .map(|provider| {
+                (
+                    provider.name.as_str(),
+                    provider.display_name.as_str(),
+                )
+            })
+            .collect(),
     };
 
     Ok((
@@ -54,7 +70,7 @@ pub async fn get_login(
 pub async fn post_login(
     State(state): State<Arc<AppState>>,
     jar: Cookies,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    client: ClientInfo,
     ctx_option: Option<Ctx>,
     Form(form): Form<LoginRequest>,
 ) -> Result<impl IntoResponse, HtmxError>
@@ -66,26 +82,83 @@ pub async fn post_login(
         ));
     }
 
-    let login_result = logic::auth::login(
-        &state,
-        &jar,
-        addr.to_string(),
-        &form,
-    )
-    .await;
+    let login_result = logic::auth::login(&state, &jar, client, &form).await;
 
-    if let Err(err) = login_result
+    match login_result
     {
-        Err(HtmxError::new_form_error(
-            err.client,
-        ))
-    }
-    else
-    {
-        Ok((
+        Ok(LoginStatus::LoggedIn) => Ok((
             HxRedirect("/".parse().unwrap()),
             "",
         )
-            .into_response())
+            .into_response()),
+        Ok(LoginStatus::TwoFactorRequired) => Ok((
+            HxRedirect("/login/two-factor".parse().unwrap()),
+            "",
+        )
+            .into_response()),
+        Err(err) => Err(HtmxError::new_form_error(
+            err.client,
+        )),
+    }
+}
+
+#[derive(Template)]
+#[template(path = "login_two_factor.html")]
+pub struct LoginTwoFactor<'a>
+{
+    title: &'a str,
+    nav_button_value: &'a str,
+    nav_button_crud_type: &'a str,
+    nav_button_route: &'a str,
+}
+
+pub async fn get_login_two_factor(
+    ctx_option: Option<Ctx>
+) -> Result<impl IntoResponse, HtmxError>
+{
+    if ctx_option.is_some()
+    {
+        return Err(HtmxError::new(
+            crate::model::error::Client::USER_ALREADY_LOGGED_IN,
+        ));
     }
+
+    let page = LoginTwoFactor {
+        title: "Two-factor authentication",
+        nav_button_value: "Login",
+        nav_button_crud_type: "get",
+        nav_button_route: "/login",
+    };
+
+    Ok((
+        HxRedirect("/login/two-factor".parse().unwrap()),
+        page,
+    )
+        .into_response())
+}
+
+pub async fn post_login_two_factor(
+    State(state): State<Arc<AppState>>,
+    jar: Cookies,
+    client: ClientInfo,
+    ctx_option: Option<Ctx>,
+    Form(form): Form<TwoFactorCodeRequest>,
+) -> Result<impl IntoResponse, HtmxError>
+{
+    if ctx_option.is_some()
+    {
+        return Err(HtmxError::new(
+            crate::model::error::Client::USER_ALREADY_LOGGED_IN,
+        ));
+    }
+
+    logic::auth::login_two_factor(&state, &jar, client, &form)
+        .await
+        .map_err(|err| HtmxError::new_form_error(err.client))?;
+
+    Ok((
+        HxRedirect("/".parse().unwrap()),
+        "",
+    )
+        .into_response())
 }
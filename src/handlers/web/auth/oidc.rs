@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use axum::extract::{
+    Path,
+    Query,
+    State,
+};
+use axum::response::{
+    IntoResponse,
+    Redirect,
+};
+use tower_cookies::Cookies;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::{
+    LoginStatus,
+    OidcCallbackRequest,
+};
+use crate::handlers::web::HtmxError;
+use crate::middleware::auth::{
+    ClientInfo,
+    Ctx,
+};
+use crate::model::{
+    error,
+    AppState,
+};
+
+use super::Notice;
+
+//plain links and redirects, the browser leaves the site in between
+pub async fn get_oidc_login(
+    State(state): State<Arc<AppState>>,
+    jar: Cookies,
+    ctx_option: Option<Ctx>,
+    Path(provider): Path<String>,
+) -> Result<impl IntoResponse, HtmxError>
+{
+    if ctx_option.is_some()
+    {
+        return Err(HtmxError::new(
+            error::Client::USER_ALREADY_LOGGED_IN,
+        ));
+    }
+
+    match logic::auth::start_oidc_login(&state, &jar, &provider).await
+    {
+        Ok(url) => Ok(Redirect::to(&url).into_response()),
+        Err(err) => Ok(Notice::new(
+            "Login",
+            "Login failed",
+            err.client.translate_error(),
+        )
+        .into_response()),
+    }
+}
+
+pub async fn get_oidc_callback(
+    State(state): State<Arc<AppState>>,
+    jar: Cookies,
+    client: ClientInfo,
+    Path(provider): Path<String>,
+    Query(query): Query<OidcCallbackRequest>,
+) -> impl IntoResponse
+{
+    match logic::auth::finish_oidc_login(
+        &state,
+        &jar,
+        client,
+        &provider,
+        &query,
+    )
+    .await
+    {
+        Ok(LoginStatus::LoggedIn) => Redirect::to("/").into_response(),
+        Ok(LoginStatus::TwoFactorRequired) =>
+        {
+            Redirect::to("/login/two-factor").into_response()
+        },
+        Err(err) =>
+        {
+            tracing::warn!(error = %err, "oidc login failed");
+
+            Notice::new(
+                "Login",
+                "Login failed",
+                err.client.translate_error(),
+            )
+            .into_response()
+        },
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::{
+    Query,
+    State,
+};
+use axum::response::IntoResponse;
+use axum::Form;
+use axum_htmx::HxRedirect;
+
+use super::Notice;
+use crate::handlers::logic;
+use crate::handlers::logic::auth::{
+    MailRequest,
+    MailTokenRequest,
+    ResetPasswordRequest,
+};
+use crate::handlers::web::HtmxError;
+use crate::middleware::auth::{
+    ClientInfo,
+    Ctx,
+};
+use crate::model::AppState;
+
+#[derive(Template)]
+#[template(path = "forgot_password.html")]
+pub struct ForgotPassword<'a>
+{
+    title: &'a str,
+    nav_button_value: &'a str,
+    nav_button_crud_type: &'a str,
+    nav_button_route: &'a str,
+}
+
+pub async fn get_forgot_password(
+    ctx_option: Option<Ctx>
+) -> Result<impl IntoResponse, HtmxError>
+{
+    if ctx_option.is_some()
+    {
+        return Err(HtmxError::new(
+            crate::model::error::Client::USER_ALREADY_LOGGED_IN,
+        ));
+    }
+
+    let page = ForgotPassword {
+        title: "Forgot password",
+        nav_button_value: "Login",
+        nav_button_crud_type: "get",
+        nav_button_route: "/login",
+    };
+
+    Ok((
+        HxRedirect("/password/forgot".parse().unwrap()),
+        page,
+    )
+        .into_response())
+}
+
+pub async fn post_forgot_password(
+    State(state): State<Arc<AppState>>,
+    client: ClientInfo,
+    Form(form): Form<MailRequest>,
+) -> Result<impl IntoResponse, HtmxError>
+{
+    logic::auth::request_password_reset(&state, &client, &form)
+        .await
+        .map_err(|err| HtmxError::new_form_error(err.client))?;
+
+    Ok((
+        HxRedirect("/password/forgot/sent".parse().unwrap()),
+        "",
+    )
+        .into_response())
+}
+
+pub async fn get_forgot_password_sent() -> impl IntoResponse
+{
+    Notice::new(
+        "Forgot password",
+        "Check your inbox",
+        "If an account uses that email, a link to reset the password is on its way.",
+    )
+}
+
+#[derive(Template)]
+#[template(path = "reset_password.html")]
+pub struct ResetPassword<'a>
+{
+    title: &'a str,
+    nav_button_value: &'a str,
+    nav_button_crud_type: &'a str,
+    nav_button_route: &'a str,
+    token: &'a str,
+}
+
+//link in the mail, the token is checked once the form is send
+pub async fn get_reset_password(
+    Query(query): Query<MailTokenRequest>
+) -> impl IntoResponse
+{
+    ResetPassword {
+        title: "Reset password",
+        nav_button_value: "Login",
+        nav_button_crud_type: "get",
+        nav_button_route: "/login",
+        token: query.token_ref(),
+    }
+    .into_response()
+}
+
+pub async fn post_reset_password(
+    State(state): State<Arc<AppState>>,
+    Form(form): Form<ResetPasswordRequest>,
+) -> Result<impl IntoResponse, HtmxError>
+{
+    logic::auth::reset_password(&state, &form)
+        .await
+        .map_err(|err| HtmxError::new_form_error(err.client))?;
+
+    Ok((
+        HxRedirect("/login".parse().unwrap()),
+        "",
+    )
+        .into_response())
+}
@@ -1,16 +1,11 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
 use askama::Template;
-use axum::extract::{
-    ConnectInfo,
-    State,
-};
+use axum::extract::State;
 use axum::response::IntoResponse;
 use axum::Form;
 use axum_htmx::HxRedirect;
 use serde::Deserialize;
-use tower_cookies::Cookies;
 
 use crate::handlers::logic;
 use crate::handlers::web::HtmxError;
@@ -61,8 +56,6 @@ pub struct RegisterRequest
 }
 pub async fn post_register(
     State(state): State<Arc<AppState>>,
-    jar: Cookies,
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     ctx_option: Option<Ctx>,
     Form(form): Form<RegisterRequest>,
 ) -> Result<impl IntoResponse, HtmxError>
@@ -87,26 +80,15 @@ pub async fn post_register(
         form.password,
     );
 
-    let user = logic::user::create_user(&state, &create_request)
+    logic::user::create_user(&state, &create_request)
         .await
         .map_err(|err| HtmxError::new_form_error(err.client))?;
 
     //schedule some task to see if ban evader
 
-    let refresh_token = logic::auth::cookies::get_refresh_token(
-        &state,
-        &jar,
-        addr.to_string(),
-        user,
-    )
-    .await
-    .map_err(|err| HtmxError::new_form_error(err.client))?;
-
-    logic::auth::cookies::create_auth_cookies(&jar, refresh_token)
-        .map_err(|err| HtmxError::new_form_error(err.client))?;
-
+    //logging in has to wait for the mailed link
     Ok((
-        HxRedirect("/".parse().unwrap()),
+        HxRedirect("/verify-email/sent".parse().unwrap()),
         "",
     )
         .into_response())
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::extract::{
+    Query,
+    State,
+};
+use axum::response::IntoResponse;
+
+use crate::handlers::logic;
+use crate::handlers::logic::auth::MailTokenRequest;
+use crate::model::AppState;
+
+#[derive(Template)]
+#[template(path = "notice.html")]
+pub struct Notice<'a>
+{
+    pub title: &'a str,
+    pub nav_button_value: &'a str,
+    pub nav_button_crud_type: &'a str,
+    pub nav_button_route: &'a str,
+    pub heading: &'a str,
+    pub message: &'a str,
+}
+
+impl<'a> Notice<'a>
+{
+    #[must_use]
+    pub fn new(
+        title: &'a str,
+        heading: &'a str,
+        message: &'a str,
+    ) -> Self
+    {
+        Self {
+            title,
+            nav_button_value: "Login",
+            nav_button_crud_type: "get",
+            nav_button_route: "/login",
+            heading,
+            message,
+        }
+    }
+}
+
+//link in the mail, so a plain get
+pub async fn get_verify_email(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<MailTokenRequest>,
+) -> impl IntoResponse
+{
+    match logic::auth::verify_email(&state, &query).await
+    {
+        Ok(()) => Notice::new(
+            "Email verified",
+            "Email verified",
+            "Your email is verified, you can log in now.",
+        ),
+        Err(err) => Notice::new(
+            "Email verification",
+            "Verification failed",
+            err.client.translate_error(),
+        ),
+    }
+}
+
+pub async fn get_verify_email_sent() -> impl IntoResponse
+{
+    Notice::new(
+        "Verify your email",
+        "Check your inbox",
+        "We've sent you a link to verify your email, open it to finish registering.",
+    )
+}
@@ -1,4 +1,11 @@
+pub mod health;
 pub mod log;
+pub mod mail;
+pub mod oidc;
+pub mod shutdown;
+pub mod telemetry;
+pub mod tls;
+pub mod webhook;
 
 pub struct FileWriter
 {
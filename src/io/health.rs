@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::model::{
+    error,
+    health,
+};
+use crate::server_error;
+
+use super::FileWriter;
+
+/// the folder still exists and takes new files.
+#[async_trait]
+impl health::Check for FileWriter
+{
+    async fn check<'err>(&self) -> error::Result<'err, ()>
+    {
+        let path = Path::new(&self.folder_path).join(format!(
+            ".health-{}",
+            Uuid::now_v7()
+        ));
+
+        tokio::fs::write(&path, b"ok").await.map_err(|err| {
+            server_error!(
+                error::Kind::Write,
+                error::OnType::Health
+            )
+            .add_debug_info("file error", err.to_string())
+            .add_debug_info(
+                "path",
+                path.display().to_string(),
+            )
+        })?;
+
+        tokio::fs::remove_file(&path).await.map_err(|err| {
+            server_error!(
+                error::Kind::Delete,
+                error::OnType::Health
+            )
+            .add_debug_info("file error", err.to_string())
+            .add_debug_info(
+                "path",
+                path.display().to_string(),
+            )
+        })
+    }
+}
@@ -1,12 +1,22 @@
+use std::collections::HashMap;
 use std::path::Path;
 
 use crate::model::error;
 use crate::model::log::{
     self,
+    Entry,
+    EntryError,
+    Filter,
     RequestLogLine,
+    RequestLogLinePersonal,
 };
 use crate::server_error;
 use axum::async_trait;
+use chrono::{
+    Days,
+    NaiveDate,
+};
+use serde::Deserialize;
 use tokio::fs::OpenOptions;
 use tokio::io::{
     AsyncWriteExt,
@@ -79,4 +89,265 @@ impl log::Repository for FileWriter
 
         Ok(())
     }
+
+    /// scans the daily files from new to old, skipping days outside the range.
+    async fn get_logs<'input, 'err>(
+        &'input self,
+        filter: &'input Filter,
+        limit: usize,
+    ) -> error::Result<'err, Vec<Entry>>
+    {
+        let mut dir =
+            tokio::fs::read_dir(&self.folder_path)
+                .await
+                .map_err(|err| {
+                    server_error!(
+                        error::Kind::FileOpening,
+                        error::OnType::Log
+                    )
+                    .add_debug_info("file error", err.to_string())
+                    .add_debug_info(
+                        "path",
+                        self.folder_path.clone(),
+                    )
+                })?;
+
+        //file names are local dates, a day of slack covers any offset
+        let first_day = filter
+            .from
+            .and_then(|from| from.date_naive().checked_sub_days(Days::new(1)));
+        let last_day = filter
+            .to
+            .and_then(|to| to.date_naive().checked_add_days(Days::new(1)));
+
+        let mut days = Vec::new();
+
+        while let Ok(Some(dir_entry)) = dir.next_entry().await
+        {
+            let path = dir_entry.path();
+
+            let Some(day) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".log"))
+                .and_then(|name| name.parse::<NaiveDate>().ok())
+            else
+            {
+                continue;
+            };
+
+            if first_day.is_none_or(|first_day| first_day <= day)
+                && last_day.is_none_or(|last_day| day <= last_day)
+            {
+                days.push((day, path));
+            }
+        }
+
+        days.sort_by_key(|(day, _)| std::cmp::Reverse(*day));
+
+        let mut entries = Vec::new();
+
+        for (_, path) in days
+        {
+            let content =
+                tokio::fs::read_to_string(&path).await.map_err(|err| {
+                    server_error!(
+                        error::Kind::Read,
+                        error::OnType::Log
+                    )
+                    .add_debug_info("file error", err.to_string())
+                    .add_debug_info(
+                        "path",
+                        path.display().to_string(),
+                    )
+                })?;
+
+            //appended in order, so the newest are at the end
+            for line in content.lines().rev()
+            {
+                let Ok(file_line) = serde_json::from_str::<FileLogLine>(line)
+                else
+                {
+                    continue;
+                };
+
+                let entry = Entry::from(file_line);
+
+                if filter.matches(&entry)
+                {
+                    entries.push(entry);
+
+                    if entries.len() >= limit
+                    {
+                        return Ok(entries);
+                    }
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// prints a json line per request, for when logs are collected from stdout.
+pub struct Stdout;
+
+#[async_trait]
+impl log::Repository for Stdout
+{
+    async fn create_log<'input, 'err>(
+        &'input self,
+        log: RequestLogLine<'input>,
+    ) -> error::Result<'err, ()>
+    {
+        let json = serde_json::to_string(&log).map_err(|err| {
+            server_error!(
+                error::Kind::Parse,
+                error::OnType::Log
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        println!("{json}");
+
+        Ok(())
+    }
+
+    async fn get_logs<'input, 'err>(
+        &'input self,
+        _filter: &'input Filter,
+        _limit: usize,
+    ) -> error::Result<'err, Vec<Entry>>
+    {
+        Err(server_error!(
+            error::Kind::NotImplemented,
+            error::OnType::Log
+        ))
+    }
+}
+
+//a `RequestLogLine` as the file sink wrote it
+#[derive(Deserialize)]
+struct FileLogLine
+{
+    req_id: String,
+    timestamp: String,
+    user_info: RequestLogLinePersonal,
+    req_path: String,
+    req_method: String,
+    client_error_type: Option<String>,
+    server_error: Option<FileServerError>,
+}
+
+#[derive(Deserialize)]
+struct FileServerError
+{
+    kind: FileTag,
+    on_type: FileTag,
+    stack: String,
+    line_nr: u32,
+    #[serde(default)]
+    debug_info: HashMap<String, String>,
+    pub_info: Option<String>,
+    child: Option<Box<FileServerError>>,
+}
+
+//`Kind` and `OnType` are written as `{"type": <variant>}`
+#[derive(Deserialize)]
+struct FileTag
+{
+    #[serde(rename = "type")]
+    name: String,
+}
+
+impl From<FileLogLine> for Entry
+{
+    fn from(value: FileLogLine) -> Self
+    {
+        let server_error = value.server_error.map(|server_error| {
+            let mut errors = Vec::new();
+            let mut next = Some(Box::new(server_error));
+
+            while let Some(server_error) = next
+            {
+                errors.push(EntryError {
+                    kind: server_error.kind.name,
+                    on_type: server_error.on_type.name,
+                    stack: format!(
+                        "{}: {}",
+                        server_error.stack, server_error.line_nr
+                    ),
+                    debug_info: server_error.debug_info,
+                    pub_info: server_error.pub_info,
+                });
+
+                next = server_error.child;
+            }
+
+            errors
+        });
+
+        Self {
+            req_id: value.req_id,
+            timestamp: value.timestamp,
+            user_info: value.user_info,
+            req_path: value.req_path,
+            req_method: value.req_method,
+            client_error_type: value.client_error_type,
+            server_error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::io::log::FileLogLine;
+    use crate::model::error;
+    use crate::model::log::{
+        Entry,
+        RequestLogLine,
+        RequestLogLinePersonal,
+    };
+    use crate::server_error;
+
+    #[test]
+    fn test_read_written_log_line_is_valid()
+    {
+        let server_error = server_error!(
+            server_error!(
+                error::Kind::NotFound,
+                error::OnType::User
+            )
+            .add_debug_info("user", String::from("1")),
+            error::Kind::Fetch,
+            error::OnType::Chat
+        );
+
+        let log_line = RequestLogLine {
+            req_id: String::from("req"),
+            timestamp: chrono::Utc::now().to_string(),
+            user_info: RequestLogLinePersonal::new(
+                Some(String::from("user")),
+                None,
+            ),
+            req_path: String::from("/api/chat"),
+            req_method: String::from("GET"),
+            client_error_type: None,
+            server_error: Some(server_error),
+        };
+
+        let json = serde_json::to_string(&log_line).unwrap();
+        let entry =
+            Entry::from(serde_json::from_str::<FileLogLine>(&json).unwrap());
+
+        let errors = entry.server_error.unwrap();
+        assert_eq!(2, errors.len());
+        assert_eq!("Fetch", errors[0].kind);
+        assert_eq!("User", errors[1].on_type);
+        assert_eq!(
+            Some(&String::from("1")),
+            errors[1].debug_info.get("user")
+        );
+    }
 }
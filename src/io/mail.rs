@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use axum::async_trait;
+use chrono::Utc;
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::model::error;
+use crate::model::mail::{
+    self,
+    Mail,
+};
+use crate::server_error;
+
+use super::FileWriter;
+
+//nothing is delivered, every mail lands in its own file for local testing
+#[async_trait]
+impl mail::Mailer for FileWriter
+{
+    async fn send_mail<'input, 'err>(
+        &'input self,
+        mail: Mail,
+    ) -> error::Result<'err, ()>
+    {
+        let path = Path::new(&self.folder_path).join(format!(
+            "{}-{}.txt",
+            Utc::now().format("%Y%m%dT%H%M%S"),
+            Uuid::now_v7()
+        ));
+
+        let content = format!(
+            "To: {}\nSubject: {}\n\n{}\n",
+            mail.to, mail.subject, mail.body
+        );
+
+        fs::write(&path, content).await.map_err(|err| {
+            server_error!(
+                error::Kind::Write,
+                error::OnType::Mail
+            )
+            .add_debug_info("file error", err.to_string())
+            .add_debug_info(
+                "path",
+                path.display().to_string(),
+            )
+        })?;
+
+        tracing::info!(
+            to = %mail.to,
+            path = %path.display(),
+            "mail written"
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,696 @@
+use std::time::Duration;
+
+use argon2::password_hash::rand_core::{
+    OsRng,
+    RngCore,
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{
+    decode,
+    decode_header,
+    Algorithm,
+    DecodingKey,
+    Validation,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{
+    Digest,
+    Sha256,
+};
+use tokio::sync::OnceCell;
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+const REQUEST_TIMEOUT_SEC: u64 = 10;
+const SCOPES: &str = "openid email profile";
+
+/// the part of the discovery document the code flow needs.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Metadata
+{
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// the claims of the id token we care about.
+#[derive(Clone, Debug, Deserialize)]
+pub struct IdentityClaims
+{
+    pub sub: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub preferred_username: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse
+{
+    id_token: String,
+}
+
+/// the random values of one login attempt,
+/// kept by the browser until the provider redirects back.
+#[derive(Clone, Debug)]
+pub struct AuthorizationRequest
+{
+    pub state: String,
+    pub nonce: String,
+    //pkce, only its hash is send to the provider up front
+    pub code_verifier: String,
+}
+
+impl AuthorizationRequest
+{
+    #[must_use]
+    pub fn new() -> Self
+    {
+        Self {
+            state: internal_random_value(),
+            nonce: internal_random_value(),
+            code_verifier: internal_random_value(),
+        }
+    }
+
+    /// S256 challenge of the verifier.
+    #[must_use]
+    pub fn code_challenge(&self) -> String
+    {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(
+            self.code_verifier.as_bytes(),
+        ))
+    }
+}
+
+impl Default for AuthorizationRequest
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+/// an openid connect provider users can log in with.
+pub struct Provider
+{
+    pub name: String,
+    pub display_name: String,
+    issuer: String,
+    client_id: String,
+    client_secret: String,
+    client: reqwest::Client,
+    //discovered on first use
+    metadata: OnceCell<Metadata>,
+}
+
+impl Provider
+{
+    #[must_use]
+    pub fn new(
+        name: String,
+        display_name: String,
+        issuer: &str,
+        client_id: String,
+        client_secret: String,
+    ) -> Self
+    {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                REQUEST_TIMEOUT_SEC,
+            ))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            name,
+            display_name,
+            issuer: issuer.trim_end_matches('/').to_string(),
+            client_id,
+            client_secret,
+            client,
+            metadata: OnceCell::new(),
+        }
+    }
+
+    pub async fn authorization_url<'err>(
+        &self,
+        redirect_uri: &str,
+        request: &AuthorizationRequest,
+    ) -> error::Result<'err, String>
+    {
+        let metadata = self.metadata().await?;
+
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.client_id),
+                ("redirect_uri", redirect_uri),
+                ("scope", SCOPES),
+                ("state", &request.state),
+                ("nonce", &request.nonce),
+                (
+                    "code_challenge",
+                    &request.code_challenge(),
+                ),
+                (
+                    "code_challenge_method",
+                    "S256",
+                ),
+            ],
+        )
+        .map_err(|err| {
+            server_error!(Kind::Parse, OnType::Oidc)
+                .add_debug_info("provider", self.name.clone())
+                .add_debug_info("error", err.to_string())
+        })?;
+
+        Ok(url.to_string())
+    }
+
+    /// trades the code of the callback for the id token.
+    pub async fn exchange_code<'err>(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: &str,
+    ) -> error::Result<'err, String>
+    {
+        let metadata = self.metadata().await?;
+
+        let response = self
+            .client
+            .post(&metadata.token_endpoint)
+            .form(&[
+                (
+                    "grant_type",
+                    "authorization_code",
+                ),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", &self.client_id),
+                (
+                    "client_secret",
+                    &self.client_secret,
+                ),
+                ("code_verifier", code_verifier),
+            ])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| {
+                server_error!(Kind::Fetch, OnType::Oidc)
+                    .add_debug_info("provider", self.name.clone())
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+        let token_response: TokenResponse =
+            response.json().await.map_err(|err| {
+                server_error!(Kind::Parse, OnType::Oidc)
+                    .add_debug_info("provider", self.name.clone())
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+        Ok(token_response.id_token)
+    }
+
+    /// checks the signature against the keys of the provider,
+    /// the issuer, audience, expiration and the nonce of the login attempt.
+    pub async fn verify_id_token<'err>(
+        &self,
+        id_token: &str,
+        nonce: &str,
+    ) -> error::Result<'err, IdentityClaims>
+    {
+        let metadata = self.metadata().await?;
+
+        let header = decode_header(id_token).map_err(|err| {
+            server_error!(Kind::InValid, OnType::Oidc)
+                .add_debug_info("error", err.to_string())
+        })?;
+
+        //symmetric algorithms would mean trusting anyone with the client secret
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        )
+        {
+            return Err(
+                server_error!(Kind::InValid, OnType::Oidc).add_debug_info(
+                    "alg",
+                    format!("{:?}", header.alg),
+                ),
+            );
+        }
+
+        let jwks: JwkSet = self
+            .client
+            .get(&metadata.jwks_uri)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| {
+                server_error!(Kind::Fetch, OnType::Oidc)
+                    .add_debug_info("provider", self.name.clone())
+                    .add_debug_info("error", err.to_string())
+            })?
+            .json()
+            .await
+            .map_err(|err| {
+                server_error!(Kind::Parse, OnType::Oidc)
+                    .add_debug_info("provider", self.name.clone())
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+        let jwk = match &header.kid
+        {
+            Some(kid) => jwks.find(kid),
+            None => jwks.keys.first(),
+        }
+        .ok_or_else(|| {
+            server_error!(Kind::NotFound, OnType::Oidc).add_debug_info(
+                "kid",
+                format!("{:?}", header.kid),
+            )
+        })?;
+
+        let decoding_key = DecodingKey::from_jwk(jwk).map_err(|err| {
+            server_error!(Kind::InValid, OnType::Oidc)
+                .add_debug_info("error", err.to_string())
+        })?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_audience(&[&self.client_id]);
+
+        let claims = decode::<IdentityClaims>(
+            id_token,
+            &decoding_key,
+            &validation,
+        )
+        .map_err(|err| {
+            server_error!(Kind::InValid, OnType::Oidc)
+                .add_debug_info("error", err.to_string())
+        })?
+        .claims;
+
+        if claims.nonce.as_deref() != Some(nonce)
+        {
+            return Err(
+                server_error!(Kind::InValid, OnType::Oidc)
+                    .add_debug_info("provider", self.name.clone())
+                    .add_debug_info(
+                        "nonce",
+                        format!("{:?}", claims.nonce),
+                    ),
+            );
+        }
+
+        Ok(claims)
+    }
+
+    async fn metadata<'err>(&self) -> error::Result<'err, &Metadata>
+    {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.issuer
+                );
+
+                let metadata: Metadata = self
+                    .client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(|err| {
+                        server_error!(Kind::Fetch, OnType::Oidc)
+                            .add_debug_info("url", url.clone())
+                            .add_debug_info("error", err.to_string())
+                    })?
+                    .json()
+                    .await
+                    .map_err(|err| {
+                        server_error!(Kind::Parse, OnType::Oidc)
+                            .add_debug_info("url", url.clone())
+                            .add_debug_info("error", err.to_string())
+                    })?;
+
+                //a document claiming another issuer could hand out foreign keys
+                if metadata.issuer.trim_end_matches('/') != self.issuer
+                {
+                    return Err(
+                        server_error!(Kind::InValid, OnType::Oidc)
+                            .add_debug_info("issuer", metadata.issuer),
+                    );
+                }
+
+                Ok(metadata)
+            })
+            .await
+    }
+}
+
+/// what is needed to set up a [`Provider`], no `Debug` to keep the secret out of logs.
+#[derive(Clone)]
+pub struct Settings
+{
+    pub name: String,
+    pub display_name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+impl Settings
+{
+    /// reads the comma separated names in `OIDC_PROVIDERS`,
+    /// then `OIDC_<NAME>_ISSUER`, `_CLIENT_ID`, `_CLIENT_SECRET`
+    /// and optionally `_DISPLAY_NAME` for every name.
+    pub fn list_from_source<'err>(
+        source: &Source
+    ) -> error::Result<'err, Vec<Self>>
+    {
+        let Some(names) = source.get("OIDC_PROVIDERS")
+        else
+        {
+            return Ok(Vec::new());
+        };
+
+        let mut settings = Vec::new();
+
+        for name in names.split(',').map(str::trim).filter(|n| !n.is_empty())
+        {
+            let prefix = format!("OIDC_{}", name.to_uppercase());
+            let read = |suffix: &str| {
+                let key = format!("{prefix}_{suffix}");
+
+                match source.get(&key).map(str::trim)
+                {
+                    Some(value) if !value.is_empty() => Ok(value.to_string()),
+                    _ => Err(
+                        server_error!(Kind::NotFound, OnType::Oidc)
+                            .add_debug_info("key", key),
+                    ),
+                }
+            };
+
+            settings.push(Self {
+                name: name.to_lowercase(),
+                display_name: read("DISPLAY_NAME")
+                    .unwrap_or_else(|_| name.to_string()),
+                issuer: read("ISSUER")?,
+                client_id: read("CLIENT_ID")?,
+                client_secret: read("CLIENT_SECRET")?,
+            });
+        }
+
+        Ok(settings)
+    }
+}
+
+/// every configured provider, looked up by the name in the login url.
+#[derive(Default)]
+pub struct Providers(Vec<Provider>);
+
+impl Providers
+{
+    #[must_use]
+    pub fn new(providers: Vec<Provider>) -> Self
+    {
+        Self(providers)
+    }
+
+    #[must_use]
+    pub fn from_settings(settings: &[Settings]) -> Self
+    {
+        Self::new(
+            settings
+                .iter()
+                .map(|settings| {
+                    Provider::new(
+                        settings.name.clone(),
+                        settings.display_name.clone(),
+                        &settings.issuer,
+                        settings.client_id.clone(),
+                        settings.client_secret.clone(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub fn get(
+        &self,
+        name: &str,
+    ) -> Option<&Provider>
+    {
+        self.0.iter().find(|provider| provider.name == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Provider>
+    {
+        self.0.iter()
+    }
+}
+
+fn internal_random_value() -> String
+{
+    let mut random_number = [0u8; 32];
+    OsRng.fill_bytes(&mut random_number);
+
+    URL_SAFE_NO_PAD.encode(random_number)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+
+    use axum::extract::State;
+    use axum::routing::{
+        get,
+        post,
+    };
+    use axum::{
+        Form,
+        Json,
+        Router,
+    };
+    use chrono::{
+        Duration,
+        Utc,
+    };
+    use jsonwebtoken::{
+        encode,
+        Header,
+    };
+    use serde_json::{
+        json,
+        Value,
+    };
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+
+    use crate::io::oidc::{
+        AuthorizationRequest,
+        Provider,
+    };
+    use crate::middleware::auth::{
+        KeyRing,
+        SigningKey,
+    };
+
+    const CLIENT_ID: &str = "mogcord";
+    const REDIRECT_URI: &str = "http://127.0.0.1/login/oidc/mock/callback";
+
+    struct MockProvider
+    {
+        issuer: String,
+        keys: KeyRing,
+        //the challenge the authorization request was made with
+        code_challenge: String,
+        nonce: String,
+    }
+
+    //serves discovery, jwks and a token endpoint that checks the pkce verifier
+    async fn spawn_mock_provider(request: &AuthorizationRequest) -> String
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!(
+            "http://{}",
+            listener.local_addr().unwrap()
+        );
+
+        let key = SigningKey::generate(String::from("mock-key")).unwrap();
+        let mock = Arc::new(MockProvider {
+            issuer: issuer.clone(),
+            keys: KeyRing::new(vec![key], "mock-key").unwrap(),
+            code_challenge: request.code_challenge(),
+            nonce: request.nonce.clone(),
+        });
+
+        let app = Router::new()
+            .route(
+                "/.well-known/openid-configuration",
+                get(|State(mock): State<Arc<MockProvider>>| async move {
+                    Json(json!({
+                        "issuer": mock.issuer,
+                        "authorization_endpoint": format!("{}/authorize", mock.issuer),
+                        "token_endpoint": format!("{}/token", mock.issuer),
+                        "jwks_uri": format!("{}/jwks", mock.issuer),
+                    }))
+                }),
+            )
+            .route(
+                "/jwks",
+                get(|State(mock): State<Arc<MockProvider>>| async move {
+                    Json(mock.keys.jwks())
+                }),
+            )
+            .route(
+                "/token",
+                post(
+                    |State(mock): State<Arc<MockProvider>>,
+                     Form(form): Form<HashMap<String, String>>| async move {
+                        let verifier = AuthorizationRequest {
+                            state: String::new(),
+                            nonce: String::new(),
+                            code_verifier: form["code_verifier"].clone(),
+                        };
+
+                        if verifier.code_challenge() != mock.code_challenge
+                            || form["code"] != "mock-code"
+                        {
+                            return Err(axum::http::StatusCode::BAD_REQUEST);
+                        }
+
+                        let signing_key = mock.keys.active();
+                        let mut header = Header::new(signing_key.algorithm);
+                        header.kid = Some(signing_key.kid.clone());
+
+                        let claims: Value = json!({
+                            "iss": mock.issuer,
+                            "aud": CLIENT_ID,
+                            "sub": "mock-subject",
+                            "exp": (Utc::now() + Duration::minutes(5)).timestamp(),
+                            "nonce": mock.nonce,
+                            "email": "mock@example.com",
+                            "email_verified": true,
+                        });
+
+                        let id_token =
+                            encode(&header, &claims, signing_key.encoding())
+                                .unwrap();
+
+                        Ok(Json(json!({ "id_token": id_token })))
+                    },
+                ),
+            )
+            .with_state(mock);
+
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        issuer
+    }
+
+    #[tokio::test]
+    async fn test_code_flow_against_mock_provider_is_valid()
+    {
+        let request = AuthorizationRequest::new();
+        let issuer = spawn_mock_provider(&request).await;
+
+        let provider = Provider::new(
+            String::from("mock"),
+            String::from("Mock"),
+            &issuer,
+            String::from(CLIENT_ID),
+            String::from("secret"),
+        );
+
+        let url = provider
+            .authorization_url(REDIRECT_URI, &request)
+            .await
+            .unwrap();
+
+        assert!(url.starts_with(&format!(
+            "{issuer}/authorize?"
+        )));
+        assert!(url.contains(&format!(
+            "code_challenge={}",
+            request.code_challenge()
+        )));
+        assert!(url.contains("code_challenge_method=S256"));
+
+        let id_token = provider
+            .exchange_code(
+                "mock-code",
+                REDIRECT_URI,
+                &request.code_verifier,
+            )
+            .await
+            .unwrap();
+
+        let claims = provider
+            .verify_id_token(&id_token, &request.nonce)
+            .await
+            .unwrap();
+
+        assert_eq!("mock-subject", claims.sub);
+        assert_eq!(
+            Some("mock@example.com"),
+            claims.email.as_deref()
+        );
+        assert!(claims.email_verified);
+
+        assert!(provider
+            .verify_id_token(&id_token, "other-nonce")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exchange_code_wrong_verifier_is_invalid()
+    {
+        let request = AuthorizationRequest::new();
+        let issuer = spawn_mock_provider(&request).await;
+
+        let provider = Provider::new(
+            String::from("mock"),
+            String::from("Mock"),
+            &issuer,
+            String::from(CLIENT_ID),
+            String::from("secret"),
+        );
+
+        assert!(provider
+            .exchange_code(
+                "mock-code",
+                REDIRECT_URI,
+                &AuthorizationRequest::new().code_verifier,
+            )
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use tokio::signal;
+
+/// resolves on ctrl+c, or on SIGTERM (what docker sends on stop) on unix.
+pub async fn signal()
+{
+    let interrupt = async {
+        if let Err(err) = signal::ctrl_c().await
+        {
+            tracing::error!(error = %err, "couldnt listen for ctrl+c");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate())
+        {
+            Ok(mut terminate) =>
+            {
+                terminate.recv().await;
+            },
+            Err(err) =>
+            {
+                tracing::error!(error = %err, "couldnt listen for SIGTERM");
+                std::future::pending::<()>().await;
+            },
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = interrupt => tracing::info!("received ctrl+c"),
+        () = terminate => tracing::info!("received SIGTERM"),
+    }
+}
@@ -0,0 +1,144 @@
+use std::str::FromStr;
+
+use tracing_subscriber::EnvFilter;
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+/// how diagnostics are written to stdout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format
+{
+    //human readable, for local development
+    Pretty,
+    //a json object per line, for log collectors
+    Json,
+}
+
+impl FromStr for Format
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.trim().to_lowercase().as_str()
+        {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "expected pretty or json, got {s}"
+            )),
+        }
+    }
+}
+
+/// level and format of the `tracing` output.
+#[derive(Clone, Debug)]
+pub struct Telemetry
+{
+    //`EnvFilter` directives, e.g. `info` or `mogcord=debug,mongodb=warn`
+    pub level: String,
+    pub format: Format,
+}
+
+impl Default for Telemetry
+{
+    fn default() -> Self
+    {
+        Self {
+            level: String::from("info"),
+            format: Format::Pretty,
+        }
+    }
+}
+
+impl Telemetry
+{
+    /// reads `LOG_LEVEL` and `LOG_FORMAT`.
+    pub fn from_source<'err>(source: &Source) -> error::Result<'err, Self>
+    {
+        let mut telemetry = Self::default();
+
+        if let Some(level) = source.get("LOG_LEVEL")
+        {
+            EnvFilter::try_new(level).map_err(|err| {
+                server_error!(Kind::Parse, OnType::Log)
+                    .add_debug_info(
+                        "key",
+                        String::from("LOG_LEVEL"),
+                    )
+                    .add_debug_info("error", err.to_string())
+            })?;
+
+            telemetry.level = level.to_string();
+        }
+
+        if let Some(format) = source.get("LOG_FORMAT")
+        {
+            telemetry.format = format.parse().map_err(|err: String| {
+                server_error!(Kind::Parse, OnType::Log)
+                    .add_debug_info(
+                        "key",
+                        String::from("LOG_FORMAT"),
+                    )
+                    .add_debug_info("error", err)
+            })?;
+        }
+
+        Ok(telemetry)
+    }
+
+    /// installs the global subscriber, only the first call in a process counts.
+    pub fn init(&self)
+    {
+        let filter = EnvFilter::try_new(&self.level)
+            .unwrap_or_else(|_| EnvFilter::new("info"));
+
+        let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+        let result = match self.format
+        {
+            Format::Pretty => builder.try_init(),
+            Format::Json => builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .try_init(),
+        };
+
+        if let Err(err) = result
+        {
+            tracing::warn!(error = %err, "tracing was already initialized");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::io::telemetry::Format;
+
+    #[test]
+    fn test_parse_format_is_valid()
+    {
+        assert_eq!(
+            Ok(Format::Json),
+            " JSON".parse()
+        );
+        assert_eq!(
+            Ok(Format::Pretty),
+            "pretty".parse()
+        );
+    }
+
+    #[test]
+    fn test_parse_format_is_invalid()
+    {
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,476 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::sync::{
+    Arc,
+    Mutex,
+    RwLock,
+};
+use std::time::{
+    Duration,
+    SystemTime,
+};
+use std::{
+    fs,
+    io,
+};
+
+use axum::extract::ConnectInfo;
+use axum::Router;
+use hyper::body::Incoming;
+use hyper::Request;
+use hyper_util::rt::{
+    TokioExecutor,
+    TokioIo,
+};
+use hyper_util::server::conn::auto;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio_rustls::rustls::crypto::{
+    ring,
+    CryptoProvider,
+};
+use tokio_rustls::rustls::server::{
+    ClientHello,
+    ResolvesServerCert,
+};
+use tokio_rustls::rustls::sign::CertifiedKey;
+use tokio_rustls::rustls::{
+    self,
+    ServerConfig,
+};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+//a client that doesnt finish its handshake in time only holds a socket
+const HANDSHAKE_TIMEOUT_SEC: u64 = 10;
+const DEFAULT_RELOAD_INTERVAL_SEC: u64 = 60;
+
+/// the pem files of the certificate chain and its private key.
+#[derive(Clone, Debug)]
+pub struct Settings
+{
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    //how often the files are checked for a renewed certificate
+    pub reload_interval_sec: u64,
+}
+
+impl Settings
+{
+    /// reads `TLS_CERT_PATH`, `TLS_KEY_PATH` and `TLS_RELOAD_INTERVAL_SEC`,
+    /// `None` when neither path is set.
+    pub fn from_source<'err>(
+        source: &Source
+    ) -> error::Result<'err, Option<Self>>
+    {
+        let (cert_path, key_path) = match (
+            source.get("TLS_CERT_PATH"),
+            source.get("TLS_KEY_PATH"),
+        )
+        {
+            (Some(cert_path), Some(key_path)) => (
+                PathBuf::from(cert_path),
+                PathBuf::from(key_path),
+            ),
+            (None, None) => return Ok(None),
+            _ =>
+            {
+                return Err(
+                    server_error!(Kind::InValid, OnType::Tls).add_debug_info(
+                        "reason",
+                        String::from(
+                            "TLS_CERT_PATH and TLS_KEY_PATH go together",
+                        ),
+                    ),
+                );
+            },
+        };
+
+        let reload_interval_sec = source.parse_or(
+            "TLS_RELOAD_INTERVAL_SEC",
+            DEFAULT_RELOAD_INTERVAL_SEC,
+            OnType::Tls,
+        )?;
+
+        Ok(Some(Self {
+            cert_path,
+            key_path,
+            reload_interval_sec,
+        }))
+    }
+}
+
+/// hands out the current certificate, swapped when its files change on disk.
+#[derive(Debug)]
+pub struct CertResolver
+{
+    settings: Settings,
+    current: RwLock<Arc<CertifiedKey>>,
+    //modification times of the cert and key that `current` was read from
+    modified: Mutex<(SystemTime, SystemTime)>,
+}
+
+impl CertResolver
+{
+    pub fn new<'err>(settings: Settings) -> error::Result<'err, Self>
+    {
+        let modified = internal_modified(&settings)?;
+        let current = internal_read_certified_key(&settings)?;
+
+        Ok(Self {
+            settings,
+            current: RwLock::new(Arc::new(current)),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    /// reads the files again when they changed since the last read,
+    /// returns whether the certificate got swapped.
+    ///
+    /// on an error the old certificate stays, a renewal that wrote the cert
+    /// but not yet its key is picked up on the next call.
+    pub fn reload<'err>(&self) -> error::Result<'err, bool>
+    {
+        let modified = internal_modified(&self.settings)?;
+
+        if *self.modified.lock().unwrap() == modified
+        {
+            return Ok(false);
+        }
+
+        let certified_key = internal_read_certified_key(&self.settings)?;
+
+        *self.current.write().unwrap() = Arc::new(certified_key);
+        *self.modified.lock().unwrap() = modified;
+
+        Ok(true)
+    }
+
+    /// checks the files every `reload_interval_sec` for as long as the process runs.
+    pub fn watch(self: Arc<Self>)
+    {
+        let period = Duration::from_secs(self.settings.reload_interval_sec);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+
+            loop
+            {
+                interval.tick().await;
+
+                match self.reload()
+                {
+                    Ok(true) => tracing::info!(
+                        cert_path = %self.settings.cert_path.display(),
+                        "reloaded the tls certificate"
+                    ),
+                    Ok(false) => (),
+                    Err(err) => tracing::warn!(
+                        error = %err,
+                        "couldnt reload the tls certificate, keeping the old one"
+                    ),
+                }
+            }
+        });
+    }
+}
+
+impl ResolvesServerCert for CertResolver
+{
+    fn resolve(
+        &self,
+        _client_hello: ClientHello<'_>,
+    ) -> Option<Arc<CertifiedKey>>
+    {
+        Some(Arc::clone(
+            &self.current.read().unwrap(),
+        ))
+    }
+}
+
+/// accepts tls 1.2 and 1.3, offering http/2 before http/1.1 over alpn.
+pub fn acceptor<'err>(
+    resolver: Arc<CertResolver>
+) -> error::Result<'err, TlsAcceptor>
+{
+    let mut config = ServerConfig::builder_with_provider(Arc::new(
+        ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|err| {
+        server_error!(Kind::Unexpected, OnType::Tls)
+            .add_debug_info("error", err.to_string())
+    })?
+    .with_no_client_auth()
+    .with_cert_resolver(resolver);
+
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(TlsAcceptor::from(Arc::new(
+        config,
+    )))
+}
+
+/// `axum::serve` over tls, the app gets the same `ConnectInfo<SocketAddr>`.
+///
+/// once `shutdown` resolves no new connections are accepted, open ones finish
+/// their requests and the future resolves when all of them closed.
+pub async fn serve(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    app: Router,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()>
+{
+    //every connection holds a receiver, `closed` resolves once all dropped theirs
+    let (drain, draining) = watch::channel(());
+    tokio::pin!(shutdown);
+
+    loop
+    {
+        let (stream, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted
+            {
+                Ok(accepted) => accepted,
+                Err(err) =>
+                {
+                    //mostly too many open files, give the others time to close
+                    tracing::warn!(error = %err, "couldnt accept a connection");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                },
+            },
+            () = &mut shutdown => break,
+        };
+
+        tokio::spawn(internal_serve_connection(
+            stream,
+            remote_addr,
+            acceptor.clone(),
+            app.clone(),
+            draining.clone(),
+        ));
+    }
+
+    drop(listener);
+    drop(draining);
+
+    let _ = drain.send(());
+    drain.closed().await;
+
+    Ok(())
+}
+
+async fn internal_serve_connection(
+    stream: tokio::net::TcpStream,
+    remote_addr: SocketAddr,
+    acceptor: TlsAcceptor,
+    app: Router,
+    mut draining: watch::Receiver<()>,
+)
+{
+    let stream = match tokio::time::timeout(
+        Duration::from_secs(HANDSHAKE_TIMEOUT_SEC),
+        acceptor.accept(stream),
+    )
+    .await
+    {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(err)) =>
+        {
+            tracing::debug!(error = %err, %remote_addr, "tls handshake failed");
+            return;
+        },
+        Err(_) =>
+        {
+            tracing::debug!(%remote_addr, "tls handshake timed out");
+            return;
+        },
+    };
+
+    let service = hyper::service::service_fn(
+        move |mut request: Request<Incoming>| {
+            request.extensions_mut().insert(ConnectInfo(remote_addr));
+            app.clone().oneshot(request)
+        },
+    );
+
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let connection =
+        builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+    tokio::pin!(connection);
+
+    tokio::select! {
+        result = connection.as_mut() =>
+        {
+            if let Err(err) = result
+            {
+                tracing::debug!(error = %err, %remote_addr, "connection closed");
+            }
+            return;
+        },
+        _ = draining.changed() => connection.as_mut().graceful_shutdown(),
+    }
+
+    if let Err(err) = connection.await
+    {
+        tracing::debug!(error = %err, %remote_addr, "connection closed");
+    }
+}
+
+fn internal_modified<'err>(
+    settings: &Settings
+) -> error::Result<'err, (SystemTime, SystemTime)>
+{
+    Ok((
+        internal_modified_at(&settings.cert_path)?,
+        internal_modified_at(&settings.key_path)?,
+    ))
+}
+
+fn internal_modified_at<'err>(path: &Path) -> error::Result<'err, SystemTime>
+{
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|err| internal_read_error(path, &err))
+}
+
+fn internal_read_certified_key<'err>(
+    settings: &Settings
+) -> error::Result<'err, CertifiedKey>
+{
+    let cert_pem = fs::read(&settings.cert_path)
+        .map_err(|err| internal_read_error(&settings.cert_path, &err))?;
+    let key_pem = fs::read(&settings.key_path)
+        .map_err(|err| internal_read_error(&settings.key_path, &err))?;
+
+    parse_certified_key(&cert_pem, &key_pem)
+}
+
+/// parses a pem certificate chain, leaf first, and the pem private key of the
+/// leaf.
+pub fn parse_certified_key<'err>(
+    cert_pem: &[u8],
+    key_pem: &[u8],
+) -> error::Result<'err, CertifiedKey>
+{
+    let certs = rustls_pemfile::certs(&mut &*cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| internal_parse_error("cert", &err))?;
+
+    if certs.is_empty()
+    {
+        return Err(internal_parse_error(
+            "cert",
+            &"no certificate found",
+        ));
+    }
+
+    let key = rustls_pemfile::private_key(&mut &*key_pem)
+        .map_err(|err| internal_parse_error("key", &err))?
+        .ok_or_else(|| internal_parse_error("key", &"no private key found"))?;
+
+    let provider: CryptoProvider = ring::default_provider();
+
+    CertifiedKey::from_der(certs, key, &provider)
+        .map_err(|err: rustls::Error| internal_parse_error("key", &err))
+}
+
+fn internal_read_error<'err>(
+    path: &Path,
+    err: &io::Error,
+) -> error::Server<'err>
+{
+    server_error!(Kind::Read, OnType::Tls)
+        .add_debug_info(
+            "path",
+            path.display().to_string(),
+        )
+        .add_debug_info("error", err.to_string())
+}
+
+fn internal_parse_error<'err>(
+    file: &'static str,
+    err: &dyn std::fmt::Display,
+) -> error::Server<'err>
+{
+    server_error!(Kind::Parse, OnType::Tls)
+        .add_debug_info("file", file.to_string())
+        .add_debug_info("error", err.to_string())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use crate::io::tls::{
+        parse_certified_key,
+        Settings,
+    };
+    use crate::model::config::Source;
+
+    #[test]
+    fn test_settings_from_source_is_valid()
+    {
+        assert!(Settings::from_source(&Source::default()).unwrap().is_none());
+
+        let settings = Settings::from_source(&Source::new(HashMap::from([
+            (
+                String::from("TLS_CERT_PATH"),
+                String::from("/certs/fullchain.pem"),
+            ),
+            (
+                String::from("TLS_KEY_PATH"),
+                String::from("/certs/privkey.pem"),
+            ),
+        ])))
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            60,
+            settings.reload_interval_sec
+        );
+    }
+
+    #[test]
+    fn test_settings_from_source_is_invalid()
+    {
+        assert!(
+            Settings::from_source(&Source::new(HashMap::from([
+                (
+                    String::from("TLS_CERT_PATH"),
+                    String::from("/certs/fullchain.pem"),
+                )
+            ])))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_certified_key_is_invalid()
+    {
+        assert!(parse_certified_key(b"", b"").is_err());
+        assert!(
+            parse_certified_key(
+                b"-----BEGIN CERTIFICATE-----\nnot base64\n-----END CERTIFICATE-----\n",
+                b"",
+            )
+            .is_err()
+        );
+    }
+}
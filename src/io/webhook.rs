@@ -0,0 +1,974 @@
+use std::net::{
+    IpAddr,
+    Ipv4Addr,
+    SocketAddr,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{
+    Hmac,
+    Mac,
+};
+use reqwest::dns::{
+    Addrs,
+    Name,
+    Resolve,
+    Resolving,
+};
+use reqwest::{
+    redirect,
+    StatusCode,
+};
+use sha2::Sha256;
+use tokio::sync::{
+    broadcast,
+    Semaphore,
+};
+
+use crate::dto::{
+    ObjectToDTO,
+    WebhookPayload,
+};
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    OnType,
+};
+use crate::model::event::Event;
+use crate::model::webhook::{
+    self,
+    Delivery,
+    EventType,
+    Webhook,
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Mogcord-Signature";
+pub const EVENT_HEADER: &str = "X-Mogcord-Event";
+pub const DELIVERY_HEADER: &str = "X-Mogcord-Delivery";
+
+const REQUEST_TIMEOUT_SEC: u64 = 10;
+const DEFAULT_MAX_CONCURRENT_DELIVERIES: usize = 32;
+const LOCAL_TARGET_ERROR: &str = "target resolves to a local address";
+
+/// what the dispatcher may connect to and how much it does at once.
+#[derive(Clone, Debug)]
+pub struct Settings
+{
+    //lets targets resolve to loopback and private addresses, for local receivers
+    pub allow_local_targets: bool,
+    //deliveries in flight, including their retries, new ones wait for a free slot
+    pub max_concurrent_deliveries: usize,
+}
+
+impl Settings
+{
+    /// reads `WEBHOOK_ALLOW_LOCAL_TARGETS` and `WEBHOOK_MAX_CONCURRENT_DELIVERIES`.
+    pub fn from_source<'err>(source: &Source) -> error::Result<'err, Self>
+    {
+        let defaults = Self::default();
+
+        Ok(Self {
+            allow_local_targets: source.parse_or(
+                "WEBHOOK_ALLOW_LOCAL_TARGETS",
+                defaults.allow_local_targets,
+                OnType::Config,
+            )?,
+            max_concurrent_deliveries: source.parse_or(
+                "WEBHOOK_MAX_CONCURRENT_DELIVERIES",
+                defaults.max_concurrent_deliveries,
+                OnType::Config,
+            )?,
+        })
+    }
+}
+
+impl Default for Settings
+{
+    fn default() -> Self
+    {
+        Self {
+            allow_local_targets: false,
+            max_concurrent_deliveries: DEFAULT_MAX_CONCURRENT_DELIVERIES,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy
+{
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl RetryPolicy
+{
+    /// delay before the given (1 based) attempt, doubles every retry.
+    #[must_use]
+    pub fn delay_before(
+        &self,
+        attempt: u32,
+    ) -> Duration
+    {
+        let exponent = attempt.saturating_sub(2).min(16);
+
+        self.base_delay.saturating_mul(2u32.pow(exponent))
+    }
+}
+
+impl Default for RetryPolicy
+{
+    fn default() -> Self
+    {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// listens on the event bus and posts matching events to the webhooks of a server.
+///
+/// every attempt is written to the delivery log,
+/// network errors, `429` and `5xx` responses are retried with exponential backoff.
+pub struct Dispatcher
+{
+    client: reqwest::Client,
+    webhooks: Arc<dyn webhook::Repository>,
+    retry_policy: RetryPolicy,
+    allow_local_targets: bool,
+    delivery_slots: Arc<Semaphore>,
+}
+
+impl Dispatcher
+{
+    #[must_use]
+    pub fn new(
+        webhooks: Arc<dyn webhook::Repository>,
+        retry_policy: RetryPolicy,
+        settings: &Settings,
+    ) -> Self
+    {
+        //a redirect could point anywhere, the resolver only sees the first host
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                REQUEST_TIMEOUT_SEC,
+            ))
+            .redirect(redirect::Policy::none());
+
+        if !settings.allow_local_targets
+        {
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+
+        Self {
+            //without the resolver the default client would connect anywhere
+            client: builder.build().expect("Couldnt build webhook client"),
+            webhooks,
+            retry_policy,
+            allow_local_targets: settings.allow_local_targets,
+            delivery_slots: Arc::new(Semaphore::new(
+                settings.max_concurrent_deliveries.max(1),
+            )),
+        }
+    }
+
+    pub fn spawn(
+        self: Arc<Self>,
+        mut receiver: broadcast::Receiver<Event>,
+    )
+    {
+        tokio::spawn(async move {
+            loop
+            {
+                match receiver.recv().await
+                {
+                    Ok(event) => Arc::clone(&self).handle_event(event).await,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) =>
+                    {
+                        tracing::warn!(
+                            skipped,
+                            "webhook dispatcher lagged behind"
+                        );
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    async fn handle_event(
+        self: Arc<Self>,
+        event: Event,
+    )
+    {
+        let Some(server_id) = event.kind.server_id()
+        else
+        {
+            return;
+        };
+        let Some(event_type) = EventType::from_event(&event.kind)
+        else
+        {
+            return;
+        };
+
+        let webhooks =
+            match self.webhooks.get_webhooks_by_server(server_id).await
+            {
+                Ok(webhooks) => webhooks,
+                Err(err) =>
+                {
+                    tracing::error!(error = %err, "webhook dispatcher failed");
+                    return;
+                },
+            };
+
+        let webhooks: Vec<Webhook> = webhooks
+            .into_iter()
+            .filter(|webhook| webhook.is_subscribed_to(&event.kind))
+            .collect();
+
+        if webhooks.is_empty()
+        {
+            return;
+        }
+
+        let event_id = event.id.clone();
+        let Ok(body) = serde_json::to_string(&WebhookPayload::obj_to_dto(
+            event,
+        ))
+        else
+        {
+            return;
+        };
+
+        //one task per target so a slow receiver doesnt hold up the others,
+        //waiting for a slot holds up the event loop instead of piling up tasks
+        for webhook in webhooks
+        {
+            let Ok(slot) =
+                Arc::clone(&self.delivery_slots).acquire_owned().await
+            else
+            {
+                return;
+            };
+            let dispatcher = Arc::clone(&self);
+            let event_id = event_id.clone();
+            let event_type = event_type.clone();
+            let body = body.clone();
+
+            tokio::spawn(async move {
+                dispatcher
+                    .deliver(
+                        &webhook,
+                        &event_id,
+                        event_type,
+                        &body,
+                    )
+                    .await;
+
+                drop(slot);
+            });
+        }
+    }
+
+    /// posts the body to the webhook until it succeeds or the attempts run out.
+    ///
+    /// returns `true` if one of the attempts succeeded.
+    pub async fn deliver(
+        &self,
+        webhook: &Webhook,
+        event_id: &str,
+        event_type: EventType,
+        body: &str,
+    ) -> bool
+    {
+        let signature = sign_payload(&webhook.secret, body);
+        let event_name = serde_json::to_value(&event_type)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        //ip literals never reach the resolver
+        if !self.allow_local_targets && is_local_ip_target(&webhook.target_url)
+        {
+            let delivery = Delivery::new(
+                webhook.id.clone(),
+                event_id.to_string(),
+                event_type,
+                1,
+                None,
+                Some(LOCAL_TARGET_ERROR.to_string()),
+            );
+
+            if let Err(err) = self.webhooks.create_delivery(delivery).await
+            {
+                tracing::error!(error = %err, "webhook dispatcher failed");
+            }
+
+            return false;
+        }
+
+        for attempt in 1..=self.retry_policy.max_attempts.max(1)
+        {
+            if attempt > 1
+            {
+                tokio::time::sleep(self.retry_policy.delay_before(attempt))
+                    .await;
+            }
+
+            let result = self
+                .client
+                .post(&webhook.target_url)
+                .header(
+                    reqwest::header::CONTENT_TYPE,
+                    "application/json",
+                )
+                .header(SIGNATURE_HEADER, &signature)
+                .header(EVENT_HEADER, &event_name)
+                .header(DELIVERY_HEADER, event_id)
+                .body(body.to_string())
+                .send()
+                .await;
+
+            let (status, error, should_retry) = match result
+            {
+                Ok(response) =>
+                {
+                    let status = response.status();
+                    (
+                        Some(status.as_u16()),
+                        None,
+                        is_retryable_status(status),
+                    )
+                },
+                Err(err) => (
+                    None,
+                    Some(err.to_string()),
+                    true,
+                ),
+            };
+
+            let delivery = Delivery::new(
+                webhook.id.clone(),
+                event_id.to_string(),
+                event_type.clone(),
+                attempt,
+                status,
+                error,
+            );
+            let succeeded = delivery.succeeded;
+
+            if let Err(err) = self.webhooks.create_delivery(delivery).await
+            {
+                tracing::error!(error = %err, "webhook dispatcher failed");
+            }
+
+            if succeeded || !should_retry
+            {
+                return succeeded;
+            }
+        }
+
+        false
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool
+{
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// resolves webhook hosts and drops the local addresses,
+/// as resolver of the client the checked address is also the one connected to.
+struct PublicResolver;
+
+impl Resolve for PublicResolver
+{
+    fn resolve(
+        &self,
+        name: Name,
+    ) -> Resolving
+    {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((name.as_str(), 0))
+                    .await?
+                    .filter(|addr| !is_local_ip(addr.ip()))
+                    .collect();
+
+            if addrs.is_empty()
+            {
+                return Err(LOCAL_TARGET_ERROR.into());
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn is_local_ip_target(target_url: &str) -> bool
+{
+    let Ok(url) = reqwest::Url::parse(target_url)
+    else
+    {
+        return false;
+    };
+
+    //ipv6 hosts keep their brackets
+    url.host_str()
+        .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+        .and_then(|host| host.parse::<IpAddr>().ok())
+        .is_some_and(is_local_ip)
+}
+
+/// loopback, private, link local, unique local and unspecified addresses,
+/// the shared (cgnat) and reserved ranges, also when embedded in ipv6.
+#[must_use]
+pub fn is_local_ip(ip: IpAddr) -> bool
+{
+    match ip
+    {
+        IpAddr::V4(ip) =>
+        {
+            let [first, second, third, _] = ip.octets();
+
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                //0.0.0.0/8, "this network"
+                || first == 0
+                //100.64.0.0/10, shared address space, cloud metadata lives here too
+                || (first == 100 && second & 0b1100_0000 == 64)
+                //192.0.0.0/24, protocol assignments
+                || (first == 192 && second == 0 && third == 0)
+                //198.18.0.0/15, benchmarking
+                || (first == 198 && second & 0b1111_1110 == 18)
+        },
+        IpAddr::V6(ip) =>
+        {
+            let segments = ip.segments();
+
+            //64:ff9b::/96 translates to the ipv4 address in its last 32 bits
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+            {
+                let [.., a, b, c, d] = ip.octets();
+
+                return is_local_ip(IpAddr::V4(Ipv4Addr::new(
+                    a, b, c, d,
+                )));
+            }
+
+            ip.is_loopback()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                || ip.is_unspecified()
+                //ipv4 mapped (::ffff:0:0/96) and compatible (::/96) addresses
+                || ip.to_ipv4().is_some_and(|ip| is_local_ip(IpAddr::V4(ip)))
+        },
+    }
+}
+
+/// signature send along in the [`SIGNATURE_HEADER`], formatted as `sha256=<hex hmac>`.
+#[must_use]
+pub fn sign_payload(
+    secret: &str,
+    body: &str,
+) -> String
+{
+    //hmac accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("hmac accepts keys of any size");
+    mac.update(body.as_bytes());
+
+    format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::atomic::{
+        AtomicU32,
+        Ordering,
+    };
+    use std::sync::{
+        Arc,
+        Mutex,
+    };
+    use std::time::Duration;
+
+    use axum::extract::State;
+    use axum::http::{
+        HeaderMap,
+        StatusCode,
+    };
+    use axum::routing::post;
+    use axum::{
+        async_trait,
+        Router,
+    };
+    use tokio::net::TcpListener;
+
+    use crate::io::webhook::{
+        is_local_ip,
+        sign_payload,
+        Dispatcher,
+        RetryPolicy,
+        Settings,
+        LOCAL_TARGET_ERROR,
+        SIGNATURE_HEADER,
+    };
+    use crate::model::webhook::{
+        self,
+        Delivery,
+        EventType,
+        Incoming,
+        Webhook,
+    };
+    use crate::model::{
+        error,
+        Pagination,
+    };
+    use crate::server_error;
+
+    #[derive(Default)]
+    struct MemoryWebhooks
+    {
+        deliveries: Mutex<Vec<Delivery>>,
+    }
+
+    #[async_trait]
+    impl webhook::Repository for MemoryWebhooks
+    {
+        async fn create_webhook<'input, 'err>(
+            &'input self,
+            webhook: Webhook,
+        ) -> error::Result<'err, Webhook>
+        {
+            Ok(webhook)
+        }
+
+        async fn get_webhook<'input, 'err>(
+            &'input self,
+            _server_id: &'input str,
+            _webhook_id: &'input str,
+        ) -> error::Result<'err, Webhook>
+        {
+            Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            ))
+        }
+
+        async fn get_webhooks_by_server<'input, 'err>(
+            &'input self,
+            _server_id: &'input str,
+        ) -> error::Result<'err, Vec<Webhook>>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn delete_webhook<'input, 'err>(
+            &'input self,
+            _server_id: &'input str,
+            _webhook_id: &'input str,
+        ) -> error::Result<'err, ()>
+        {
+            Ok(())
+        }
+
+        async fn create_delivery<'input, 'err>(
+            &'input self,
+            delivery: Delivery,
+        ) -> error::Result<'err, ()>
+        {
+            self.deliveries.lock().unwrap().push(delivery);
+            Ok(())
+        }
+
+        async fn get_deliveries<'input, 'err>(
+            &'input self,
+            _webhook_id: &'input str,
+            _pagination: Pagination,
+        ) -> error::Result<'err, Vec<Delivery>>
+        {
+            Ok(self.deliveries.lock().unwrap().clone())
+        }
+
+        async fn create_incoming_webhook<'input, 'err>(
+            &'input self,
+            webhook: Incoming,
+        ) -> error::Result<'err, Incoming>
+        {
+            Ok(webhook)
+        }
+
+        async fn get_incoming_webhook<'input, 'err>(
+            &'input self,
+            _webhook_id: &'input str,
+        ) -> error::Result<'err, Incoming>
+        {
+            Err(server_error!(
+                error::Kind::NotFound,
+                error::OnType::Webhook
+            ))
+        }
+
+        async fn get_incoming_webhooks_by_server<'input, 'err>(
+            &'input self,
+            _server_id: &'input str,
+        ) -> error::Result<'err, Vec<Incoming>>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn delete_incoming_webhook<'input, 'err>(
+            &'input self,
+            _server_id: &'input str,
+            _webhook_id: &'input str,
+        ) -> error::Result<'err, ()>
+        {
+            Ok(())
+        }
+    }
+
+    struct Receiver
+    {
+        calls: AtomicU32,
+        fail_first: u32,
+        secret: String,
+    }
+
+    async fn receive(
+        State(receiver): State<Arc<Receiver>>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode
+    {
+        let call = receiver.calls.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if signature != sign_payload(&receiver.secret, &body)
+        {
+            return StatusCode::UNAUTHORIZED;
+        }
+
+        if call <= receiver.fail_first
+        {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+
+        StatusCode::NO_CONTENT
+    }
+
+    async fn internal_spawn_receiver(
+        fail_first: u32,
+        secret: String,
+    ) -> (String, Arc<Receiver>)
+    {
+        let receiver = Arc::new(Receiver {
+            calls: AtomicU32::new(0),
+            fail_first,
+            secret,
+        });
+
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .with_state(Arc::clone(&receiver));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (
+            format!("http://{addr}/hook"),
+            receiver,
+        )
+    }
+
+    fn internal_retry_policy(max_attempts: u32) -> RetryPolicy
+    {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(5),
+        }
+    }
+
+    //the receivers of these tests listen on localhost
+    fn internal_local_settings() -> Settings
+    {
+        Settings {
+            allow_local_targets: true,
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn test_is_local_ip_is_valid()
+    {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "0.1.2.3",
+            "100.64.0.1",
+            "100.100.100.200",
+            "100.127.255.254",
+            "192.0.0.8",
+            "198.18.0.1",
+            "198.19.255.254",
+            "64:ff9b::10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "::10.0.0.1",
+            "::127.0.0.1",
+        ]
+        {
+            assert!(
+                is_local_ip(ip.parse().unwrap()),
+                "{ip}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_local_ip_is_invalid()
+    {
+        for ip in [
+            "93.184.216.34",
+            "172.32.0.1",
+            "2606:4700::1111",
+            "::ffff:93.184.216.34",
+            "100.63.255.255",
+            "100.128.0.1",
+            "192.0.2.1",
+            "198.17.255.255",
+            "198.20.0.1",
+            "64:ff9b::93.184.216.34",
+            "::93.184.216.34",
+        ]
+        {
+            assert!(
+                !is_local_ip(ip.parse().unwrap()),
+                "{ip}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_retry_policy_delay_before_is_valid()
+    {
+        let policy = RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::from_secs(1),
+        };
+
+        assert_eq!(
+            Duration::from_secs(1),
+            policy.delay_before(2)
+        );
+        assert_eq!(
+            Duration::from_secs(2),
+            policy.delay_before(3)
+        );
+        assert_eq!(
+            Duration::from_secs(4),
+            policy.delay_before(4)
+        );
+    }
+
+    #[test]
+    fn test_sign_payload_is_valid()
+    {
+        let signature = sign_payload("secret", "{}");
+
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(
+            signature,
+            sign_payload("secret", "{}")
+        );
+        assert_ne!(
+            signature,
+            sign_payload("other", "{}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retried_until_success_is_valid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "http://localhost/hook",
+            Vec::new(),
+            vec![EventType::MemberJoined],
+        )
+        .unwrap();
+        let (url, receiver) =
+            internal_spawn_receiver(2, webhook.secret.clone()).await;
+        let webhook = Webhook {
+            target_url: url,
+            ..webhook
+        };
+
+        let repo = Arc::new(MemoryWebhooks::default());
+        let dispatcher = Dispatcher::new(
+            Arc::clone(&repo) as Arc<dyn webhook::Repository>,
+            internal_retry_policy(5),
+            &internal_local_settings(),
+        );
+
+        let succeeded = dispatcher
+            .deliver(
+                &webhook,
+                "event",
+                EventType::MemberJoined,
+                "{}",
+            )
+            .await;
+
+        let deliveries = repo.deliveries.lock().unwrap();
+
+        assert!(succeeded);
+        assert_eq!(
+            3,
+            receiver.calls.load(Ordering::SeqCst)
+        );
+        assert_eq!(3, deliveries.len());
+        assert_eq!(
+            Some(503),
+            deliveries[0].response_status
+        );
+        assert!(deliveries[2].succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_after_max_attempts_is_invalid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "http://localhost/hook",
+            Vec::new(),
+            vec![EventType::MemberJoined],
+        )
+        .unwrap();
+        let (url, receiver) = internal_spawn_receiver(
+            u32::MAX,
+            webhook.secret.clone(),
+        )
+        .await;
+        let webhook = Webhook {
+            target_url: url,
+            ..webhook
+        };
+
+        let repo = Arc::new(MemoryWebhooks::default());
+        let dispatcher = Dispatcher::new(
+            Arc::clone(&repo) as Arc<dyn webhook::Repository>,
+            internal_retry_policy(3),
+            &internal_local_settings(),
+        );
+
+        let succeeded = dispatcher
+            .deliver(
+                &webhook,
+                "event",
+                EventType::MemberJoined,
+                "{}",
+            )
+            .await;
+
+        assert!(!succeeded);
+        assert_eq!(
+            3,
+            receiver.calls.load(Ordering::SeqCst)
+        );
+        assert!(repo
+            .deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|delivery| !delivery.succeeded));
+    }
+
+    #[tokio::test]
+    async fn test_deliver_to_local_target_is_invalid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "http://localhost/hook",
+            Vec::new(),
+            vec![EventType::MemberJoined],
+        )
+        .unwrap();
+        let (url, receiver) =
+            internal_spawn_receiver(0, webhook.secret.clone()).await;
+        let by_name = Webhook {
+            target_url: url.replace("127.0.0.1", "localhost"),
+            ..webhook.clone()
+        };
+        let by_ip = Webhook {
+            target_url: url.replace(
+                "127.0.0.1",
+                "[::ffff:127.0.0.1]",
+            ),
+            ..webhook
+        };
+
+        let repo = Arc::new(MemoryWebhooks::default());
+        let dispatcher = Dispatcher::new(
+            Arc::clone(&repo) as Arc<dyn webhook::Repository>,
+            internal_retry_policy(1),
+            &Settings::default(),
+        );
+
+        for webhook in [by_name, by_ip]
+        {
+            assert!(
+                !dispatcher
+                    .deliver(
+                        &webhook,
+                        "event",
+                        EventType::MemberJoined,
+                        "{}",
+                    )
+                    .await
+            );
+        }
+
+        assert_eq!(
+            0,
+            receiver.calls.load(Ordering::SeqCst)
+        );
+        assert!(repo
+            .deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|delivery| !delivery.succeeded));
+        assert!(
+            repo.deliveries.lock().unwrap()[1]
+                .error
+                .as_deref()
+                .is_some_and(|error| error == LOCAL_TARGET_ERROR)
+        );
+    }
+}
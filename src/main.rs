@@ -1,9 +1,31 @@
 use dotenv::dotenv;
-use std::env;
+use futures_util::future::{
+    self,
+    BoxFuture,
+};
+use futures_util::FutureExt;
+use std::future::{
+    Future,
+    IntoFuture,
+};
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::{
+    env,
+    io,
+};
 use tokio::net::TcpListener;
+use tokio::sync::watch;
 
+use mogcord::db::migration::Migrator;
+use mogcord::db::MongolDB;
 use mogcord::handlers;
+use mogcord::io::{
+    shutdown,
+    tls,
+};
+use mogcord::model::config::Config;
+use mogcord::model::metrics::Metrics;
 use mogcord::model::AppState;
 
 #[tokio::main]
@@ -11,36 +33,197 @@ async fn main() -> Result<(), Box<dyn std::error::Error>>
 {
     dotenv().ok();
 
-    let mongoldb_connection_string = env::var("MONGOLDB_CONNECTION")
-        .unwrap_or("mongodb://localhost:27017".to_string());
+    let config = Config::load().expect("Couldnt load config");
 
-    let api_socket =
-        env::var("API_SOCKET").unwrap_or("127.0.0.1:3000".to_string());
+    config.telemetry.init();
 
-    let log_path = env::var("LOG_PATH").unwrap_or("./logs_server".to_string());
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str)
+    {
+        //the image has no shell or curl, so docker checks through the binary
+        Some("healthcheck") => return healthcheck(&config).await,
+        Some("migrate") => return migrate(&config, &args[1..]).await,
+        _ => (),
+    }
 
-    let state = AppState::new(
-        &mongoldb_connection_string,
-        &log_path,
-    )
-    .await;
+    let api_socket = config.api_socket;
+    let state = AppState::new(config).await;
 
-    let app = handlers::new(state);
+    let drain_timeout = state.config.shutdown_drain_timeout();
+    let app = handlers::new(Arc::clone(&state));
 
     let listener = TcpListener::bind(api_socket).await.unwrap();
 
-    println!(
-        "{:<12} - {:?}",
-        "Listening",
-        listener.local_addr()
-    );
+    let (stop, stopped) = watch::channel(());
+
+    let server: BoxFuture<io::Result<()>> = match &state.config.tls
+    {
+        Some(settings) =>
+        {
+            let resolver = Arc::new(
+                tls::CertResolver::new(settings.clone())
+                    .expect("Couldnt load the tls certificate"),
+            );
+            Arc::clone(&resolver).watch();
+
+            let acceptor =
+                tls::acceptor(resolver).expect("Couldnt configure tls");
+
+            tracing::info!(
+                addr = %listener.local_addr()?,
+                "listening on https"
+            );
+
+            tls::serve(
+                listener,
+                acceptor,
+                app,
+                internal_stopped(&stopped),
+            )
+            .boxed()
+        },
+        None =>
+        {
+            tracing::info!(
+                addr = %listener.local_addr()?,
+                "listening"
+            );
+
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(internal_stopped(&stopped))
+            .into_future()
+            .boxed()
+        },
+    };
+
+    let redirect: BoxFuture<io::Result<()>> = match state
+        .config
+        .http_redirect_socket
+    {
+        Some(redirect_socket) =>
+        {
+            let listener = TcpListener::bind(redirect_socket).await.unwrap();
+
+            tracing::info!(
+                addr = %listener.local_addr()?,
+                "redirecting http to https"
+            );
 
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
+            axum::serve(
+                listener,
+                handlers::https_redirect(&state.config.public_url),
+            )
+            .with_graceful_shutdown(internal_stopped(&stopped))
+            .into_future()
+            .boxed()
+        },
+        None => future::ready(Ok(())).boxed(),
+    };
+
+    let server = future::try_join(server, redirect);
+    tokio::pin!(server);
+
+    //stop accepting, let open requests finish (and their transactions commit),
+    //then close the idle keep-alive connections
+    tokio::select! {
+        result = &mut server =>
+        {
+            result?;
+        },
+        () = shutdown::signal() =>
+        {
+            tracing::info!(
+                timeout_sec = drain_timeout.as_secs(),
+                "draining open requests"
+            );
+            let _ = stop.send(());
+
+            if tokio::time::timeout(drain_timeout, server).await.is_err()
+            {
+                tracing::warn!("drain timeout passed, dropping open requests");
+            }
+        },
+    }
+
+    if tokio::time::timeout(
+        drain_timeout,
+        state.logs.flush(),
     )
     .await
-    .unwrap();
+    .is_err()
+    {
+        tracing::warn!("drain timeout passed, dropping pending request logs");
+    }
+
+    tracing::info!("shut down");
+
+    Ok(())
+}
+
+/// resolves once `stop` sends, one per listener.
+fn internal_stopped(stopped: &watch::Receiver<()>) -> impl Future<Output = ()>
+{
+    let mut stopped = stopped.clone();
+
+    async move {
+        stopped.changed().await.ok();
+    }
+}
+
+async fn healthcheck(config: &Config)
+    -> Result<(), Box<dyn std::error::Error>>
+{
+    let port = config.api_socket.port();
+
+    //the certificate is issued for the public host, not for 127.0.0.1
+    let (scheme, client) = match config.tls
+    {
+        Some(_) => (
+            "https",
+            reqwest::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .build()?,
+        ),
+        None => ("http", reqwest::Client::new()),
+    };
+
+    let response = client
+        .get(format!(
+            "{scheme}://127.0.0.1:{port}/health/ready"
+        ))
+        .send()
+        .await?;
+
+    if !response.status().is_success()
+    {
+        return Err(format!(
+            "not ready: {}",
+            response.status()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+async fn migrate(
+    config: &Config,
+    args: &[String],
+) -> Result<(), Box<dyn std::error::Error>>
+{
+    let db = MongolDB::init(
+        &config.mongoldb_connection,
+        Arc::new(Metrics::new()?),
+    )
+    .await?;
+
+    for line in Migrator::new(db.database()).run_command(args).await?
+    {
+        println!("{line}");
+    }
 
     Ok(())
 }
@@ -1,3 +1,6 @@
 pub mod auth;
 pub mod cookies;
 pub mod logging;
+pub mod metrics;
+pub mod rate_limit;
+pub mod request_id;
@@ -1,16 +1,38 @@
+mod client_info;
 mod cookie_names;
 mod ctx;
 mod jwt;
+mod key_ring;
 
 use std::sync::Arc;
 
+pub use client_info::*;
 pub use cookie_names::*;
 pub use ctx::*;
 pub use jwt::*;
+pub use key_ring::*;
 
-pub const ACCES_TOKEN_TTL_MIN: i64 = 10 * 10000;
+//default of `Config::acces_token_ttl_min`, revoking a session only locks out
+//acces tokens once they expire, a password reset or ban right away
+pub const ACCES_TOKEN_TTL_MIN: i64 = 15;
+pub const MAX_ACCES_TOKEN_TTL_MIN: i64 = 60 * 24;
 pub const REFRESH_TOKEN_TTL_MIN: i64 = 60 * 24 * 365;
 pub const DEVICE_ID_TTL_MIN: i64 = 60 * 24 * 365 * 5;
+pub const TWO_FACTOR_TOKEN_TTL_MIN: i64 = 5;
+pub const OIDC_TOKEN_TTL_MIN: i64 = 10;
+//login attempts, see `AppState::login_ip_limiter` and `login_email_limiter`
+pub const LOGIN_ATTEMPTS_PER_IP: usize = 20;
+pub const LOGIN_ATTEMPTS_PER_EMAIL: usize = 10;
+pub const LOGIN_ATTEMPTS_WINDOW_MIN: u64 = 5;
+//mails sent on request (password reset, verification),
+//see `AppState::mail_ip_limiter` and `mail_email_limiter`
+pub const MAIL_REQUESTS_PER_IP: usize = 10;
+pub const MAIL_REQUESTS_PER_EMAIL: usize = 3;
+pub const MAIL_REQUESTS_WINDOW_MIN: u64 = 60;
+//wrong passwords before the account is locked, see `AppState::login_lockout`
+pub const LOGIN_LOCKOUT_FAILURES: usize = 5;
+pub const LOGIN_LOCKOUT_WINDOW_MIN: u64 = 15;
+pub const LOGIN_LOCKOUT_DURATION_MIN: u64 = 15;
 
 use axum::async_trait;
 use axum::body::Body;
@@ -19,13 +41,18 @@ use axum::extract::{
     State,
 };
 use axum::http::request::Parts;
-use axum::http::Request;
+use axum::http::{
+    header,
+    HeaderMap,
+    Request,
+};
 use axum::middleware::Next;
 use axum::response::Response;
 use tower_cookies::Cookies;
 
 use crate::middleware::auth;
 use crate::middleware::cookies::Manager;
+use crate::model::user::User;
 use crate::model::{
     error,
     AppState,
@@ -38,7 +65,7 @@ pub async fn mw_require_authentication(
     next: Next,
 ) -> error::Result<Response>
 {
-    println!("AUTH MIDDLEWARE (REG): ");
+    tracing::debug!("require authentication");
 
     ctx?;
 
@@ -51,7 +78,7 @@ pub async fn mw_require_admin_authentication(
     next: Next,
 ) -> error::Result<Response>
 {
-    println!("AUTH MIDDLEWARE (MNG): ");
+    tracing::debug!("require admin authentication");
 
     match ctx
     {
@@ -79,43 +106,187 @@ pub async fn mw_ctx_resolver<'err>(
     next: Next,
 ) -> error::Result<'err, Response>
 {
-    println!("MTX RESOLVER: ");
+    tracing::debug!("resolve ctx");
+
+    //api and bot clients send the acces token themselves and refresh it via
+    //the token endpoints, so no cookie fallback or silent refresh here
+    if let Some(bearer_result) = get_bearer_token(req.headers())
+        .map(|token| get_ctx_from_token(&state.acces_token_keys, token))
+    {
+        let bearer_result = internal_check_session(&state, bearer_result).await;
+
+        internal_touch_presence(&state, &bearer_result);
+        req.extensions_mut().insert(bearer_result);
+
+        return Ok(next.run(req).await);
+    }
 
-    let mut ctx_result = get_ctx(&jar);
+    let mut ctx_result = get_ctx(&state.acces_token_keys, &jar);
 
     match ctx_result
     {
         Ok(_) => (),
         Err(err) if matches!(err.kind, error::Kind::Expired) =>
         {
-            println!("REFRESH");
+            tracing::debug!("acces token expired, refreshing");
 
-            let _ =
-                crate::handlers::logic::auth::refresh_token(&state, &jar).await;
-            ctx_result = get_ctx(&jar);
+            let client = ClientInfo::from_request(
+                req.headers(),
+                req.extensions(),
+            );
+
+            let _ = crate::handlers::logic::auth::refresh_token(
+                &state,
+                &jar,
+                client,
+            )
+            .await;
+            ctx_result = get_ctx(&state.acces_token_keys, &jar);
         },
         Err(_) => jar.remove_cookie(auth::CookieNames::AUTH_ACCES.to_string()),
     }
 
+    let ctx_result = internal_check_session(&state, ctx_result).await;
+
+    internal_touch_presence(&state, &ctx_result);
     req.extensions_mut().insert(ctx_result);
 
     Ok(next.run(req).await)
 }
 
-pub fn get_ctx<'err>(jar: &Cookies) -> Result<Ctx, error::Server<'err>>
+/// acces tokens cant be revoked themselves, so the user is read on every
+/// request to refuse the ones issued before a password reset or of a banned user.
+async fn internal_check_session<'err>(
+    state: &Arc<AppState>,
+    ctx_result: error::Result<'err, Ctx>,
+) -> error::Result<'err, Ctx>
+{
+    let ctx = ctx_result?;
+    let user = state.users.get_user_by_id(ctx.user_id_ref()).await?;
+
+    check_session(&user, &ctx)?;
+
+    Ok(ctx)
+}
+
+/// refuses an acces token the user no longer accepts.
+pub fn check_session<'err>(
+    user: &User,
+    ctx: &Ctx,
+) -> error::Result<'err, ()>
+{
+    if !user.flag.is_allowed_on_mogcord()
+    {
+        return Err(server_error!(
+            error::Kind::IncorrectPermissions,
+            error::OnType::User
+        )
+        .add_client(error::Client::NOT_ALLOWED_PLATFORM)
+        .add_debug_info(
+            "user flag",
+            user.flag.to_string(),
+        ));
+    }
+
+    if !user.is_session_valid(ctx.issued_at())
+    {
+        return Err(server_error!(
+            error::Kind::NoAuth,
+            error::OnType::AccesToken
+        )
+        .add_client(error::Client::PERMISSION_NO_AUTH)
+        .add_debug_info(
+            "reason",
+            String::from("issued before the cut off"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// any authenticated request keeps the user online,
+/// in the background so the request doesnt wait on the write.
+fn internal_touch_presence(
+    state: &Arc<AppState>,
+    ctx_result: &error::Result<'_, Ctx>,
+)
+{
+    let Ok(ctx) = ctx_result
+    else
+    {
+        return;
+    };
+
+    if state.presence_touch_limiter.hit(ctx.user_id_ref()).is_err()
+    {
+        return;
+    }
+
+    let state = Arc::clone(state);
+    let user_id = ctx.user_id_ref().to_string();
+
+    tokio::spawn(async move {
+        if let Err(err) = state.presences.touch_presence(&user_id).await
+        {
+            tracing::warn!(error = %err, "couldnt touch presence");
+        }
+    });
+}
+
+pub fn get_ctx<'err>(
+    keys: &KeyRing,
+    jar: &Cookies,
+) -> Result<Ctx, error::Server<'err>>
 {
     match jar
         .get_cookie(auth::CookieNames::AUTH_ACCES.as_str())
-        .and_then(|val| internal_parse_token(val.as_str()))
+        .and_then(|val| internal_parse_token(keys, val.as_str()))
     {
         Ok(claims) => Ok(Ctx::new(
             claims.sub,
             claims.is_admin,
+            claims.device_id,
+            claims.iat,
         )),
         Err(e) => Err(e),
     }
 }
 
+pub fn get_ctx_from_token<'err>(
+    keys: &KeyRing,
+    acces_token: &str,
+) -> Result<Ctx, error::Server<'err>>
+{
+    let claims = internal_parse_token(keys, acces_token)?;
+
+    Ok(Ctx::new(
+        claims.sub,
+        claims.is_admin,
+        claims.device_id,
+        claims.iat,
+    ))
+}
+
+/// returns the token of an `Authorization: Bearer <token>` header, if any.
+#[must_use]
+pub fn get_bearer_token(headers: &HeaderMap) -> Option<&str>
+{
+    let (scheme, token) = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .split_once(' ')?;
+
+    let token = token.trim();
+
+    if !scheme.eq_ignore_ascii_case("bearer") || token.is_empty()
+    {
+        return None;
+    }
+
+    Some(token)
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Ctx
 where
@@ -139,13 +310,166 @@ where
     }
 }
 
-fn internal_parse_token<'err>(acces_token: &str)
-    -> error::Result<'err, Claims>
+fn internal_parse_token<'err>(
+    keys: &KeyRing,
+    acces_token: &str,
+) -> error::Result<'err, Claims>
 {
     let claims = jwt::extract_acces_token(
+        keys,
         acces_token,
         &TokenStatus::DisallowExpired,
     )?;
 
     Ok(claims)
 }
+
+#[cfg(test)]
+mod tests
+{
+    use axum::http::{
+        header,
+        HeaderMap,
+        HeaderValue,
+    };
+
+    use chrono::{
+        Duration,
+        Utc,
+    };
+
+    use crate::middleware::auth::{
+        check_session,
+        create_acces_token,
+        get_bearer_token,
+        get_ctx_from_token,
+        CreateAccesTokenRequest,
+        Ctx,
+        KeyRing,
+    };
+    use crate::model::user::{
+        Flag,
+        User,
+    };
+
+    fn internal_headers(authorization: &'static str) -> HeaderMap
+    {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static(authorization),
+        );
+
+        headers
+    }
+
+    #[test]
+    fn test_get_bearer_token_is_valid()
+    {
+        assert_eq!(
+            Some("abc.def.ghi"),
+            get_bearer_token(&internal_headers(
+                "Bearer abc.def.ghi"
+            ))
+        );
+        assert_eq!(
+            Some("abc.def.ghi"),
+            get_bearer_token(&internal_headers(
+                "bearer abc.def.ghi"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_bearer_token_is_invalid()
+    {
+        assert_eq!(
+            None,
+            get_bearer_token(&HeaderMap::new())
+        );
+        assert_eq!(
+            None,
+            get_bearer_token(&internal_headers(
+                "Basic dXNlcjpwYXNz"
+            ))
+        );
+        assert_eq!(
+            None,
+            get_bearer_token(&internal_headers("Bearer "))
+        );
+    }
+
+    fn internal_user() -> User
+    {
+        User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        )
+    }
+
+    //the ctx of an acces token issued now, as the middleware reads it
+    fn internal_ctx(
+        keys: &KeyRing,
+        user: &User,
+    ) -> Ctx
+    {
+        let device_id = String::from("device");
+        let token = create_acces_token(
+            keys,
+            &CreateAccesTokenRequest::new(&user.id, false, &device_id),
+            Duration::minutes(15),
+        )
+        .unwrap();
+
+        get_ctx_from_token(keys, &token).unwrap()
+    }
+
+    #[test]
+    fn test_check_session_issued_after_reset_is_valid()
+    {
+        let keys = KeyRing::load(None, None).unwrap();
+        let mut user = internal_user();
+
+        assert!(check_session(
+            &user,
+            &internal_ctx(&keys, &user)
+        )
+        .is_ok());
+
+        user.sessions_valid_after = Some(Utc::now() - Duration::minutes(1));
+
+        assert!(check_session(
+            &user,
+            &internal_ctx(&keys, &user)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_check_session_issued_before_reset_is_invalid()
+    {
+        let keys = KeyRing::load(None, None).unwrap();
+        let mut user = internal_user();
+        let ctx = internal_ctx(&keys, &user);
+
+        //the password is reset after the token was handed out
+        user.sessions_valid_after = Some(Utc::now() + Duration::seconds(1));
+
+        assert!(check_session(&user, &ctx).is_err());
+    }
+
+    #[test]
+    fn test_check_session_of_banned_user_is_invalid()
+    {
+        let keys = KeyRing::load(None, None).unwrap();
+        let mut user = internal_user();
+        let ctx = internal_ctx(&keys, &user);
+
+        user.flag = Flag::Banned {
+            date: Utc::now(),
+        };
+
+        assert!(check_session(&user, &ctx).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use axum::async_trait;
+use axum::extract::{
+    ConnectInfo,
+    FromRequestParts,
+};
+use axum::http::request::Parts;
+use axum::http::{
+    header,
+    Extensions,
+    HeaderMap,
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+//who is logging in/refreshing, stored on the refresh token of the device
+#[derive(Clone, Debug)]
+pub struct ClientInfo
+{
+    //without the port, every new connection gets another one
+    pub ip_addr: String,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo
+{
+    const MAX_USER_AGENT_LENGTH: usize = 256;
+
+    #[must_use]
+    pub fn new(
+        ip_addr: String,
+        user_agent: Option<String>,
+    ) -> Self
+    {
+        Self {
+            ip_addr,
+            user_agent: user_agent.map(|user_agent| {
+                user_agent
+                    .chars()
+                    .take(Self::MAX_USER_AGENT_LENGTH)
+                    .collect()
+            }),
+        }
+    }
+
+    #[must_use]
+    pub fn from_request(
+        headers: &HeaderMap,
+        extensions: &Extensions,
+    ) -> Self
+    {
+        let ip_addr = extensions.get::<ConnectInfo<SocketAddr>>().map_or(
+            String::from("unknown"),
+            |ConnectInfo(addr)| addr.ip().to_string(),
+        );
+
+        let user_agent = headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        Self::new(ip_addr, user_agent)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection>
+    {
+        Ok(Self::from_request(
+            &parts.headers,
+            &parts.extensions,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use axum::extract::ConnectInfo;
+    use axum::http::{
+        Extensions,
+        HeaderMap,
+    };
+
+    use crate::middleware::auth::ClientInfo;
+    use crate::model::rate_limit::Limiter;
+
+    fn internal_client(addr: &str) -> ClientInfo
+    {
+        let mut extensions = Extensions::new();
+        extensions.insert(ConnectInfo(
+            addr.parse::<SocketAddr>().unwrap(),
+        ));
+
+        ClientInfo::from_request(&HeaderMap::new(), &extensions)
+    }
+
+    #[test]
+    fn test_client_info_from_request_is_valid()
+    {
+        let client = internal_client("203.0.113.7:40000");
+        let other_connection = internal_client("203.0.113.7:40001");
+
+        assert_eq!("203.0.113.7", client.ip_addr);
+
+        //a new connection from the same ip shares the bucket
+        let limiter = Limiter::new(1, Duration::from_mins(1));
+
+        assert!(limiter.hit(&client.ip_addr).is_ok());
+        assert!(limiter.hit(&other_connection.ip_addr).is_err());
+    }
+}
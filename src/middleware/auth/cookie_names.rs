@@ -8,6 +8,10 @@ pub enum CookieNames
     AUTH_ACCES,
     AUTH_REFRESH,
     DEVICE_ID,
+    //password was right, second factor still missing
+    TWO_FACTOR,
+    //oidc login on its way through the provider
+    OIDC,
 }
 
 impl fmt::Display for CookieNames
@@ -31,6 +35,8 @@ impl CookieNames
             CookieNames::AUTH_ACCES => "ACCES_TOKEN",
             CookieNames::AUTH_REFRESH => "SESSION_TOKEN",
             CookieNames::DEVICE_ID => "DEVICE_ID",
+            CookieNames::TWO_FACTOR => "TWO_FACTOR_TOKEN",
+            CookieNames::OIDC => "OIDC_TOKEN",
         }
     }
 
@@ -44,6 +50,8 @@ impl CookieNames
                 60 * 24 * 365
             },
             CookieNames::DEVICE_ID => 60 * 24 * 365 * 5,
+            CookieNames::TWO_FACTOR => super::TWO_FACTOR_TOKEN_TTL_MIN,
+            CookieNames::OIDC => super::OIDC_TOKEN_TTL_MIN,
         }
     }
 }
@@ -3,6 +3,10 @@ pub struct Ctx
 {
     user_id: String,
     is_admin: bool,
+    //none for acces tokens issued before devices were tracked
+    device_id: Option<String>,
+    //`iat` of the acces token, in seconds
+    issued_at: i64,
 }
 
 impl Ctx
@@ -11,11 +15,15 @@ impl Ctx
     pub fn new(
         user_id: String,
         is_admin: bool,
+        device_id: Option<String>,
+        issued_at: i64,
     ) -> Self
     {
         Self {
             user_id,
             is_admin,
+            device_id,
+            issued_at,
         }
     }
 }
@@ -39,4 +47,16 @@ impl Ctx
     {
         self.is_admin
     }
+
+    #[must_use]
+    pub fn device_id_ref(&self) -> Option<&str>
+    {
+        self.device_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn issued_at(&self) -> i64
+    {
+        self.issued_at
+    }
 }
@@ -5,18 +5,18 @@ use chrono::{
 use jsonwebtoken::errors::ErrorKind;
 use jsonwebtoken::{
     decode,
+    decode_header,
     encode,
-    DecodingKey,
-    EncodingKey,
     Header,
     Validation,
 };
+use serde::de::DeserializeOwned;
 use serde::{
     Deserialize,
     Serialize,
 };
-use std::env;
 
+use crate::io::oidc::AuthorizationRequest;
 use crate::model::error::{
     self,
     Kind,
@@ -24,7 +24,13 @@ use crate::model::error::{
 };
 use crate::server_error;
 
-use super::ACCES_TOKEN_TTL_MIN;
+use super::{
+    KeyRing,
+    OIDC_TOKEN_TTL_MIN,
+    TWO_FACTOR_TOKEN_TTL_MIN,
+};
+
+const TWO_FACTOR_PURPOSE: &str = "two_factor";
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Claims
@@ -32,6 +38,34 @@ pub struct Claims
     //user id
     pub sub: String,
     pub is_admin: bool,
+    //device of the refresh token this acces token came from
+    #[serde(default)]
+    pub device_id: Option<String>,
+    //0 for acces tokens issued before it was set
+    #[serde(default)]
+    pub iat: i64,
+    pub exp: usize,
+}
+
+//claims are disjoint from `Claims` on purpose,
+//neither token can be decoded as the other
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TwoFactorClaims
+{
+    //user id
+    pub sub: String,
+    pub purpose: String,
+    pub exp: usize,
+}
+
+//one oidc login attempt, carried by the browser to the callback
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OidcClaims
+{
+    pub provider: String,
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
     pub exp: usize,
 }
 
@@ -46,6 +80,7 @@ pub struct CreateAccesTokenRequest<'id>
 {
     user_id: &'id String,
     is_admin: bool,
+    device_id: &'id String,
 }
 
 impl<'user_info> CreateAccesTokenRequest<'user_info>
@@ -54,92 +89,202 @@ impl<'user_info> CreateAccesTokenRequest<'user_info>
     pub fn new(
         user_id: &'user_info String,
         is_admin: bool,
+        device_id: &'user_info String,
     ) -> Self
     {
         Self {
             user_id,
             is_admin,
+            device_id,
         }
     }
 }
 
 pub fn create_acces_token<'err>(
-    request: &CreateAccesTokenRequest
+    keys: &KeyRing,
+    request: &CreateAccesTokenRequest,
+    ttl: Duration,
 ) -> error::Result<'err, String>
 {
     let claims = Claims {
         sub: request.user_id.clone(),
         is_admin: request.is_admin,
-        #[allow(clippy::cast_possible_truncation)]
-        #[allow(clippy::cast_sign_loss)]
-        exp: (Utc::now() + Duration::minutes(ACCES_TOKEN_TTL_MIN)).timestamp()
-            as usize,
+        device_id: Some(request.device_id.clone()),
+        iat: Utc::now().timestamp(),
+        exp: internal_expiration(ttl),
     };
 
-    let acces_token_key = env::var("ACCES_TOKEN_KEY").map_err(|_| {
-        server_error!(
-            Kind::NotFound,
-            OnType::AccesTokenHashKey
-        )
-    })?;
-
-    let acces_token = encode(
-        &Header::default(),
+    internal_encode(
+        keys,
         &claims,
-        &EncodingKey::from_secret(acces_token_key.as_ref()),
+        OnType::AccesToken,
     )
-    .map_err(|_| {
-        server_error!(
-            Kind::Create,
-            OnType::AccesToken
-        )
-    })?;
-
-    Ok(acces_token)
 }
 
 pub fn extract_acces_token<'err>(
+    keys: &KeyRing,
     token: &str,
     acces_token_status: &TokenStatus,
 ) -> error::Result<'err, Claims>
 {
-    let acces_token_key = env::var("ACCES_TOKEN_KEY").map_err(|_| {
-        server_error!(
-            Kind::NotFound,
-            OnType::AccesTokenHashKey
+    internal_decode(
+        keys,
+        token,
+        acces_token_status,
+        OnType::AccesToken,
+    )
+}
+
+/// short lived proof that the password was right,
+/// traded in for a session once the second factor checks out.
+pub fn create_two_factor_token<'err>(
+    keys: &KeyRing,
+    user_id: &str,
+) -> error::Result<'err, String>
+{
+    let claims = TwoFactorClaims {
+        sub: user_id.to_string(),
+        purpose: TWO_FACTOR_PURPOSE.to_string(),
+        exp: internal_expiration(Duration::minutes(
+            TWO_FACTOR_TOKEN_TTL_MIN,
+        )),
+    };
+
+    internal_encode(
+        keys,
+        &claims,
+        OnType::TwoFactor,
+    )
+}
+
+pub fn extract_two_factor_token<'err>(
+    keys: &KeyRing,
+    token: &str,
+) -> error::Result<'err, TwoFactorClaims>
+{
+    let claims: TwoFactorClaims = internal_decode(
+        keys,
+        token,
+        &TokenStatus::DisallowExpired,
+        OnType::TwoFactor,
+    )?;
+
+    if claims.purpose != TWO_FACTOR_PURPOSE
+    {
+        return Err(server_error!(
+            Kind::InValid,
+            OnType::TwoFactor
         )
-    })?;
+        .add_debug_info("purpose", claims.purpose));
+    }
+
+    Ok(claims)
+}
+
+/// signed so the callback can trust the state, nonce and pkce verifier
+/// it gets back without storing them server side.
+pub fn create_oidc_token<'err>(
+    keys: &KeyRing,
+    provider: &str,
+    request: &AuthorizationRequest,
+) -> error::Result<'err, String>
+{
+    let claims = OidcClaims {
+        provider: provider.to_string(),
+        state: request.state.clone(),
+        nonce: request.nonce.clone(),
+        code_verifier: request.code_verifier.clone(),
+        exp: internal_expiration(Duration::minutes(
+            OIDC_TOKEN_TTL_MIN,
+        )),
+    };
+
+    internal_encode(keys, &claims, OnType::Oidc)
+}
+
+pub fn extract_oidc_token<'err>(
+    keys: &KeyRing,
+    token: &str,
+) -> error::Result<'err, OidcClaims>
+{
+    internal_decode(
+        keys,
+        token,
+        &TokenStatus::DisallowExpired,
+        OnType::Oidc,
+    )
+}
+
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+fn internal_expiration(ttl: Duration) -> usize
+{
+    (Utc::now() + ttl).timestamp() as usize
+}
+
+fn internal_encode<'err, T: Serialize>(
+    keys: &KeyRing,
+    claims: &T,
+    on_type: OnType,
+) -> error::Result<'err, String>
+{
+    let signing_key = keys.active();
+
+    let mut header = Header::new(signing_key.algorithm);
+    header.kid = Some(signing_key.kid.clone());
+
+    encode(
+        &header,
+        claims,
+        signing_key.encoding(),
+    )
+    .map_err(|_| server_error!(Kind::Create, on_type))
+}
+
+fn internal_decode<'err, T: DeserializeOwned>(
+    keys: &KeyRing,
+    token: &str,
+    token_status: &TokenStatus,
+    on_type: OnType,
+) -> error::Result<'err, T>
+{
+    //the kid picks the key, tokens signed with a dropped key are invalid
+    let signing_key = decode_header(token)
+        .ok()
+        .and_then(|header| header.kid)
+        .and_then(|kid| keys.get(&kid))
+        .ok_or_else(|| {
+            server_error!(
+                error::Kind::InValid,
+                on_type.clone()
+            )
+            .add_debug_info("token", token.to_string())
+        })?;
 
-    let mut validation = Validation::default();
+    let mut validation = Validation::new(signing_key.algorithm);
 
-    if acces_token_status == &TokenStatus::AllowExpired
+    if token_status == &TokenStatus::AllowExpired
     {
         validation.validate_exp = false;
     }
 
-    match decode::<Claims>(
+    match decode::<T>(
         token,
-        &DecodingKey::from_secret(acces_token_key.as_ref()),
+        signing_key.decoding(),
         &validation,
     )
     {
-        Ok(acces_token_data) => Ok(acces_token_data.claims),
+        Ok(token_data) => Ok(token_data.claims),
         Err(err) => match *err.kind()
         {
             ErrorKind::ExpiredSignature => Err(server_error!(
                 Kind::Expired,
-                OnType::AccesToken
+                on_type
             )),
             _ =>
             {
-                let err = server_error!(
-                    error::Kind::InValid,
-                    error::OnType::AccesToken
-                )
-                .add_debug_info(
-                    "acces token",
-                    token.to_string(),
-                );
+                let err = server_error!(error::Kind::InValid, on_type)
+                    .add_debug_info("token", token.to_string());
 
                 Err(err)
             },
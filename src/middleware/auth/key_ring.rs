@@ -0,0 +1,483 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters,
+    CommonParameters,
+    EllipticCurve,
+    Jwk,
+    JwkSet,
+    KeyAlgorithm,
+    OctetKeyPairParameters,
+    OctetKeyPairType,
+    PublicKeyUse,
+    RSAKeyParameters,
+    RSAKeyType,
+};
+use jsonwebtoken::{
+    Algorithm,
+    DecodingKey,
+    EncodingKey,
+};
+use ring::rand::SystemRandom;
+use ring::rsa::PublicKeyComponents;
+use ring::signature::{
+    Ed25519KeyPair,
+    KeyPair,
+    RsaKeyPair,
+};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+/// a key the acces tokens can be signed and verified with,
+/// identified by the `kid` header of the token.
+#[derive(Clone)]
+pub struct SigningKey
+{
+    pub kid: String,
+    pub algorithm: Algorithm,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    jwk: Jwk,
+}
+
+impl SigningKey
+{
+    /// parses a PKCS#8 (Ed25519 or RSA) or PKCS#1 (RSA) private key in PEM.
+    pub fn from_pem<'err>(
+        kid: String,
+        pem_bytes: &[u8],
+    ) -> error::Result<'err, Self>
+    {
+        let parsed = pem::parse(pem_bytes).map_err(|err| {
+            server_error!(
+                Kind::Parse,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info("kid", kid.clone())
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let der = parsed.contents();
+
+        match parsed.tag()
+        {
+            "PRIVATE KEY" =>
+            {
+                if Ed25519KeyPair::from_pkcs8_maybe_unchecked(der).is_ok()
+                {
+                    return Self::from_ed25519_pkcs8(kid, der);
+                }
+
+                let key_pair = RsaKeyPair::from_pkcs8(der).map_err(|err| {
+                    server_error!(
+                        Kind::InValid,
+                        OnType::AccesTokenKey
+                    )
+                    .add_debug_info("kid", kid.clone())
+                    .add_debug_info("error", err.to_string())
+                })?;
+
+                Self::from_rsa(kid, &key_pair, pem_bytes)
+            },
+            "RSA PRIVATE KEY" =>
+            {
+                let key_pair = RsaKeyPair::from_der(der).map_err(|err| {
+                    server_error!(
+                        Kind::InValid,
+                        OnType::AccesTokenKey
+                    )
+                    .add_debug_info("kid", kid.clone())
+                    .add_debug_info("error", err.to_string())
+                })?;
+
+                Self::from_rsa(kid, &key_pair, pem_bytes)
+            },
+            tag => Err(server_error!(
+                Kind::InValid,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info("kid", kid)
+            .add_debug_info("pem tag", tag.to_string())),
+        }
+    }
+
+    /// creates a new random Ed25519 key.
+    pub fn generate<'err>(kid: String) -> error::Result<'err, Self>
+    {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| {
+                server_error!(
+                    Kind::Create,
+                    OnType::AccesTokenKey
+                )
+            })?;
+
+        Self::from_ed25519_pkcs8(kid, pkcs8.as_ref())
+    }
+
+    #[must_use]
+    pub fn encoding(&self) -> &EncodingKey
+    {
+        &self.encoding
+    }
+
+    #[must_use]
+    pub fn decoding(&self) -> &DecodingKey
+    {
+        &self.decoding
+    }
+
+    fn from_ed25519_pkcs8<'err>(
+        kid: String,
+        pkcs8: &[u8],
+    ) -> error::Result<'err, Self>
+    {
+        let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(pkcs8)
+            .map_err(|err| {
+                server_error!(
+                    Kind::InValid,
+                    OnType::AccesTokenKey
+                )
+                .add_debug_info("kid", kid.clone())
+                .add_debug_info("error", err.to_string())
+            })?;
+
+        let algorithm =
+            AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()),
+            });
+
+        Self::new(
+            kid,
+            Algorithm::EdDSA,
+            EncodingKey::from_ed_der(pkcs8),
+            algorithm,
+        )
+    }
+
+    fn from_rsa<'err>(
+        kid: String,
+        key_pair: &RsaKeyPair,
+        pem_bytes: &[u8],
+    ) -> error::Result<'err, Self>
+    {
+        let encoding = EncodingKey::from_rsa_pem(pem_bytes).map_err(|err| {
+            server_error!(
+                Kind::InValid,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info("kid", kid.clone())
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let components: PublicKeyComponents<Vec<u8>> = key_pair.public().into();
+
+        let algorithm = AlgorithmParameters::RSA(RSAKeyParameters {
+            key_type: RSAKeyType::RSA,
+            n: URL_SAFE_NO_PAD.encode(components.n),
+            e: URL_SAFE_NO_PAD.encode(components.e),
+        });
+
+        Self::new(
+            kid,
+            Algorithm::RS256,
+            encoding,
+            algorithm,
+        )
+    }
+
+    fn new<'err>(
+        kid: String,
+        algorithm: Algorithm,
+        encoding: EncodingKey,
+        parameters: AlgorithmParameters,
+    ) -> error::Result<'err, Self>
+    {
+        let key_algorithm = match algorithm
+        {
+            Algorithm::RS256 => KeyAlgorithm::RS256,
+            _ => KeyAlgorithm::EdDSA,
+        };
+
+        let jwk = Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(key_algorithm),
+                key_id: Some(kid.clone()),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        };
+
+        let decoding = DecodingKey::from_jwk(&jwk).map_err(|err| {
+            server_error!(
+                Kind::InValid,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info("kid", kid.clone())
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        Ok(Self {
+            kid,
+            algorithm,
+            encoding,
+            decoding,
+            jwk,
+        })
+    }
+}
+
+/// every key acces tokens may be signed with.
+///
+/// new tokens are signed with the active key only,
+/// the others stay around so tokens signed before a rotation keep verifying.
+pub struct KeyRing
+{
+    active_kid: String,
+    keys: Vec<SigningKey>,
+}
+
+impl KeyRing
+{
+    pub fn new<'err>(
+        keys: Vec<SigningKey>,
+        active_kid: &str,
+    ) -> error::Result<'err, Self>
+    {
+        if !keys.iter().any(|key| key.kid == active_kid)
+        {
+            return Err(server_error!(
+                Kind::NotFound,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info(
+                "active kid",
+                active_kid.to_string(),
+            ));
+        }
+
+        Ok(Self {
+            active_kid: active_kid.to_string(),
+            keys,
+        })
+    }
+
+    /// loads every `<kid>.pem` of `ACCES_TOKEN_KEYS_DIR`,
+    /// signing with `ACCES_TOKEN_ACTIVE_KID` or else the last kid by name.
+    ///
+    /// without a directory a random key is generated,
+    /// meaning every acces token becomes invalid on restart.
+    pub fn load<'err>(
+        keys_dir: Option<&Path>,
+        active_kid: Option<&str>,
+    ) -> error::Result<'err, Self>
+    {
+        let Some(keys_dir) = keys_dir
+        else
+        {
+            tracing::warn!(
+                "ACCES_TOKEN_KEYS_DIR not set, using a generated key"
+            );
+
+            let key = SigningKey::generate(Uuid::now_v7().to_string())?;
+            let active_kid = key.kid.clone();
+
+            return Self::new(vec![key], &active_kid);
+        };
+
+        Self::from_dir(keys_dir, active_kid)
+    }
+
+    pub fn from_dir<'err>(
+        dir: &Path,
+        active_kid: Option<&str>,
+    ) -> error::Result<'err, Self>
+    {
+        let entries = fs::read_dir(dir).map_err(|err| {
+            server_error!(
+                Kind::Read,
+                OnType::AccesTokenKey
+            )
+            .add_debug_info(
+                "dir",
+                dir.display().to_string(),
+            )
+            .add_debug_info("error", err.to_string())
+        })?;
+
+        let mut keys = Vec::new();
+
+        for entry in entries.flatten()
+        {
+            let path = entry.path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some("pem")
+            {
+                continue;
+            }
+
+            let Some(kid) = path.file_stem().and_then(|stem| stem.to_str())
+            else
+            {
+                continue;
+            };
+
+            let pem_bytes = fs::read(&path).map_err(|err| {
+                server_error!(
+                    Kind::Read,
+                    OnType::AccesTokenKey
+                )
+                .add_debug_info(
+                    "path",
+                    path.display().to_string(),
+                )
+                .add_debug_info("error", err.to_string())
+            })?;
+
+            keys.push(SigningKey::from_pem(
+                kid.to_string(),
+                &pem_bytes,
+            )?);
+        }
+
+        keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+
+        let active_kid = match active_kid
+        {
+            Some(active_kid) => active_kid.to_string(),
+            None =>
+            {
+                keys.last().map(|key| key.kid.clone()).ok_or_else(|| {
+                    server_error!(
+                        Kind::NotFound,
+                        OnType::AccesTokenKey
+                    )
+                    .add_debug_info(
+                        "dir",
+                        dir.display().to_string(),
+                    )
+                })?
+            },
+        };
+
+        Self::new(keys, &active_kid)
+    }
+
+    #[must_use]
+    pub fn active(&self) -> &SigningKey
+    {
+        self.get(&self.active_kid)
+            .expect("active kid is checked on creation")
+    }
+
+    #[must_use]
+    pub fn get(
+        &self,
+        kid: &str,
+    ) -> Option<&SigningKey>
+    {
+        self.keys.iter().find(|key| key.kid == kid)
+    }
+
+    /// the public halves of every key, for other services to verify our tokens.
+    #[must_use]
+    pub fn jwks(&self) -> JwkSet
+    {
+        JwkSet {
+            keys: self.keys.iter().map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::Duration;
+
+    use crate::middleware::auth::{
+        create_acces_token,
+        extract_acces_token,
+        CreateAccesTokenRequest,
+        KeyRing,
+        SigningKey,
+        TokenStatus,
+    };
+
+    #[test]
+    fn test_generated_key_in_jwks_is_valid()
+    {
+        let key = SigningKey::generate(String::from("key-1")).unwrap();
+        let key_ring = KeyRing::new(vec![key], "key-1").unwrap();
+
+        let jwks = key_ring.jwks();
+
+        assert_eq!(1, jwks.keys.len());
+        assert!(jwks.find("key-1").is_some());
+        assert_eq!("key-1", key_ring.active().kid);
+    }
+
+    #[test]
+    fn test_unknown_active_kid_is_invalid()
+    {
+        let key = SigningKey::generate(String::from("key-1")).unwrap();
+
+        assert!(KeyRing::new(vec![key], "key-2").is_err());
+    }
+
+    #[test]
+    fn test_token_of_retired_key_is_valid()
+    {
+        let old_key = SigningKey::generate(String::from("key-1")).unwrap();
+        let new_key = SigningKey::generate(String::from("key-2")).unwrap();
+
+        let old_ring = KeyRing::new(vec![old_key.clone()], "key-1").unwrap();
+        let rotated_ring = KeyRing::new(
+            vec![old_key, new_key],
+            "key-2",
+        )
+        .unwrap();
+
+        let user_id = String::from("user");
+        let device_id = String::from("device");
+        let request = CreateAccesTokenRequest::new(&user_id, false, &device_id);
+
+        let old_token = create_acces_token(
+            &old_ring,
+            &request,
+            Duration::minutes(10),
+        )
+        .unwrap();
+        let new_token = create_acces_token(
+            &rotated_ring,
+            &request,
+            Duration::minutes(10),
+        )
+        .unwrap();
+
+        let claims = extract_acces_token(
+            &rotated_ring,
+            &old_token,
+            &TokenStatus::DisallowExpired,
+        )
+        .unwrap();
+
+        assert_eq!(user_id, claims.sub);
+        assert!(extract_acces_token(
+            &old_ring,
+            &new_token,
+            &TokenStatus::DisallowExpired,
+        )
+        .is_err());
+    }
+}
@@ -1,7 +1,7 @@
-use std::sync::Arc;
-
 use axum::extract::State;
 use axum::http::{
+    header,
+    HeaderValue,
     Method,
     Uri,
 };
@@ -9,25 +9,29 @@ use axum::response::{
     IntoResponse,
     Response,
 };
-use axum::Json;
+use axum::{
+    Extension,
+    Json,
+};
 use serde_json::json;
 use tower_cookies::Cookies;
-use uuid::Uuid;
 
 use crate::middleware::auth::{
     self,
     Ctx,
 };
 use crate::middleware::cookies::Manager;
+use crate::middleware::request_id::RequestId;
 use crate::model::error;
 use crate::model::log::{
     log_request,
-    Repository,
+    Queue,
     RequestLogLinePersonal,
 };
 
 pub async fn main_response_mapper(
-    State(state): State<Arc<dyn Repository>>,
+    State(queue): State<Queue>,
+    Extension(RequestId(req_id)): Extension<RequestId>,
     uri: Uri,
     ctx: Option<Ctx>,
     req_method: Method,
@@ -35,15 +39,13 @@ pub async fn main_response_mapper(
     res: Response,
 ) -> Response
 {
-    let req_id = Uuid::now_v7();
-
     let service_error = res.extensions().get::<error::Server>();
     let client_status_error =
         service_error.map(error::Server::client_status_and_error);
 
     let error_response = client_status_error.as_ref().map(
         |(status_code, client_error, extra_info)| {
-            let client_error_body = if extra_info.is_none()
+            let mut client_error_body = if extra_info.is_none()
             {
                 json!(
                 {
@@ -67,16 +69,44 @@ pub async fn main_response_mapper(
                 })
             };
 
-            (
+            let retry_after = service_error.and_then(|err| err.retry_after);
+
+            if let Some(retry_after) = retry_after
+            {
+                client_error_body["error"]["retry_after"] = json!(retry_after);
+            }
+
+            let mut response = (
                 *status_code,
                 Json(client_error_body),
             )
-                .into_response()
+                .into_response();
+
+            if let Some(retry_after) = retry_after
+            {
+                response.headers_mut().insert(
+                    header::RETRY_AFTER,
+                    HeaderValue::from(retry_after),
+                );
+            }
+
+            response
         },
     );
 
     let client_error_option = client_status_error.map(|(_, client, _)| client);
 
+    match service_error
+    {
+        Some(err) => tracing::warn!(
+            status = %res.status(),
+            client_error = client_error_option.as_ref().map(AsRef::<str>::as_ref),
+            error = %err,
+            "request failed"
+        ),
+        None => tracing::info!(status = %res.status(), "request handled"),
+    }
+
     let device_id_option =
         jar.get_cookie(auth::CookieNames::DEVICE_ID.as_str()).ok();
 
@@ -86,17 +116,14 @@ pub async fn main_response_mapper(
     );
 
     log_request(
-        state,
+        &queue,
         req_id,
         user_info,
-        req_method,
-        uri,
+        &req_method,
+        &uri,
         service_error,
         client_error_option,
-    )
-    .await;
-
-    println!();
+    );
 
     error_response.unwrap_or(res)
 }
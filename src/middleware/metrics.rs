@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::{
+    MatchedPath,
+    Request,
+    State,
+};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::model::error;
+use crate::model::metrics::Metrics;
+
+/// route layer, so the route pattern is known when the request comes in.
+pub async fn mw_metrics(
+    State(metrics): State<Arc<Metrics>>,
+    matched_path: Option<MatchedPath>,
+    req: Request<Body>,
+    next: Next,
+) -> Response
+{
+    let started_at = Instant::now();
+    let method = req.method().to_string();
+    let route = matched_path.map_or_else(
+        || String::from("unmatched"),
+        |path| path.as_str().to_string(),
+    );
+
+    let res = next.run(req).await;
+
+    //the response mapper turns the error into its real status later on
+    let status = match res.extensions().get::<error::Server>()
+    {
+        Some(err) =>
+        {
+            metrics.observe_error(err);
+
+            err.client_status_and_error().0
+        },
+        None => res.status(),
+    };
+
+    metrics.observe_request(
+        &method,
+        &route,
+        status.as_u16(),
+        started_at.elapsed(),
+    );
+
+    res
+}
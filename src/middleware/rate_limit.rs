@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::middleware::auth::{
+    ClientInfo,
+    Ctx,
+};
+use crate::model::rate_limit::Group;
+use crate::model::{
+    error,
+    AppState,
+};
+use crate::server_error;
+
+/// takes a token of the group, per user when logged in and per ip otherwise.
+pub async fn mw_rate_limit<'err>(
+    State((state, group)): State<(Arc<AppState>, Group)>,
+    ctx_option: Option<Ctx>,
+    client: ClientInfo,
+    req: Request<Body>,
+    next: Next,
+) -> error::Result<'err, Response>
+{
+    let key = internal_key(
+        ctx_option.as_ref().map(Ctx::user_id_ref),
+        &client,
+    );
+
+    if let Err(retry_after) = state.rate_limits.take(group, &key)
+    {
+        return Err(server_error!(
+            error::Kind::TooManyRequests,
+            error::OnType::RateLimit
+        )
+        .add_client(error::Client::RATE_LIMITED)
+        .add_debug_info("group", format!("{group:?}"))
+        .add_debug_info("key", key)
+        .add_retry_after(retry_after));
+    }
+
+    Ok(next.run(req).await)
+}
+
+//the bare ip, a client opening new connections would otherwise get a new key
+fn internal_key(
+    user_id_option: Option<&str>,
+    client: &ClientInfo,
+) -> String
+{
+    match user_id_option
+    {
+        Some(user_id) => format!("user:{user_id}"),
+        None => format!("ip:{}", client.ip_addr),
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::net::SocketAddr;
+
+    use axum::extract::ConnectInfo;
+    use axum::http::{
+        Extensions,
+        HeaderMap,
+    };
+
+    use crate::middleware::auth::ClientInfo;
+    use crate::middleware::rate_limit::internal_key;
+
+    fn internal_client(addr: &str) -> ClientInfo
+    {
+        let mut extensions = Extensions::new();
+        extensions.insert(ConnectInfo(
+            addr.parse::<SocketAddr>().unwrap(),
+        ));
+
+        ClientInfo::from_request(&HeaderMap::new(), &extensions)
+    }
+
+    #[test]
+    fn test_internal_key_is_valid()
+    {
+        assert_eq!(
+            "ip:203.0.113.7",
+            internal_key(
+                None,
+                &internal_client("203.0.113.7:40000")
+            )
+        );
+        assert_eq!(
+            internal_key(
+                None,
+                &internal_client("203.0.113.7:40000")
+            ),
+            internal_key(
+                None,
+                &internal_client("203.0.113.7:40001")
+            )
+        );
+        assert_eq!(
+            "user:0191",
+            internal_key(
+                Some("0191"),
+                &internal_client("203.0.113.7:40000")
+            )
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// id of the current request, in the request log, the tracing span
+/// and the `X-Request-Id` response header.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestId(pub Uuid);
+
+/// outermost layer, everything traced while handling the request
+/// is recorded under a span with its id.
+pub async fn mw_request_id(
+    mut req: Request<Body>,
+    next: Next,
+) -> Response
+{
+    let req_id = Uuid::now_v7();
+
+    req.extensions_mut().insert(RequestId(req_id));
+
+    let span = tracing::info_span!(
+        "request",
+        req_id = %req_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+
+    let mut res = next.run(req).instrument(span).await;
+
+    if let Ok(header_value) = HeaderValue::from_str(&req_id.to_string())
+    {
+        res.headers_mut().insert(
+            REQUEST_ID_HEADER,
+            header_value,
+        );
+    }
+
+    res
+}
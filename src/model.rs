@@ -1,19 +1,32 @@
 mod appstate;
 mod hashing;
 mod pagination;
+mod password_policy;
 
 pub use appstate::*;
 pub use hashing::*;
 pub use pagination::*;
+pub use password_policy::*;
 
 pub mod bucket;
 pub mod channel;
 pub mod channel_parent;
+pub mod config;
 pub mod error;
+pub mod event;
+pub mod external_identity;
+pub mod health;
 pub mod log;
+pub mod mail;
+pub mod mail_token;
 pub mod message;
+pub mod metrics;
+pub mod presence;
+pub mod rate_limit;
 pub mod refresh_token;
 pub mod relation;
+pub mod two_factor;
 pub mod user;
+pub mod webhook;
 
 const ROLE_NAME_EVERYBODY: &str = "everybody";
@@ -1,16 +1,41 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::db::migration::Migrator;
 use crate::db::MongolDB;
+use crate::io::log::Stdout;
+use crate::io::oidc::Providers;
+use crate::io::webhook::{
+    Dispatcher,
+    RetryPolicy,
+};
 use crate::io::FileWriter;
+use crate::middleware::auth::{
+    self,
+    KeyRing,
+};
 
+use super::config::Config;
 use super::{
     channel,
     channel_parent,
+    event,
+    external_identity,
+    health,
     log,
+    mail,
+    mail_token,
     message,
+    metrics,
+    presence,
+    rate_limit,
     refresh_token,
     relation,
+    two_factor,
     user,
+    webhook,
+    Hashing,
+    PasswordPolicy,
 };
 
 pub struct AppState
@@ -23,20 +48,74 @@ pub struct AppState
     pub messages: Arc<dyn message::Repository>,
     pub refresh_tokens: Arc<dyn refresh_token::Repository>,
     pub relations: Arc<dyn relation::Repository>,
-    pub logs: Arc<dyn log::Repository>,
+    pub presences: Arc<dyn presence::Repository>,
+    pub webhooks: Arc<dyn webhook::Repository>,
+    pub two_factors: Arc<dyn two_factor::Repository>,
+    //request logs, written to the sinks of `LOG_SINKS` in the background
+    pub logs: log::Queue,
+    //the same sinks, to read the logs back
+    pub log_reader: Arc<dyn log::Repository>,
+    pub mail_tokens: Arc<dyn mail_token::Repository>,
+    pub external_identities: Arc<dyn external_identity::Repository>,
+    pub mailer: Arc<dyn mail::Mailer>,
+    pub events: event::Bus,
+    pub incoming_webhook_limiter: rate_limit::Limiter,
+    pub two_factor_limiter: rate_limit::Limiter,
+    pub login_ip_limiter: rate_limit::Limiter,
+    pub login_email_limiter: rate_limit::Limiter,
+    pub login_lockout: rate_limit::Lockout,
+    pub mail_ip_limiter: rate_limit::Limiter,
+    pub mail_email_limiter: rate_limit::Limiter,
+    //one hit per user per interval, see `middleware::auth::mw_ctx_resolver`
+    pub presence_touch_limiter: rate_limit::Limiter,
+    //per route group, see `middleware::rate_limit`
+    pub rate_limits: rate_limit::GroupLimiter,
+    pub acces_token_keys: KeyRing,
+    pub hashing: Hashing,
+    pub password_policy: PasswordPolicy,
+    pub oidc_providers: Providers,
+    pub metrics: Arc<metrics::Metrics>,
+    //what `/health/ready` checks
+    pub health_checks: Vec<(
+        &'static str,
+        Arc<dyn health::Check>,
+    )>,
+    pub config: Config,
 }
 
 impl AppState
 {
-    pub async fn new(
-        db_con: &str,
-        log_path: &str,
-    ) -> Arc<Self>
+    pub async fn new(config: Config) -> Arc<Self>
     {
+        let metrics = Arc::new(
+            metrics::Metrics::new().expect("Couldnt register metrics"),
+        );
+
         let db = Arc::new(
-            MongolDB::init(db_con).await.expect("Couldnt connect to db"),
+            MongolDB::init(
+                &config.mongoldb_connection,
+                Arc::clone(&metrics),
+            )
+            .await
+            .expect("Couldnt connect to db"),
         );
 
+        let migrator = Migrator::new(db.database());
+        if config.migrate_on_start
+        {
+            migrator.up(None).await.expect("Couldnt apply migrations");
+        }
+        else
+        {
+            let pending =
+                migrator.pending().await.expect("Couldnt read migrations");
+
+            assert!(
+                pending.is_empty(),
+                "Pending migrations {pending:?}, run `mogcord migrate up`"
+            );
+        }
+
         let chats = Arc::clone(&db) as Arc<dyn channel_parent::Repository>;
         let servers = Arc::clone(&db) as Arc<dyn channel_parent::Repository>;
         let channel_parents =
@@ -47,10 +126,78 @@ impl AppState
         let refresh_tokens =
             Arc::clone(&db) as Arc<dyn refresh_token::Repository>;
         let relations = Arc::clone(&db) as Arc<dyn relation::Repository>;
+        let presences = Arc::clone(&db) as Arc<dyn presence::Repository>;
+        let webhooks = Arc::clone(&db) as Arc<dyn webhook::Repository>;
+        let two_factors = Arc::clone(&db) as Arc<dyn two_factor::Repository>;
+        let mail_tokens = Arc::clone(&db) as Arc<dyn mail_token::Repository>;
+        let external_identities =
+            Arc::clone(&db) as Arc<dyn external_identity::Repository>;
+
+        let log_file_writer = Arc::new(FileWriter::new(
+            config.log_path.clone(),
+        ));
+
+        let log_sinks: Vec<Arc<dyn log::Repository>> = config
+            .log_sinks
+            .iter()
+            .map(|sink| match sink
+            {
+                log::Sink::File =>
+                {
+                    Arc::clone(&log_file_writer) as Arc<dyn log::Repository>
+                },
+                log::Sink::Mongo => Arc::clone(&db) as Arc<dyn log::Repository>,
+                log::Sink::Stdout =>
+                {
+                    Arc::new(Stdout) as Arc<dyn log::Repository>
+                },
+            })
+            .collect();
+        let log_sink = match <[_; 1]>::try_from(log_sinks)
+        {
+            Ok([sink]) => sink,
+            Err(sinks) => Arc::new(log::FanOut::new(sinks)),
+        };
+        let logs = log::Queue::spawn(
+            Arc::clone(&log_sink),
+            config.log_queue_capacity,
+        );
+
+        let mailer = Arc::new(FileWriter::new(
+            config.mail_path.clone(),
+        )) as Arc<dyn mail::Mailer>;
+
+        let acces_token_keys = KeyRing::load(
+            config.acces_token_keys_dir.as_deref(),
+            config.acces_token_active_kid.as_deref(),
+        )
+        .expect("Couldnt load acces token keys");
+
+        let rate_limits = rate_limit::GroupLimiter::new(&config.rate_limits);
+        let oidc_providers = Providers::from_settings(&config.oidc_providers);
+
+        let mut health_checks = vec![(
+            "mongo",
+            Arc::clone(&db) as Arc<dyn health::Check>,
+        )];
+        if config.log_sinks.contains(&log::Sink::File)
+        {
+            health_checks.push((
+                "log_dir",
+                log_file_writer as Arc<dyn health::Check>,
+            ));
+        }
+
+        let events = event::Bus::new();
+
+        Arc::new(Dispatcher::new(
+            Arc::clone(&webhooks),
+            RetryPolicy::default(),
+            &config.webhooks,
+        ))
+        .spawn(events.subscribe());
 
-        let logs = Arc::new(FileWriter::new(
-            log_path.to_string(),
-        )) as Arc<dyn log::Repository>;
+        Arc::clone(&metrics).spawn_event_counter(events.subscribe());
 
         Arc::new(Self {
             chats,
@@ -61,7 +208,58 @@ impl AppState
             messages,
             refresh_tokens,
             relations,
+            presences,
+            webhooks,
+            two_factors,
             logs,
+            log_reader: log_sink,
+            mail_tokens,
+            external_identities,
+            mailer,
+            events,
+            incoming_webhook_limiter: rate_limit::Limiter::new(
+                webhook::Incoming::RATE_LIMIT_MESSAGES,
+                Duration::from_secs(webhook::Incoming::RATE_LIMIT_WINDOW_SEC),
+            ),
+            two_factor_limiter: rate_limit::Limiter::new(
+                two_factor::TwoFactor::RATE_LIMIT_ATTEMPTS,
+                Duration::from_secs(
+                    two_factor::TwoFactor::RATE_LIMIT_WINDOW_SEC,
+                ),
+            ),
+            login_ip_limiter: rate_limit::Limiter::new(
+                auth::LOGIN_ATTEMPTS_PER_IP,
+                Duration::from_secs(auth::LOGIN_ATTEMPTS_WINDOW_MIN * 60),
+            ),
+            login_email_limiter: rate_limit::Limiter::new(
+                auth::LOGIN_ATTEMPTS_PER_EMAIL,
+                Duration::from_secs(auth::LOGIN_ATTEMPTS_WINDOW_MIN * 60),
+            ),
+            login_lockout: rate_limit::Lockout::new(
+                auth::LOGIN_LOCKOUT_FAILURES,
+                Duration::from_secs(auth::LOGIN_LOCKOUT_WINDOW_MIN * 60),
+                Duration::from_secs(auth::LOGIN_LOCKOUT_DURATION_MIN * 60),
+            ),
+            mail_ip_limiter: rate_limit::Limiter::new(
+                auth::MAIL_REQUESTS_PER_IP,
+                Duration::from_secs(auth::MAIL_REQUESTS_WINDOW_MIN * 60),
+            ),
+            mail_email_limiter: rate_limit::Limiter::new(
+                auth::MAIL_REQUESTS_PER_EMAIL,
+                Duration::from_secs(auth::MAIL_REQUESTS_WINDOW_MIN * 60),
+            ),
+            presence_touch_limiter: rate_limit::Limiter::new(
+                1,
+                Duration::from_secs(presence::PRESENCE_TOUCH_INTERVAL_SEC),
+            ),
+            rate_limits,
+            acces_token_keys,
+            hashing: config.hashing.clone(),
+            password_policy: config.password_policy.clone(),
+            oidc_providers,
+            metrics,
+            health_checks,
+            config,
         })
     }
 }
@@ -1,5 +1,7 @@
+mod listing;
 mod repository;
 
+pub use listing::*;
 pub use repository::*;
 
 use serde::{
@@ -28,6 +30,9 @@ pub struct Server
     pub id: String,
     pub name: String,
     pub owner: User,
+    //opt-in, lists the server in the public directory
+    #[serde(default)]
+    pub discoverable: bool,
     //key is user id
     pub users: HashMap<String, User>,
     //key is channel id
@@ -42,28 +47,6 @@ pub struct Server
 
 impl Server
 {
-    #[must_use]
-    fn convert(
-        id: String,
-        name: String,
-        owner: User,
-        users: HashMap<String, User>,
-        channels: HashMap<String, Channel>,
-        roles: HashMap<String, Role>,
-        user_roles: HashMap<String, Vec<String>>,
-    ) -> Self
-    {
-        Self {
-            id,
-            name,
-            owner,
-            users,
-            channels,
-            roles,
-            user_roles,
-        }
-    }
-
     pub fn new<'err>(
         name: String,
         owner: User,
@@ -83,6 +66,7 @@ impl Server
             id: Uuid::now_v7().to_string(),
             name,
             owner,
+            discoverable: false,
             users: HashMap::new(),
             channels: HashMap::from([(
                 base_channel.id.clone(),
@@ -170,39 +154,86 @@ impl Server
         self.is_owner(other_user) || self.users.contains_key(other_user)
     }
 
+    #[must_use]
+    pub fn member_count(&self) -> usize
+    {
+        //owner isnt part of users
+        self.users.len() + 1
+    }
+
     #[must_use]
     pub fn filter_channels(
         self,
         user_id: &str,
     ) -> Self
     {
-        let filtered_channels = if self.internal_server_check_permision(
+        if !self.internal_server_check_permision(
             user_id,
             Role::can_read_channels,
         )
         {
-            self.channels
-                .clone()
-                .into_iter()
-                .filter(|(channel_id, _)| {
-                    self.can_read(user_id, Some(channel_id)).unwrap_or(false)
-                })
-                .collect()
+            return self.internal_filter_channels(|_| false);
+        }
+
+        let readable_channel_ids: Vec<String> = self
+            .channels
+            .keys()
+            .filter(|channel_id| {
+                self.can_read(user_id, Some(channel_id)).unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+
+        self.internal_filter_channels(|channel| {
+            readable_channel_ids.contains(&channel.id)
+        })
+    }
+
+    /// filters the channels down to the ones a non-member would be able to read,
+    /// meaning only the channels readable by [`ROLE_NAME_EVERYBODY`].
+    #[must_use]
+    pub fn filter_channels_for_preview(self) -> Self
+    {
+        let can_everybody_read_channels =
+            self.roles.get(ROLE_NAME_EVERYBODY).map_or(
+                self.roles.is_empty(),
+                |role| role.can_read_channels().unwrap_or(true),
+            );
+
+        if !can_everybody_read_channels
+        {
+            return self.internal_filter_channels(|_| false);
         }
-        else
+
+        self.internal_filter_channels(|channel| {
+            channel.can_role_read(ROLE_NAME_EVERYBODY)
+        })
+    }
+
+    pub fn set_discoverable<'err>(
+        &mut self,
+        user_id: &str,
+        discoverable: bool,
+    ) -> error::Result<'err, bool>
+    {
+        if !self.is_owner(user_id)
         {
-            HashMap::new()
-        };
+            return Err(server_error!(
+                error::Kind::IncorrectPermissions,
+                error::OnType::Server
+            )
+            .add_client(error::Client::SERVER_EDIT_NOT_OWNER)
+            .add_debug_info("user id", user_id.to_string()));
+        }
 
-        Self::convert(
-            self.id,
-            self.name,
-            self.owner,
-            self.users,
-            filtered_channels,
-            self.roles,
-            self.user_roles,
-        )
+        if self.discoverable == discoverable
+        {
+            return Ok(false);
+        }
+
+        self.discoverable = discoverable;
+
+        Ok(true)
     }
 }
 
@@ -263,6 +294,16 @@ impl channel::Parent for Server
 
 impl Server
 {
+    fn internal_filter_channels(
+        mut self,
+        predicate: impl Fn(&Channel) -> bool,
+    ) -> Self
+    {
+        self.channels.retain(|_, channel| predicate(channel));
+
+        self
+    }
+
     fn internal_server_check_permision(
         &self,
         user_id: &str,
@@ -351,3 +392,83 @@ impl Server
         Ok(false)
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::channel::{
+        self,
+        Channel,
+    };
+    use crate::model::channel_parent::Server;
+    use crate::model::user::User;
+    use crate::model::ROLE_NAME_EVERYBODY;
+
+    fn internal_create_server() -> Server
+    {
+        let owner = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+
+        Server::new(String::from("Mogcord"), owner).unwrap()
+    }
+
+    #[test]
+    fn test_new_server_discoverable_is_invalid()
+    {
+        let server = internal_create_server();
+
+        assert!(!server.discoverable);
+        assert_eq!(1, server.member_count());
+    }
+
+    #[test]
+    fn test_set_discoverable_as_owner_is_valid()
+    {
+        let mut server = internal_create_server();
+        let owner_id = server.owner.id.clone();
+
+        assert!(server.set_discoverable(&owner_id, true).unwrap());
+        assert!(server.discoverable);
+        assert!(!server.set_discoverable(&owner_id, true).unwrap());
+    }
+
+    #[test]
+    fn test_set_discoverable_as_non_owner_is_invalid()
+    {
+        let mut server = internal_create_server();
+
+        assert!(server.set_discoverable("not_the_owner", true).is_err());
+        assert!(!server.discoverable);
+    }
+
+    #[test]
+    fn test_filter_channels_for_preview_private_channel_is_invalid()
+    {
+        let mut server = internal_create_server();
+
+        let mut private_channel =
+            Channel::new_private(Some(String::from("Staff")));
+        private_channel.add_role(channel::Role::new_public(
+            String::from("staff"),
+            2,
+        ));
+        let private_channel_id = private_channel.id.clone();
+
+        server.channels.insert(
+            private_channel_id.clone(),
+            private_channel,
+        );
+
+        let preview = server.filter_channels_for_preview();
+
+        assert_eq!(1, preview.channels.len());
+        assert!(!preview.channels.contains_key(&private_channel_id));
+        assert!(preview
+            .channels
+            .values()
+            .all(|channel| channel.can_role_read(ROLE_NAME_EVERYBODY)));
+    }
+}
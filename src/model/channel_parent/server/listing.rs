@@ -0,0 +1,14 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+//lightweight view of a discoverable server
+//used for the directory, avoids pulling in every user
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Listing
+{
+    pub id: String,
+    pub name: String,
+    pub member_count: usize,
+}
@@ -1,8 +1,14 @@
 use axum::async_trait;
 
-use crate::model::error;
+use crate::model::{
+    error,
+    Pagination,
+};
 
-use super::Server;
+use super::{
+    Listing,
+    Server,
+};
 
 #[async_trait]
 pub trait Repository: Send + Sync
@@ -16,6 +22,11 @@ pub trait Repository: Send + Sync
         server_id: &'input str,
         user_id: &'input str,
     ) -> error::Result<'err, ()>;
+    async fn update_server_discoverable<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        discoverable: bool,
+    ) -> error::Result<'err, ()>;
     async fn get_server_by_id<'input, 'err>(
         &'input self,
         server_id: &'input str,
@@ -24,4 +35,9 @@ pub trait Repository: Send + Sync
         &'input self,
         channel_id: &'input str,
     ) -> error::Result<'err, Server>;
+    async fn get_discoverable_servers<'input, 'err>(
+        &'input self,
+        name_option: Option<&'input str>,
+        pagination: Pagination,
+    ) -> error::Result<'err, Vec<Listing>>;
 }
@@ -0,0 +1,611 @@
+mod source;
+
+pub use source::*;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::io::telemetry::Telemetry;
+use crate::io::{
+    oidc,
+    tls,
+    webhook,
+};
+use crate::middleware::auth::{
+    ACCES_TOKEN_TTL_MIN,
+    MAX_ACCES_TOKEN_TTL_MIN,
+};
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::model::refresh_token::REFRESH_TOKEN_TTL_IN_DAYS;
+use crate::server_error;
+
+use super::{
+    log,
+    rate_limit,
+    Hashing,
+    PageLimits,
+    PasswordPolicy,
+};
+
+/// every setting of the server, read once on startup.
+///
+/// the connection, socket, log path and secrets have no defaults,
+/// a generated signing key and an open `/metrics` have to be asked for.
+///
+/// no `Debug`, it holds the oidc client secrets and the metrics token.
+pub struct Config
+{
+    pub mongoldb_connection: String,
+    //apply pending migrations on start, when off the server refuses to start with any
+    pub migrate_on_start: bool,
+    pub api_socket: SocketAddr,
+    //serve `api_socket` over https when set
+    pub tls: Option<tls::Settings>,
+    //plain http listener that only redirects to `public_url`
+    pub http_redirect_socket: Option<SocketAddr>,
+    //how long open requests get to finish on shutdown
+    pub shutdown_drain_timeout_sec: u64,
+    //base of the links put in mails, without a trailing slash
+    pub public_url: String,
+    pub log_path: String,
+    pub log_sinks: Vec<log::Sink>,
+    pub log_queue_capacity: usize,
+    pub telemetry: Telemetry,
+    pub mail_path: String,
+    pub acces_token_keys_dir: Option<PathBuf>,
+    //sign with a key generated on startup instead, for development
+    pub acces_token_ephemeral_key: bool,
+    pub acces_token_active_kid: Option<String>,
+    pub acces_token_ttl_min: i64,
+    pub refresh_token_ttl_in_days: i64,
+    pub pagination: PageLimits,
+    pub rate_limits: HashMap<rate_limit::Group, rate_limit::Quota>,
+    pub hashing: Hashing,
+    pub password_policy: PasswordPolicy,
+    pub oidc_providers: Vec<oidc::Settings>,
+    pub webhooks: webhook::Settings,
+    //bearer token for `/metrics`
+    pub metrics_token: Option<String>,
+    //serve `/metrics` without a token, for development
+    pub metrics_public: bool,
+}
+
+impl Config
+{
+    pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SEC: u64 = 30;
+
+    /// reads the environment and the toml file in `CONFIG_PATH`,
+    /// see [`Source`].
+    pub fn load<'err>() -> error::Result<'err, Self>
+    {
+        Self::from_source(&Source::load()?)
+    }
+
+    pub fn from_source<'err>(source: &Source) -> error::Result<'err, Self>
+    {
+        let default_limits = PageLimits::default();
+
+        let config = Self {
+            mongoldb_connection: internal_required(
+                source,
+                "MONGOLDB_CONNECTION",
+            )?,
+            migrate_on_start: source.parse_or(
+                "MIGRATE_ON_START",
+                true,
+                OnType::Config,
+            )?,
+            api_socket: source
+                .parse("API_SOCKET", OnType::Config)?
+                .ok_or_else(|| internal_missing("API_SOCKET"))?,
+            tls: tls::Settings::from_source(source)?,
+            http_redirect_socket: source.parse(
+                "HTTP_REDIRECT_SOCKET",
+                OnType::Config,
+            )?,
+            shutdown_drain_timeout_sec: source.parse_or(
+                "SHUTDOWN_DRAIN_TIMEOUT_SEC",
+                Self::DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SEC,
+                OnType::Config,
+            )?,
+            public_url: internal_string_or(
+                source,
+                "PUBLIC_URL",
+                "http://127.0.0.1:3000",
+            )
+            .trim_end_matches('/')
+            .to_string(),
+            log_path: internal_required(source, "LOG_PATH")?,
+            log_sinks: log::Sink::from_source(source)?,
+            log_queue_capacity: log::Queue::capacity_from_source(source)?,
+            telemetry: Telemetry::from_source(source)?,
+            mail_path: internal_string_or(
+                source,
+                "MAIL_PATH",
+                "./mails_server",
+            ),
+            acces_token_keys_dir: source
+                .get("ACCES_TOKEN_KEYS_DIR")
+                .map(PathBuf::from),
+            acces_token_ephemeral_key: source.parse_or(
+                "ACCES_TOKEN_EPHEMERAL_KEY",
+                false,
+                OnType::Config,
+            )?,
+            acces_token_active_kid: source
+                .get("ACCES_TOKEN_ACTIVE_KID")
+                .map(str::to_string),
+            acces_token_ttl_min: source.parse_or(
+                "ACCES_TOKEN_TTL_MIN",
+                ACCES_TOKEN_TTL_MIN,
+                OnType::Config,
+            )?,
+            refresh_token_ttl_in_days: source.parse_or(
+                "REFRESH_TOKEN_TTL_IN_DAYS",
+                REFRESH_TOKEN_TTL_IN_DAYS,
+                OnType::Config,
+            )?,
+            pagination: PageLimits {
+                default_page_size: source.parse_or(
+                    "PAGE_SIZE_DEFAULT",
+                    default_limits.default_page_size,
+                    OnType::Config,
+                )?,
+                max_page_size: source.parse_or(
+                    "PAGE_SIZE_MAX",
+                    default_limits.max_page_size,
+                    OnType::Config,
+                )?,
+            },
+            rate_limits: rate_limit::GroupLimiter::quotas_from_source(source)?,
+            hashing: Hashing::from_source(source)?,
+            password_policy: PasswordPolicy::from_source(source)?,
+            oidc_providers: oidc::Settings::list_from_source(source)?,
+            webhooks: webhook::Settings::from_source(source)?,
+            metrics_token: source.get("METRICS_TOKEN").map(str::to_string),
+            metrics_public: source.parse_or(
+                "METRICS_PUBLIC",
+                false,
+                OnType::Config,
+            )?,
+        };
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    #[must_use]
+    pub fn acces_token_ttl(&self) -> chrono::Duration
+    {
+        chrono::Duration::minutes(self.acces_token_ttl_min)
+    }
+
+    #[must_use]
+    pub fn shutdown_drain_timeout(&self) -> std::time::Duration
+    {
+        std::time::Duration::from_secs(self.shutdown_drain_timeout_sec)
+    }
+
+    #[must_use]
+    pub fn refresh_token_ttl(&self) -> chrono::Duration
+    {
+        chrono::Duration::days(self.refresh_token_ttl_in_days)
+    }
+
+    fn validate<'err>(&self) -> error::Result<'err, ()>
+    {
+        if self.acces_token_ttl_min <= 0
+            || self.acces_token_ttl_min > MAX_ACCES_TOKEN_TTL_MIN
+        {
+            return Err(internal_invalid(
+                "ACCES_TOKEN_TTL_MIN",
+                "must be above 0 and at most a day",
+            ));
+        }
+
+        if self.refresh_token_ttl_in_days <= 0
+        {
+            return Err(internal_invalid(
+                "REFRESH_TOKEN_TTL_IN_DAYS",
+                "must be above 0",
+            ));
+        }
+
+        if self.pagination.default_page_size == 0
+            || self.pagination.default_page_size > self.pagination.max_page_size
+        {
+            return Err(internal_invalid(
+                "PAGE_SIZE_DEFAULT",
+                "must be above 0 and at most PAGE_SIZE_MAX",
+            ));
+        }
+
+        if !self.public_url.starts_with("http://")
+            && !self.public_url.starts_with("https://")
+        {
+            return Err(internal_invalid(
+                "PUBLIC_URL",
+                "must start with http:// or https://",
+            ));
+        }
+
+        if let Some(tls) = &self.tls
+        {
+            if tls.reload_interval_sec == 0
+            {
+                return Err(internal_invalid(
+                    "TLS_RELOAD_INTERVAL_SEC",
+                    "must be above 0",
+                ));
+            }
+
+            for path in [&tls.cert_path, &tls.key_path]
+            {
+                if !path.is_file()
+                {
+                    return Err(internal_invalid(
+                        "TLS_CERT_PATH",
+                        "TLS_CERT_PATH and TLS_KEY_PATH must be existing files",
+                    )
+                    .add_debug_info(
+                        "path",
+                        path.display().to_string(),
+                    ));
+                }
+            }
+        }
+
+        if let Some(http_redirect_socket) = self.http_redirect_socket
+        {
+            if self.tls.is_none()
+            {
+                return Err(internal_invalid(
+                    "HTTP_REDIRECT_SOCKET",
+                    "needs TLS_CERT_PATH and TLS_KEY_PATH",
+                ));
+            }
+
+            if http_redirect_socket == self.api_socket
+            {
+                return Err(internal_invalid(
+                    "HTTP_REDIRECT_SOCKET",
+                    "must differ from API_SOCKET",
+                ));
+            }
+
+            //redirecting to the host header would make this an open redirect
+            if !self.public_url.starts_with("https://")
+            {
+                return Err(internal_invalid(
+                    "PUBLIC_URL",
+                    "must start with https:// to redirect to it",
+                ));
+            }
+        }
+
+        if self.webhooks.max_concurrent_deliveries == 0
+        {
+            return Err(internal_invalid(
+                "WEBHOOK_MAX_CONCURRENT_DELIVERIES",
+                "must be above 0",
+            ));
+        }
+
+        if self
+            .metrics_token
+            .as_deref()
+            .is_some_and(|token| token.trim().is_empty())
+        {
+            return Err(internal_invalid(
+                "METRICS_TOKEN",
+                "must not be empty when set",
+            ));
+        }
+
+        match (
+            &self.metrics_token,
+            self.metrics_public,
+        )
+        {
+            (None, false) =>
+            {
+                return Err(internal_missing(
+                    "METRICS_TOKEN",
+                ))
+            },
+            (Some(_), true) =>
+            {
+                return Err(internal_invalid(
+                    "METRICS_PUBLIC",
+                    "must not be set together with METRICS_TOKEN",
+                ));
+            },
+            _ =>
+            {},
+        }
+
+        match (
+            &self.acces_token_keys_dir,
+            self.acces_token_ephemeral_key,
+        )
+        {
+            (None, false) =>
+            {
+                return Err(internal_missing(
+                    "ACCES_TOKEN_KEYS_DIR",
+                ));
+            },
+            (Some(_), true) =>
+            {
+                return Err(internal_invalid(
+                    "ACCES_TOKEN_EPHEMERAL_KEY",
+                    "must not be set together with ACCES_TOKEN_KEYS_DIR",
+                ));
+            },
+            _ =>
+            {},
+        }
+
+        if let Some(keys_dir) = &self.acces_token_keys_dir
+        {
+            if !keys_dir.is_dir()
+            {
+                return Err(internal_invalid(
+                    "ACCES_TOKEN_KEYS_DIR",
+                    "must be an existing directory",
+                )
+                .add_debug_info(
+                    "dir",
+                    keys_dir.display().to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn internal_string_or(
+    source: &Source,
+    key: &str,
+    default: &str,
+) -> String
+{
+    source.get(key).unwrap_or(default).to_string()
+}
+
+fn internal_required<'err>(
+    source: &Source,
+    key: &'static str,
+) -> error::Result<'err, String>
+{
+    source
+        .get(key)
+        .map(str::to_string)
+        .ok_or_else(|| internal_missing(key))
+}
+
+fn internal_missing<'err>(key: &'static str) -> error::Server<'err>
+{
+    server_error!(Kind::NotFound, OnType::Config)
+        .add_debug_info("key", key.to_string())
+}
+
+fn internal_invalid<'err>(
+    key: &'static str,
+    reason: &'static str,
+) -> error::Server<'err>
+{
+    server_error!(Kind::InValid, OnType::Config)
+        .add_debug_info("key", key.to_string())
+        .add_debug_info("reason", reason.to_string())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use crate::model::config::{
+        Config,
+        Source,
+    };
+
+    //the settings without a default
+    const REQUIRED: [(&str, &str); 6] = [
+        (
+            "MONGOLDB_CONNECTION",
+            "mongodb://localhost:27017",
+        ),
+        ("API_SOCKET", "127.0.0.1:3000"),
+        ("LOG_PATH", "./logs_server"),
+        (
+            "ACCES_TOKEN_EPHEMERAL_KEY",
+            "true",
+        ),
+        ("METRICS_TOKEN", "metrics"),
+        (
+            "PASSWORD_BLOCKLIST_BUNDLED",
+            "true",
+        ),
+    ];
+
+    fn internal_vars<'vars>(
+        vars: impl IntoIterator<Item = &'vars (&'vars str, &'vars str)>
+    ) -> Source
+    {
+        Source::new(
+            vars.into_iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        value.to_string(),
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    fn internal_source(vars: &[(&str, &str)]) -> Source
+    {
+        internal_vars(REQUIRED.iter().chain(vars))
+    }
+
+    #[test]
+    fn test_config_from_source_is_valid()
+    {
+        let config = Config::from_source(&internal_source(&[
+            ("ACCES_TOKEN_TTL_MIN", "15"),
+            (
+                "REFRESH_TOKEN_TTL_IN_DAYS",
+                "7",
+            ),
+            ("PAGE_SIZE_MAX", "100"),
+            (
+                "PUBLIC_URL",
+                "https://mogcord.example.com/",
+            ),
+        ]))
+        .unwrap();
+
+        assert_eq!(15, config.acces_token_ttl_min);
+        assert_eq!(
+            7,
+            config.refresh_token_ttl_in_days
+        );
+        assert_eq!(
+            100,
+            config.pagination.max_page_size
+        );
+        assert_eq!(
+            "https://mogcord.example.com",
+            config.public_url
+        );
+        assert_eq!(
+            "127.0.0.1:3000",
+            config.api_socket.to_string()
+        );
+    }
+
+    #[test]
+    fn test_config_from_source_is_invalid()
+    {
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "ACCES_TOKEN_TTL_MIN",
+                "0"
+            )]))
+            .is_err()
+        );
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "ACCES_TOKEN_TTL_MIN",
+                "100000"
+            )]))
+            .is_err()
+        );
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "PAGE_SIZE_DEFAULT",
+                "80"
+            )]))
+            .is_err()
+        );
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "API_SOCKET",
+                "localhost"
+            )]))
+            .is_err()
+        );
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "METRICS_TOKEN",
+                " "
+            )]))
+            .is_err()
+        );
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "WEBHOOK_MAX_CONCURRENT_DELIVERIES",
+                "0"
+            )]))
+            .is_err()
+        );
+        //redirecting without serving tls
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "HTTP_REDIRECT_SOCKET",
+                "127.0.0.1:8080"
+            )]))
+            .is_err()
+        );
+        //a provider without its secret
+        assert!(
+            Config::from_source(&internal_source(&[
+                ("OIDC_PROVIDERS", "corp"),
+                (
+                    "OIDC_CORP_ISSUER",
+                    "https://login.example.com"
+                ),
+                (
+                    "OIDC_CORP_CLIENT_ID",
+                    "mogcord"
+                ),
+            ]))
+            .is_err()
+        );
+        //a generated key next to a keys dir
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "ACCES_TOKEN_KEYS_DIR",
+                "."
+            )]))
+            .is_err()
+        );
+        //an open endpoint next to a token
+        assert!(
+            Config::from_source(&internal_source(&[(
+                "METRICS_PUBLIC",
+                "true"
+            )]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_config_from_source_without_required_is_invalid()
+    {
+        for (missing, _) in REQUIRED
+        {
+            assert!(
+                Config::from_source(&internal_vars(
+                    REQUIRED.iter().filter(|(key, _)| *key != missing)
+                ))
+                .is_err(),
+                "{missing}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_from_source_with_dev_opt_ins_is_valid()
+    {
+        let config = Config::from_source(&internal_vars(
+            REQUIRED
+                .iter()
+                .filter(|(key, _)| *key != "METRICS_TOKEN")
+                .chain(&[("METRICS_PUBLIC", "true")]),
+        ))
+        .unwrap();
+
+        assert!(config.metrics_token.is_none());
+        assert!(config.acces_token_keys_dir.is_none());
+    }
+}
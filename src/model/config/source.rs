@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fmt::{
+    self,
+    Display,
+};
+use std::str::FromStr;
+use std::{
+    env,
+    fs,
+};
+
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+/// raw settings by their env name,
+/// the environment on top of the optional toml file in `CONFIG_PATH`.
+#[derive(Default)]
+pub struct Source(HashMap<String, String>);
+
+//holds every secret of the environment, only the names are printed
+impl fmt::Debug for Source
+{
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result
+    {
+        let mut keys: Vec<&String> = self.0.keys().collect();
+        keys.sort_unstable();
+
+        f.debug_tuple("Source").field(&keys).finish()
+    }
+}
+
+impl Source
+{
+    #[must_use]
+    pub fn new(vars: HashMap<String, String>) -> Self
+    {
+        Self(vars)
+    }
+
+    /// reads the file of `CONFIG_PATH` when set, then the environment.
+    pub fn load<'err>() -> error::Result<'err, Self>
+    {
+        let mut vars = match env::var("CONFIG_PATH")
+        {
+            Ok(path) =>
+            {
+                let content = fs::read_to_string(&path).map_err(|err| {
+                    server_error!(Kind::Read, OnType::Config)
+                        .add_debug_info("path", path.clone())
+                        .add_debug_info("error", err.to_string())
+                })?;
+
+                Self::parse_toml(&content).map_err(|err| {
+                    server_error!(Kind::Parse, OnType::Config)
+                        .add_debug_info("path", path)
+                        .add_debug_info("error", err)
+                })?
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        vars.extend(env::vars());
+
+        Ok(Self(vars))
+    }
+
+    /// flattens a toml document to env names,
+    /// `[oidc.corp] client_id = "mogcord"` becomes `OIDC_CORP_CLIENT_ID`
+    /// and arrays become comma separated lists.
+    pub fn parse_toml(content: &str)
+        -> Result<HashMap<String, String>, String>
+    {
+        let table: toml::Table = content
+            .parse()
+            .map_err(|err: toml::de::Error| err.to_string())?;
+
+        let mut vars = HashMap::new();
+        internal_flatten("", &table, &mut vars)?;
+
+        Ok(vars)
+    }
+
+    #[must_use]
+    pub fn get(
+        &self,
+        key: &str,
+    ) -> Option<&str>
+    {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// parses `key`, `default` when it isnt set.
+    pub fn parse_or<'err, T>(
+        &self,
+        key: &str,
+        default: T,
+        on_type: OnType,
+    ) -> error::Result<'err, T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Ok(self.parse(key, on_type)?.unwrap_or(default))
+    }
+
+    /// parses `key`, `None` when it isnt set.
+    pub fn parse<'err, T>(
+        &self,
+        key: &str,
+        on_type: OnType,
+    ) -> error::Result<'err, Option<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.get(key)
+            .map(|value| {
+                value.trim().parse().map_err(|err: T::Err| {
+                    server_error!(Kind::Parse, on_type)
+                        .add_debug_info("key", key.to_string())
+                        .add_debug_info("value", value.to_string())
+                        .add_debug_info("error", err.to_string())
+                })
+            })
+            .transpose()
+    }
+}
+
+fn internal_flatten(
+    prefix: &str,
+    table: &toml::Table,
+    vars: &mut HashMap<String, String>,
+) -> Result<(), String>
+{
+    for (key, value) in table
+    {
+        let key = match prefix
+        {
+            "" => key.to_uppercase(),
+            _ => format!(
+                "{prefix}_{}",
+                key.to_uppercase()
+            ),
+        };
+
+        let value = match value
+        {
+            toml::Value::Table(table) =>
+            {
+                internal_flatten(&key, table, vars)?;
+                continue;
+            },
+            toml::Value::Array(values) => values
+                .iter()
+                .map(|value| {
+                    internal_scalar(value)
+                        .ok_or_else(|| format!("nested value in {key}"))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .join(","),
+            value => internal_scalar(value)
+                .ok_or_else(|| format!("unexpected value in {key}"))?,
+        };
+
+        vars.insert(key, value);
+    }
+
+    Ok(())
+}
+
+fn internal_scalar(value: &toml::Value) -> Option<String>
+{
+    match value
+    {
+        toml::Value::String(value) => Some(value.clone()),
+        toml::Value::Integer(value) => Some(value.to_string()),
+        toml::Value::Float(value) => Some(value.to_string()),
+        toml::Value::Boolean(value) => Some(value.to_string()),
+        toml::Value::Datetime(value) => Some(value.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use crate::model::config::Source;
+
+    #[test]
+    fn test_parse_toml_is_valid()
+    {
+        let vars = Source::parse_toml(
+            r#"
+            log_sinks = ["file", "mongo"]
+            acces_token_ttl_min = 15
+
+            [oidc.corp]
+            client_secret = "secret"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some("file,mongo"),
+            vars.get("LOG_SINKS").map(String::as_str)
+        );
+        assert_eq!(
+            Some("15"),
+            vars.get("ACCES_TOKEN_TTL_MIN").map(String::as_str)
+        );
+        assert_eq!(
+            Some("secret"),
+            vars.get("OIDC_CORP_CLIENT_SECRET").map(String::as_str)
+        );
+    }
+
+    #[test]
+    fn test_parse_toml_is_invalid()
+    {
+        assert!(Source::parse_toml("log_sinks = ").is_err());
+        assert!(Source::parse_toml("log_sinks = [[\"file\"]]").is_err());
+    }
+
+    #[test]
+    fn test_source_debug_is_valid()
+    {
+        let source = Source::new(HashMap::from([(
+            String::from("METRICS_TOKEN"),
+            String::from("hunter22"),
+        )]));
+
+        let debug = format!("{source:?}");
+
+        assert!(debug.contains("METRICS_TOKEN"));
+        assert!(!debug.contains("hunter22"));
+    }
+}
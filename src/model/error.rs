@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::time::Duration;
 
 use axum::http::StatusCode;
 use axum::response::{
@@ -21,6 +22,8 @@ pub struct Server<'err>
     pub line_nr: u32,
     pub debug_info: HashMap<&'err str, String>,
     pub pub_info: Option<String>,
+    //seconds, send along as `Retry-After`
+    pub retry_after: Option<u64>,
     pub client: Client,
     pub child: Option<Box<Server<'err>>>,
 }
@@ -42,6 +45,7 @@ impl<'err> Server<'err>
             line_nr,
             debug_info: HashMap::new(),
             pub_info: None,
+            retry_after: None,
             client: Client::SERVICE_ERROR,
             child: None,
         }
@@ -63,6 +67,7 @@ impl<'err> Server<'err>
             line_nr,
             debug_info: HashMap::new(),
             pub_info: self.pub_info.take(),
+            retry_after: self.retry_after,
             client: self.client.clone(),
             child: Some(Box::new(self)),
         }
@@ -82,6 +87,7 @@ impl<'err> Server<'err>
             line_nr,
             debug_info: HashMap::new(),
             pub_info: self.pub_info.take(),
+            retry_after: self.retry_after,
             client: self.client.clone(),
             child: Some(Box::new(self)),
         }
@@ -107,6 +113,7 @@ impl<'err> Server<'err>
     {
         self.client = child.client.clone();
         self.pub_info = child.pub_info.take();
+        self.retry_after = child.retry_after;
 
         self.child = Some(Box::new(child));
 
@@ -138,6 +145,26 @@ impl<'err> Server<'err>
 
         self
     }
+
+    /// for `TooManyRequests`, tells the client how long to back off.
+    #[must_use]
+    pub fn add_retry_after(
+        self,
+        retry_after: Duration,
+    ) -> Self
+    {
+        //rounded up, retrying a bit too early would just fail again
+        let seconds =
+            retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        let seconds = seconds.max(1);
+
+        let mut server = self.add_public_info(format!(
+            "retry after {seconds} seconds"
+        ));
+        server.retry_after = Some(seconds);
+
+        server
+    }
 }
 
 #[derive(
@@ -170,6 +197,7 @@ pub enum Kind
     Parse,
     Read,
     Revoke,
+    TooManyRequests,
     Unexpected,
     Update,
     Verifying,
@@ -183,22 +211,33 @@ pub enum Kind
 pub enum OnType
 {
     AccesToken,
-    AccesTokenHashKey,
+    AccesTokenKey,
     Bucket,
     Channel,
     ChannelParent,
     Chat,
     ChatGroup,
     ChatPrivate,
+    Config,
     Cookie,
     Ctx,
     Date,
     Hashing,
+    Health,
     Log,
     Macro,
+    Mail,
+    MailToken,
     Email,
+    ExternalIdentity,
     Message,
+    Metrics,
+    Migration,
     Mongo,
+    Oidc,
+    Password,
+    Presence,
+    RateLimit,
     RefreshToken,
     Relation,
     RelationBlocked,
@@ -206,9 +245,14 @@ pub enum OnType
     Rights,
     Server,
     SpawnBlocking,
+    Tls,
     Transaction,
+    TwoFactor,
+    Typing,
     User,
     Username,
+    Webhook,
+    WebhookDelivery,
 }
 
 impl Server<'_>
@@ -309,6 +353,8 @@ impl Server<'_>
 
             Kind::NoAuth => StatusCode::UNAUTHORIZED,
 
+            Kind::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+
             Kind::FileOpening
             | Kind::FlushBuffer
             | Kind::Write
@@ -332,35 +378,62 @@ pub enum Client
     CHAT_ADD_NON_FRIEND,
     CHAT_ADD_WITH_SELF,
     INVALID_PARAMS,
+    LOGIN_LOCKED,
+    LOGIN_RATE_LIMITED,
+    EMAIL_NOT_VERIFIED,
     MAIL_IN_USE,
+    MAIL_TOKEN_INVALID,
     MESSAGE_NOT_PART_CHANNEL,
     NOT_ALLOWED_PLATFORM,
+    OIDC_EMAIL_NOT_VERIFIED,
+    OIDC_LOGIN_FAILED,
+    OIDC_PROVIDER_NOT_FOUND,
     CHAT_EDIT_NOT_OWNER,
     CHAT_PARENT_CTX_NOT_PART_OF_PARENT,
     CHAT_CTX_NOT_PART_OF_CHAT,
     SERVER_CTX_NOT_PART_OF_SERVER,
     PASSWORD_CONFIRM_NOT_MATCH,
+    PASSWORD_CONTAINS_USER_INFO,
+    PASSWORD_INVALID_LENGTH,
+    PASSWORD_MISSING_CHARACTERS,
+    PASSWORD_TOO_COMMON,
     PERMISSION_NO_ADMIN,
     PERMISSION_NO_AUTH,
     PRIVATE_CHAT_TRY_EDIT,
+    RATE_LIMITED,
     COOKIES_NOT_FOUND,
     RELATION_NO_INCOMING_FRIEND,
     MESSAGE_CREATE_FAIL,
     MESSAGE_EDIT_FAIL,
+    MESSAGE_INVALID_LENGTH,
     RELATION_DUPLICATE_OUTGOING_FRIEND,
     SERVER_BLOCKED_YOU,
+    SERVER_EDIT_NOT_OWNER,
+    SERVER_NOT_DISCOVERABLE,
     SERVER_NOT_FOUND,
     SERVICE_ERROR,
+    SESSION_REVOKE_CURRENT,
+    SESSION_REUSE_DETECTED,
     RELATION_SELF_TRY_BLOCK_SELF,
     RELATION_SELF_TRY_FRIEND_SELF,
     RELATION_SELF_TRY_UNBLOCK_SELF,
     RELATION_SELF_TRY_UNFRIEND_SELF,
+    TWO_FACTOR_ALREADY_ENABLED,
+    TWO_FACTOR_INVALID_CODE,
+    TWO_FACTOR_NOT_ENABLED,
+    TWO_FACTOR_RATE_LIMITED,
+    TWO_FACTOR_REQUIRED,
     USER_ALREADY_LOGGED_IN,
     USERNAME_IN_USE,
     RELATION_USER_ALREADY_BLOCKED,
     RELATION_USER_ALREADY_FRIEND,
     RELATION_USER_BLOCKED,
     RELATION_USER_BLOCKED_YOU,
+    WEBHOOK_INVALID_URL,
+    WEBHOOK_CHANNEL_NOT_PART_OF_SERVER,
+    WEBHOOK_INVALID_NAME,
+    WEBHOOK_INVALID_TOKEN,
+    WEBHOOK_RATE_LIMITED,
 }
 
 impl fmt::Display for Client
@@ -0,0 +1,232 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::message::Message;
+
+//not serializable on purpose, payloads carry full models (eg. hashed passwords)
+//consumers should map events to their own dto
+#[derive(Clone, Debug)]
+pub struct Event
+{
+    pub id: String,
+    pub timestamp: DateTime<Utc>,
+    pub kind: Kind,
+}
+
+impl Event
+{
+    #[must_use]
+    pub fn new(kind: Kind) -> Self
+    {
+        Self {
+            id: Uuid::now_v7().to_string(),
+            timestamp: Utc::now(),
+            kind,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Kind
+{
+    MessageCreated
+    {
+        //none when the message lives in a chat
+        server_id: Option<String>,
+        message: Message,
+    },
+    MessageUpdated
+    {
+        server_id: Option<String>,
+        message: Message,
+    },
+    MemberJoined
+    {
+        server_id: String, user_id: String
+    },
+    FriendRequested
+    {
+        user_id: String,
+        other_user_id: String,
+    },
+    FriendConfirmed
+    {
+        user_id: String,
+        other_user_id: String,
+    },
+    ChatUsersAdded
+    {
+        chat_id: String,
+        user_ids: Vec<String>,
+    },
+}
+
+impl Kind
+{
+    #[must_use]
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Kind::MessageCreated {
+                ..
+            } => "message_created",
+            Kind::MessageUpdated {
+                ..
+            } => "message_updated",
+            Kind::MemberJoined {
+                ..
+            } => "member_joined",
+            Kind::FriendRequested {
+                ..
+            } => "friend_requested",
+            Kind::FriendConfirmed {
+                ..
+            } => "friend_confirmed",
+            Kind::ChatUsersAdded {
+                ..
+            } => "chat_users_added",
+        }
+    }
+
+    #[must_use]
+    pub fn server_id(&self) -> Option<&str>
+    {
+        match self
+        {
+            Kind::MessageCreated {
+                server_id,
+                ..
+            }
+            | Kind::MessageUpdated {
+                server_id,
+                ..
+            } => server_id.as_deref(),
+            Kind::MemberJoined {
+                server_id,
+                ..
+            } => Some(server_id),
+            Kind::FriendRequested {
+                ..
+            }
+            | Kind::FriendConfirmed {
+                ..
+            }
+            | Kind::ChatUsersAdded {
+                ..
+            } => None,
+        }
+    }
+
+    #[must_use]
+    pub fn channel_id(&self) -> Option<&str>
+    {
+        match self
+        {
+            Kind::MessageCreated {
+                message,
+                ..
+            }
+            | Kind::MessageUpdated {
+                message,
+                ..
+            } => Some(&message.channel.id),
+            _ => None,
+        }
+    }
+}
+
+/// in-process publish/subscribe for domain [`Event`]s.
+///
+/// every subscriber gets its own copy of each event,
+/// a subscriber that falls more than [`Bus::CAPACITY`] events behind
+/// skips the oldest ones instead of slowing down the publisher.
+pub struct Bus
+{
+    sender: broadcast::Sender<Event>,
+}
+
+impl Bus
+{
+    pub const CAPACITY: usize = 1024;
+
+    #[must_use]
+    pub fn new() -> Self
+    {
+        let (sender, _) = broadcast::channel(Self::CAPACITY);
+
+        Self {
+            sender,
+        }
+    }
+
+    pub fn publish(
+        &self,
+        kind: Kind,
+    )
+    {
+        //only errors when nobody is listening, which is fine
+        let _ = self.sender.send(Event::new(kind));
+    }
+
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<Event>
+    {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for Bus
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::event::{
+        Bus,
+        Kind,
+    };
+
+    #[tokio::test]
+    async fn test_publish_is_valid()
+    {
+        let bus = Bus::new();
+
+        let mut subscriber_1 = bus.subscribe();
+        let mut subscriber_2 = bus.subscribe();
+
+        bus.publish(Kind::MemberJoined {
+            server_id: String::from("server"),
+            user_id: String::from("user"),
+        });
+
+        let event_1 = subscriber_1.recv().await.unwrap();
+        let event_2 = subscriber_2.recv().await.unwrap();
+
+        assert_eq!(event_1.id, event_2.id);
+        assert_eq!(
+            "member_joined",
+            event_1.kind.name()
+        );
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_valid()
+    {
+        let bus = Bus::new();
+
+        bus.publish(Kind::FriendRequested {
+            user_id: String::from("user"),
+            other_user_id: String::from("other"),
+        });
+    }
+}
@@ -0,0 +1,57 @@
+mod repository;
+
+pub use repository::*;
+
+use chrono::{
+    DateTime,
+    Utc,
+};
+use uuid::Uuid;
+
+//account of an oidc provider linked to a user, the subject is stable per provider
+#[derive(Clone, Debug)]
+pub struct ExternalIdentity
+{
+    pub id: String,
+    pub provider: String,
+    pub subject: String,
+    pub user_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExternalIdentity
+{
+    #[must_use]
+    pub fn new(
+        provider: String,
+        subject: String,
+        user_id: String,
+    ) -> Self
+    {
+        Self {
+            id: Uuid::now_v7().to_string(),
+            provider,
+            subject,
+            user_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn convert(
+        id: String,
+        provider: String,
+        subject: String,
+        user_id: String,
+        created_at: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            id,
+            provider,
+            subject,
+            user_id,
+            created_at,
+        }
+    }
+}
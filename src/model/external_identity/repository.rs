@@ -0,0 +1,19 @@
+use axum::async_trait;
+
+use crate::model::error;
+
+use super::ExternalIdentity;
+
+#[async_trait]
+pub trait Repository: Send + Sync
+{
+    async fn create_external_identity<'input, 'err>(
+        &'input self,
+        identity: ExternalIdentity,
+    ) -> error::Result<'err, ExternalIdentity>;
+    async fn get_external_identity<'input, 'err>(
+        &'input self,
+        provider: &'input str,
+        subject: &'input str,
+    ) -> error::Result<'err, ExternalIdentity>;
+}
@@ -5,9 +5,15 @@ use argon2::password_hash::{
     PasswordVerifier,
     SaltString,
 };
-use argon2::Argon2;
+use argon2::{
+    Algorithm,
+    Argon2,
+    Params,
+    Version,
+};
 use tokio::task;
 
+use super::config::Source;
 use super::error::{
     self,
     Kind,
@@ -15,21 +21,79 @@ use super::error::{
 };
 use crate::server_error;
 
-pub struct Hashing;
+/// argon2id hashing with the parameters from config.
+///
+/// hashes keep the parameters they were made with,
+/// so changing them only affects new hashes, see `needs_rehash`.
+#[derive(Clone, Debug)]
+pub struct Hashing
+{
+    params: Params,
+}
+
+impl Default for Hashing
+{
+    fn default() -> Self
+    {
+        Self::new(Params::default())
+    }
+}
 
 impl Hashing
 {
+    #[must_use]
+    pub fn new(params: Params) -> Self
+    {
+        Self {
+            params,
+        }
+    }
+
+    /// reads `ARGON2_MEMORY_KIB`, `ARGON2_ITERATIONS` and `ARGON2_PARALLELISM`,
+    /// falling back to the argon2 defaults.
+    pub fn from_source<'err>(source: &Source) -> error::Result<'err, Self>
+    {
+        let memory_kib = source.parse_or(
+            "ARGON2_MEMORY_KIB",
+            Params::DEFAULT_M_COST,
+            OnType::Hashing,
+        )?;
+        let iterations = source.parse_or(
+            "ARGON2_ITERATIONS",
+            Params::DEFAULT_T_COST,
+            OnType::Hashing,
+        )?;
+        let parallelism = source.parse_or(
+            "ARGON2_PARALLELISM",
+            Params::DEFAULT_P_COST,
+            OnType::Hashing,
+        )?;
+
+        let params = Params::new(
+            memory_kib,
+            iterations,
+            parallelism,
+            None,
+        )
+        .map_err(|err| {
+            server_error!(Kind::InValid, OnType::Hashing)
+                .add_debug_info("error", err.to_string())
+        })?;
+
+        Ok(Self::new(params))
+    }
+
     pub async fn hash_text<'err>(
-        clear_text: &str
+        &self,
+        clear_text: &str,
     ) -> error::Result<'err, String>
     {
         let clear_text = clear_text.to_string();
+        let argon2 = self.internal_give_argon_settings();
 
         let text_hashed = task::spawn_blocking(move || {
             let salt = SaltString::generate(&mut OsRng);
 
-            let argon2 = Self::internal_give_argon_settings();
-
             //no need to return the salt, its stored inside the hash
             return argon2
                 .hash_password(clear_text.as_bytes(), &salt)
@@ -57,12 +121,15 @@ impl Hashing
     }
 
     pub async fn verify_hash<'input, 'err>(
+        &self,
         clear_text: &'input str,
         hash: &'input str,
     ) -> error::Result<'err, ()>
     {
         let clear_text = clear_text.to_string();
         let hash = hash.to_string();
+        //verifying uses the parameters stored in the hash
+        let argon2 = self.internal_give_argon_settings();
 
         task::spawn_blocking(move || {
             let parsed_hash = PasswordHash::new(&hash).map_err(|_| {
@@ -72,8 +139,6 @@ impl Hashing
                 )
             })?;
 
-            let argon2 = Self::internal_give_argon_settings();
-
             argon2
                 .verify_password(
                     clear_text.as_bytes(),
@@ -97,8 +162,64 @@ impl Hashing
         Ok(())
     }
 
-    fn internal_give_argon_settings<'schema>() -> Argon2<'schema>
+    /// whether the hash was made with other settings than the current ones.
+    #[must_use]
+    pub fn needs_rehash(
+        &self,
+        hash: &str,
+    ) -> bool
+    {
+        let Ok(parsed_hash) = PasswordHash::new(hash)
+        else
+        {
+            return false;
+        };
+
+        if parsed_hash.algorithm != Algorithm::Argon2id.ident()
+            || parsed_hash.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        match Params::try_from(&parsed_hash)
+        {
+            Ok(params) =>
+            {
+                params.m_cost() != self.params.m_cost()
+                    || params.t_cost() != self.params.t_cost()
+                    || params.p_cost() != self.params.p_cost()
+            },
+            Err(_) => true,
+        }
+    }
+
+    fn internal_give_argon_settings(&self) -> Argon2<'static>
+    {
+        Argon2::new(
+            Algorithm::Argon2id,
+            Version::V0x13,
+            self.params.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use argon2::Params;
+
+    use crate::model::Hashing;
+
+    #[tokio::test]
+    async fn test_needs_rehash_after_params_changed_is_valid()
     {
-        Argon2::default()
+        let old_hashing = Hashing::new(Params::new(8, 1, 1, None).unwrap());
+        let new_hashing = Hashing::new(Params::new(16, 1, 1, None).unwrap());
+
+        let hash = old_hashing.hash_text("hunter2").await.unwrap();
+
+        assert!(!old_hashing.needs_rehash(&hash));
+        assert!(new_hashing.needs_rehash(&hash));
+        assert!(new_hashing.verify_hash("hunter2", &hash).await.is_ok());
     }
 }
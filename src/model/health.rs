@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::async_trait;
+use futures_util::future::join_all;
+use serde::Serialize;
+
+use super::error;
+
+/// a dependency the server can't do without.
+#[async_trait]
+pub trait Check: Send + Sync
+{
+    async fn check<'err>(&self) -> error::Result<'err, ()>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status
+{
+    Ok,
+    Failing,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report
+{
+    pub status: Status,
+    //per check, errors are only traced, they can hold paths and hosts
+    pub checks: Vec<(&'static str, Status)>,
+}
+
+impl Report
+{
+    /// runs every check at once, a check that takes longer than `timeout` fails.
+    pub async fn run(
+        checks: &[(&'static str, Arc<dyn Check>)],
+        timeout: Duration,
+    ) -> Self
+    {
+        let results =
+            join_all(checks.iter().map(|(name, check)| async move {
+                let status =
+                    match tokio::time::timeout(timeout, check.check()).await
+                    {
+                        Ok(Ok(())) => Status::Ok,
+                        Ok(Err(err)) =>
+                        {
+                            tracing::warn!(check = name, error = %err, "health check failed");
+
+                            Status::Failing
+                        },
+                        Err(_) =>
+                        {
+                            tracing::warn!(check = name, "health check timed out");
+
+                            Status::Failing
+                        },
+                    };
+
+                (*name, status)
+            }))
+            .await;
+
+        let status = if results.iter().all(|(_, status)| *status == Status::Ok)
+        {
+            Status::Ok
+        }
+        else
+        {
+            Status::Failing
+        };
+
+        Self {
+            status,
+            checks: results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use axum::async_trait;
+
+    use crate::model::error;
+    use crate::model::health::{
+        Check,
+        Report,
+        Status,
+    };
+    use crate::server_error;
+
+    struct Fixed(bool);
+
+    #[async_trait]
+    impl Check for Fixed
+    {
+        async fn check<'err>(&self) -> error::Result<'err, ()>
+        {
+            if self.0
+            {
+                return Ok(());
+            }
+
+            Err(server_error!(
+                error::Kind::Unexpected,
+                error::OnType::Health
+            ))
+        }
+    }
+
+    struct Hanging;
+
+    #[async_trait]
+    impl Check for Hanging
+    {
+        async fn check<'err>(&self) -> error::Result<'err, ()>
+        {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_all_ok_is_valid()
+    {
+        let report = Report::run(
+            &[
+                (
+                    "a",
+                    Arc::new(Fixed(true)) as Arc<dyn Check>,
+                ),
+                ("b", Arc::new(Fixed(true))),
+            ],
+            Duration::from_millis(50),
+        )
+        .await;
+
+        assert_eq!(Status::Ok, report.status);
+    }
+
+    #[tokio::test]
+    async fn test_report_failing_or_hanging_is_invalid()
+    {
+        let report = Report::run(
+            &[
+                (
+                    "a",
+                    Arc::new(Fixed(true)) as Arc<dyn Check>,
+                ),
+                ("b", Arc::new(Fixed(false))),
+                ("c", Arc::new(Hanging)),
+            ],
+            Duration::from_millis(50),
+        )
+        .await;
+
+        assert_eq!(Status::Failing, report.status);
+        assert_eq!(
+            vec![
+                ("a", Status::Ok),
+                ("b", Status::Failing),
+                ("c", Status::Failing)
+            ],
+            report.checks
+        );
+    }
+}
@@ -1,25 +1,33 @@
+mod entry;
+mod queue;
 mod repository;
+mod sink;
 
+pub use entry::*;
+pub use queue::*;
 pub use repository::*;
+pub use sink::*;
 
 use axum::http::{
     Method,
     Uri,
 };
-use serde::Serialize;
-use serde_json::json;
-use std::sync::Arc;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use uuid::Uuid;
 
 use super::error;
 
-pub async fn log_request(
-    state: Arc<dyn Repository>,
+/// queues the log, the sinks are written to in the background.
+pub fn log_request(
+    queue: &Queue,
     req_id: Uuid,
     user_info: RequestLogLinePersonal,
-    req_method: Method,
-    uri: Uri,
-    service_error: Option<&error::Server<'_>>,
+    req_method: &Method,
+    uri: &Uri,
+    service_error: Option<&error::Server<'static>>,
     client_error: Option<error::Client>,
 )
 {
@@ -39,25 +47,14 @@ pub async fn log_request(
         server_error: service_error.cloned(),
     };
 
-    println!(
-        "   ->> log_request: \n{:#}",
-        json!(log_line)
-    );
-    if let Err(err) = state.create_log(log_line).await
-    {
-        println!("	->> LOG INSERT FAILED");
-        println!(
-            "   ->> log_request FAILED INSERT: \n{}",
-            json!(err)
-        );
-    }
+    queue.push(log_line);
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RequestLogLinePersonal
 {
-    user_id: Option<String>,
-    device_id: Option<String>,
+    pub user_id: Option<String>,
+    pub device_id: Option<String>,
 }
 
 impl RequestLogLinePersonal
@@ -75,7 +72,7 @@ impl RequestLogLinePersonal
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct RequestLogLine<'err>
 {
     pub req_id: String,
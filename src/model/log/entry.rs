@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use chrono::{
+    DateTime,
+    NaiveDateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::RequestLogLinePersonal;
+
+/// a request log as read back from a sink.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Entry
+{
+    pub req_id: String,
+    pub timestamp: String,
+    pub user_info: RequestLogLinePersonal,
+    pub req_path: String,
+    pub req_method: String,
+    pub client_error_type: Option<String>,
+    //the error chain flattened, outermost first
+    pub server_error: Option<Vec<EntryError>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntryError
+{
+    pub kind: String,
+    pub on_type: String,
+    //file and line
+    pub stack: String,
+    pub debug_info: HashMap<String, String>,
+    pub pub_info: Option<String>,
+}
+
+impl Entry
+{
+    //how `RequestLogLine` writes its timestamp
+    const TIMESTAMP_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S%.f UTC";
+
+    #[must_use]
+    pub fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>>
+    {
+        NaiveDateTime::parse_from_str(
+            timestamp,
+            Self::TIMESTAMP_FORMAT,
+        )
+        .ok()
+        .map(|naive| naive.and_utc())
+    }
+}
+
+/// what to look for in the request logs, every set field has to match.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Filter
+{
+    pub req_id: Option<String>,
+    pub user_id: Option<String>,
+    pub device_id: Option<String>,
+    //prefix of the path, query included
+    pub path: Option<String>,
+    pub client_error_type: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl Filter
+{
+    pub const DEFAULT_LIMIT: usize = 50;
+    pub const MAX_LIMIT: usize = 500;
+    //exports are built in memory
+    pub const MAX_EXPORT_LIMIT: usize = 10_000;
+
+    #[must_use]
+    pub fn matches(
+        &self,
+        entry: &Entry,
+    ) -> bool
+    {
+        let optional_eq = |filter: &Option<String>, value: Option<&String>| {
+            filter.as_ref().is_none_or(|filter| Some(filter) == value)
+        };
+
+        if !optional_eq(
+            &self.req_id,
+            Some(&entry.req_id),
+        ) || !optional_eq(
+            &self.user_id,
+            entry.user_info.user_id.as_ref(),
+        ) || !optional_eq(
+            &self.device_id,
+            entry.user_info.device_id.as_ref(),
+        ) || !optional_eq(
+            &self.client_error_type,
+            entry.client_error_type.as_ref(),
+        ) || !self
+            .path
+            .as_ref()
+            .is_none_or(|path| entry.req_path.starts_with(path))
+        {
+            return false;
+        }
+
+        if self.from.is_none() && self.to.is_none()
+        {
+            return true;
+        }
+
+        Entry::parse_timestamp(&entry.timestamp).is_some_and(|timestamp| {
+            self.from.is_none_or(|from| from <= timestamp)
+                && self.to.is_none_or(|to| timestamp <= to)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::{
+        Duration,
+        Utc,
+    };
+
+    use crate::model::log::{
+        Entry,
+        Filter,
+        RequestLogLinePersonal,
+    };
+
+    fn entry() -> Entry
+    {
+        Entry {
+            req_id: String::from("req"),
+            timestamp: Utc::now().to_string(),
+            user_info: RequestLogLinePersonal::new(
+                Some(String::from("user")),
+                None,
+            ),
+            req_path: String::from("/api/chat/1?page=2"),
+            req_method: String::from("GET"),
+            client_error_type: Some(String::from("SERVICE_ERROR")),
+            server_error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_timestamp_is_valid()
+    {
+        let now = Utc::now();
+
+        assert_eq!(
+            Some(now),
+            Entry::parse_timestamp(&now.to_string())
+        );
+    }
+
+    #[test]
+    fn test_filter_matches_is_valid()
+    {
+        let entry = entry();
+        let filter = Filter {
+            user_id: Some(String::from("user")),
+            path: Some(String::from("/api/chat")),
+            client_error_type: Some(String::from("SERVICE_ERROR")),
+            from: Some(Utc::now() - Duration::minutes(1)),
+            ..Default::default()
+        };
+
+        assert!(Filter::default().matches(&entry));
+        assert!(filter.matches(&entry));
+    }
+
+    #[test]
+    fn test_filter_matches_is_invalid()
+    {
+        let entry = entry();
+
+        assert!(!Filter {
+            device_id: Some(String::from("device")),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!Filter {
+            path: Some(String::from("/api/server")),
+            ..Default::default()
+        }
+        .matches(&entry));
+        assert!(!Filter {
+            to: Some(Utc::now() - Duration::minutes(1)),
+            ..Default::default()
+        }
+        .matches(&entry));
+    }
+}
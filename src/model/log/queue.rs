@@ -0,0 +1,283 @@
+use std::sync::atomic::{
+    AtomicU64,
+    Ordering,
+};
+use std::sync::Arc;
+
+use tokio::sync::{
+    mpsc,
+    oneshot,
+};
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+use super::{
+    Repository,
+    RequestLogLine,
+};
+
+/// bounded queue in front of the log sinks,
+/// a single background task drains it so requests never wait on a write.
+///
+/// logs are dropped, not waited on, while the queue is full.
+#[derive(Clone)]
+pub struct Queue
+{
+    sender: mpsc::Sender<Message>,
+    dropped: Arc<AtomicU64>,
+}
+
+enum Message
+{
+    Log(Box<RequestLogLine<'static>>),
+    //answered once every log queued before it is written
+    Flush(oneshot::Sender<()>),
+}
+
+impl Queue
+{
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// spawns the task writing to `sink`, it stops once every `Queue` is dropped.
+    #[must_use]
+    pub fn spawn(
+        sink: Arc<dyn Repository>,
+        capacity: usize,
+    ) -> Self
+    {
+        let (sender, mut receiver) = mpsc::channel(capacity.max(1));
+
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await
+            {
+                match message
+                {
+                    Message::Log(log) =>
+                    {
+                        if let Err(err) = sink.create_log(*log).await
+                        {
+                            tracing::error!(error = %err, "log sink failed");
+                        }
+                    },
+                    Message::Flush(done) =>
+                    {
+                        let _ = done.send(());
+                    },
+                }
+            }
+        });
+
+        Self {
+            sender,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// reads `LOG_QUEUE_CAPACITY`, defaulting to `DEFAULT_CAPACITY`.
+    pub fn capacity_from_source<'err>(
+        source: &Source
+    ) -> error::Result<'err, usize>
+    {
+        let capacity = source.parse_or(
+            "LOG_QUEUE_CAPACITY",
+            Self::DEFAULT_CAPACITY,
+            OnType::Log,
+        )?;
+
+        if capacity == 0
+        {
+            return Err(
+                server_error!(Kind::InValid, OnType::Log).add_debug_info(
+                    "key",
+                    String::from("LOG_QUEUE_CAPACITY"),
+                ),
+            );
+        }
+
+        Ok(capacity)
+    }
+
+    /// hands the log to the background task.
+    pub fn push(
+        &self,
+        log: RequestLogLine<'static>,
+    )
+    {
+        match self.sender.try_send(Message::Log(Box::new(log)))
+        {
+            Ok(()) | Err(mpsc::error::TrySendError::Closed(_)) =>
+            {},
+            Err(mpsc::error::TrySendError::Full(message)) =>
+            {
+                let Message::Log(log) = message
+                else
+                {
+                    return;
+                };
+
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+
+                tracing::warn!(
+                    req_id = %log.req_id,
+                    dropped,
+                    "log queue full, dropped request log"
+                );
+            },
+        }
+    }
+
+    /// waits until every log pushed before this call is written,
+    /// used on shutdown so the last requests aren't lost.
+    pub async fn flush(&self)
+    {
+        let (done, written) = oneshot::channel();
+
+        if self.sender.send(Message::Flush(done)).await.is_ok()
+        {
+            let _ = written.await;
+        }
+    }
+
+    /// how many logs were dropped because the queue was full.
+    #[must_use]
+    pub fn dropped(&self) -> u64
+    {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::{
+        Arc,
+        Mutex,
+    };
+    use std::time::Duration;
+
+    use axum::async_trait;
+    use tokio::sync::Notify;
+
+    use crate::model::error;
+    use crate::model::log::{
+        Entry,
+        Filter,
+        Queue,
+        Repository,
+        RequestLogLine,
+        RequestLogLinePersonal,
+    };
+
+    //blocks every write until released, keeps what it got
+    struct SlowSink
+    {
+        release: Notify,
+        req_ids: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Repository for SlowSink
+    {
+        async fn create_log<'input, 'err>(
+            &'input self,
+            log: RequestLogLine<'input>,
+        ) -> error::Result<'err, ()>
+        {
+            self.release.notified().await;
+            self.req_ids.lock().unwrap().push(log.req_id);
+
+            Ok(())
+        }
+
+        async fn get_logs<'input, 'err>(
+            &'input self,
+            _filter: &'input Filter,
+            _limit: usize,
+        ) -> error::Result<'err, Vec<Entry>>
+        {
+            Ok(Vec::new())
+        }
+    }
+
+    fn log_line(req_id: &str) -> RequestLogLine<'static>
+    {
+        RequestLogLine {
+            req_id: req_id.to_string(),
+            timestamp: String::new(),
+            user_info: RequestLogLinePersonal::new(None, None),
+            req_path: String::from("/"),
+            req_method: String::from("GET"),
+            client_error_type: None,
+            server_error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_full_queue_is_invalid()
+    {
+        let sink = Arc::new(SlowSink {
+            release: Notify::new(),
+            req_ids: Mutex::new(Vec::new()),
+        });
+        let queue = Queue::spawn(sink.clone(), 1);
+
+        //the first is picked up by the task and blocks it, the second waits
+        queue.push(log_line("1"));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        queue.push(log_line("2"));
+        assert_eq!(0, queue.dropped());
+        queue.push(log_line("3"));
+        assert_eq!(1, queue.dropped());
+
+        for _ in 0..2
+        {
+            sink.release.notify_one();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert_eq!(
+            vec!["1", "2"],
+            *sink.req_ids.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_waits_for_pushed_logs_is_valid()
+    {
+        let sink = Arc::new(SlowSink {
+            release: Notify::new(),
+            req_ids: Mutex::new(Vec::new()),
+        });
+        let queue = Queue::spawn(sink.clone(), 4);
+
+        queue.push(log_line("1"));
+        queue.push(log_line("2"));
+
+        let flushing = queue.clone();
+        let flush = tokio::spawn(async move { flushing.flush().await });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!flush.is_finished());
+
+        for _ in 0..2
+        {
+            sink.release.notify_one();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        tokio::time::timeout(Duration::from_secs(1), flush)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            vec!["1", "2"],
+            *sink.req_ids.lock().unwrap()
+        );
+    }
+}
@@ -2,7 +2,11 @@ use axum::async_trait;
 
 use crate::model::error;
 
-use super::RequestLogLine;
+use super::{
+    Entry,
+    Filter,
+    RequestLogLine,
+};
 
 #[async_trait]
 pub trait Repository: Send + Sync
@@ -11,4 +15,10 @@ pub trait Repository: Send + Sync
         &'input self,
         log: RequestLogLine<'input>,
     ) -> error::Result<'err, ()>;
+    /// newest first, at most `limit`.
+    async fn get_logs<'input, 'err>(
+        &'input self,
+        filter: &'input Filter,
+        limit: usize,
+    ) -> error::Result<'err, Vec<Entry>>;
 }
@@ -0,0 +1,183 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+use super::{
+    Entry,
+    Filter,
+    Repository,
+    RequestLogLine,
+};
+
+/// where request logs end up, picked with `LOG_SINKS`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sink
+{
+    //a json line per request in a file per day under `LOG_PATH`
+    File,
+    //the `logs` collection
+    Mongo,
+    //a json line per request on stdout
+    Stdout,
+}
+
+impl FromStr for Sink
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.trim().to_lowercase().as_str()
+        {
+            "file" => Ok(Self::File),
+            "mongo" => Ok(Self::Mongo),
+            "stdout" => Ok(Self::Stdout),
+            _ => Err(format!(
+                "expected file, mongo or stdout, got {s}"
+            )),
+        }
+    }
+}
+
+impl Sink
+{
+    pub const DEFAULT: [Sink; 1] = [Sink::File];
+
+    /// parses a comma separated list, e.g. `file,mongo`.
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String>
+    {
+        let mut sinks = Vec::new();
+
+        for sink in s.split(',').filter(|sink| !sink.trim().is_empty())
+        {
+            let sink: Self = sink.parse()?;
+
+            if !sinks.contains(&sink)
+            {
+                sinks.push(sink);
+            }
+        }
+
+        if sinks.is_empty()
+        {
+            return Err(String::from(
+                "expected at least one sink",
+            ));
+        }
+
+        Ok(sinks)
+    }
+
+    /// reads `LOG_SINKS`, without it logs go to a file.
+    pub fn from_source<'err>(source: &Source)
+        -> error::Result<'err, Vec<Self>>
+    {
+        let Some(value) = source.get("LOG_SINKS")
+        else
+        {
+            return Ok(Self::DEFAULT.to_vec());
+        };
+
+        Self::parse_list(value).map_err(|err| {
+            server_error!(Kind::Parse, OnType::Log)
+                .add_debug_info(
+                    "key",
+                    String::from("LOG_SINKS"),
+                )
+                .add_debug_info("error", err)
+        })
+    }
+}
+
+/// writes every log to all of its sinks.
+pub struct FanOut(Vec<Arc<dyn Repository>>);
+
+impl FanOut
+{
+    #[must_use]
+    pub fn new(sinks: Vec<Arc<dyn Repository>>) -> Self
+    {
+        Self(sinks)
+    }
+}
+
+#[async_trait]
+impl Repository for FanOut
+{
+    /// one failing sink doesnt keep the log from the others,
+    /// the first error is returned after all of them were tried.
+    async fn create_log<'input, 'err>(
+        &'input self,
+        log: RequestLogLine<'input>,
+    ) -> error::Result<'err, ()>
+    {
+        let mut result = Ok(());
+
+        for sink in &self.0
+        {
+            if let Err(err) = sink.create_log(log.clone()).await
+            {
+                if result.is_ok()
+                {
+                    result = Err(err);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// read from the first sink that can be queried.
+    async fn get_logs<'input, 'err>(
+        &'input self,
+        filter: &'input Filter,
+        limit: usize,
+    ) -> error::Result<'err, Vec<Entry>>
+    {
+        for sink in &self.0
+        {
+            match sink.get_logs(filter, limit).await
+            {
+                Err(err) if matches!(err.kind, Kind::NotImplemented) =>
+                {},
+                result => return result,
+            }
+        }
+
+        Err(server_error!(
+            Kind::NotImplemented,
+            OnType::Log
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::log::Sink;
+
+    #[test]
+    fn test_parse_list_is_valid()
+    {
+        assert_eq!(
+            Ok(vec![Sink::File, Sink::Mongo]),
+            Sink::parse_list("file, Mongo,file,")
+        );
+    }
+
+    #[test]
+    fn test_parse_list_is_invalid()
+    {
+        assert!(Sink::parse_list("file,kafka").is_err());
+        assert!(Sink::parse_list(" , ").is_err());
+    }
+}
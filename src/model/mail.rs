@@ -0,0 +1,63 @@
+mod mailer;
+
+pub use mailer::*;
+
+use super::mail_token::Purpose;
+
+//plain text on purpose, keeps every mailer implementation trivial
+#[derive(Clone, Debug)]
+pub struct Mail
+{
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Mail
+{
+    #[must_use]
+    pub fn new(
+        to: String,
+        subject: String,
+        body: String,
+    ) -> Self
+    {
+        Self {
+            to,
+            subject,
+            body,
+        }
+    }
+
+    #[must_use]
+    pub fn email_verification(
+        to: String,
+        link: &str,
+    ) -> Self
+    {
+        Self::new(
+            to,
+            String::from("Verify your Mogcord email"),
+            format!(
+                "Welcome to Mogcord!\n\nVerify your email by opening the link below, it expires in {} hours.\n\n{link}\n\nIf you didn't register, you can ignore this mail.",
+                Purpose::EmailVerification.ttl().num_hours()
+            ),
+        )
+    }
+
+    #[must_use]
+    pub fn password_reset(
+        to: String,
+        link: &str,
+    ) -> Self
+    {
+        Self::new(
+            to,
+            String::from("Reset your Mogcord password"),
+            format!(
+                "Someone asked to reset the password of your Mogcord account.\n\nChoose a new password by opening the link below, it expires in {} minutes.\n\n{link}\n\nIf it wasn't you, you can ignore this mail.",
+                Purpose::PasswordReset.ttl().num_minutes()
+            ),
+        )
+    }
+}
@@ -0,0 +1,14 @@
+use axum::async_trait;
+
+use crate::model::error;
+
+use super::Mail;
+
+#[async_trait]
+pub trait Mailer: Send + Sync
+{
+    async fn send_mail<'input, 'err>(
+        &'input self,
+        mail: Mail,
+    ) -> error::Result<'err, ()>;
+}
@@ -0,0 +1,136 @@
+mod repository;
+
+pub use repository::*;
+
+use argon2::password_hash::rand_core::{
+    OsRng,
+    RngCore,
+};
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Purpose
+{
+    EmailVerification,
+    PasswordReset,
+}
+
+impl Purpose
+{
+    #[must_use]
+    pub fn ttl(&self) -> Duration
+    {
+        match self
+        {
+            Self::EmailVerification => Duration::hours(24),
+            Self::PasswordReset => Duration::minutes(30),
+        }
+    }
+}
+
+//single use token mailed to a user, only the hash is kept
+#[derive(Clone, Debug)]
+pub struct MailToken
+{
+    pub id: String,
+    pub user_id: String,
+    pub purpose: Purpose,
+    pub token_hash: String,
+    pub expiration_date: DateTime<Utc>,
+}
+
+impl MailToken
+{
+    /// returns the token together with the plain value for the mail.
+    #[must_use]
+    pub fn new(
+        user_id: String,
+        purpose: Purpose,
+    ) -> (Self, String)
+    {
+        let mut random_number = [0u8; 32];
+        OsRng.fill_bytes(&mut random_number);
+        let value = hex::encode(random_number);
+
+        let token = Self {
+            id: Uuid::now_v7().to_string(),
+            user_id,
+            expiration_date: Utc::now() + purpose.ttl(),
+            purpose,
+            token_hash: Self::hash_value(&value),
+        };
+
+        (token, value)
+    }
+
+    #[must_use]
+    pub fn convert(
+        id: String,
+        user_id: String,
+        purpose: Purpose,
+        token_hash: String,
+        expiration_date: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            id,
+            user_id,
+            purpose,
+            token_hash,
+            expiration_date,
+        }
+    }
+
+    //values are random and long, a plain sha256 is enough, no need for argon
+    #[must_use]
+    pub fn hash_value(value: &str) -> String
+    {
+        hex::encode(Sha256::digest(
+            value.trim().as_bytes(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::Utc;
+
+    use crate::model::mail_token::{
+        MailToken,
+        Purpose,
+    };
+
+    #[test]
+    fn test_new_token_keeps_only_hash_is_valid()
+    {
+        let (token, value) = MailToken::new(
+            String::from("user"),
+            Purpose::PasswordReset,
+        );
+
+        assert_ne!(value, token.token_hash);
+        assert_eq!(
+            MailToken::hash_value(&value),
+            token.token_hash
+        );
+        assert!(token.expiration_date > Utc::now());
+        assert!(
+            token.expiration_date <= Utc::now() + Purpose::PasswordReset.ttl()
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use axum::async_trait;
+
+use crate::model::error;
+
+use super::{
+    MailToken,
+    Purpose,
+};
+
+#[async_trait]
+pub trait Repository: Send + Sync
+{
+    /// replaces the unused tokens of the user with the same purpose.
+    async fn create_mail_token<'input, 'err>(
+        &'input self,
+        token: MailToken,
+    ) -> error::Result<'err, MailToken>;
+    /// returns the token without using it up, as long as it did not expire.
+    async fn get_mail_token<'input, 'err>(
+        &'input self,
+        purpose: Purpose,
+        token_hash: &'input str,
+    ) -> error::Result<'err, MailToken>;
+    /// removes and returns the token, as long as it did not expire.
+    async fn consume_mail_token<'input, 'err>(
+        &'input self,
+        purpose: Purpose,
+        token_hash: &'input str,
+    ) -> error::Result<'err, MailToken>;
+}
@@ -1,6 +1,8 @@
+mod bot;
 mod flag;
 mod repository;
 
+pub use bot::*;
 pub use flag::*;
 pub use repository::*;
 
@@ -28,7 +30,11 @@ pub struct Message
     pub value: String,
     #[serde(with = "chrono_datetime_as_bson_datetime")]
     pub timestamp: DateTime<Utc>,
+    //for bot messages this is the user that created the webhook
     pub owner: User,
+    //set when the message was posted through an incoming webhook
+    #[serde(default)]
+    pub bot: Option<Bot>,
     pub channel: Channel,
     pub bucket_id: Option<String>,
     //we actually gonna delete stuff?
@@ -38,22 +44,39 @@ pub struct Message
 
 impl Message
 {
-    #[must_use]
-    pub fn new(
+    pub const MAX_LENGTH: usize = 2000;
+
+    pub fn new<'err>(
         value: String,
         owner: User,
         channel: Channel,
-    ) -> Self
+    ) -> error::Result<'err, Self>
     {
-        Self {
+        Self::is_value_meeting_requirements(&value)?;
+
+        Ok(Self {
             id: Uuid::now_v7().to_string(),
             value,
             timestamp: Utc::now(),
             owner,
+            bot: None,
             channel,
             bucket_id: None,
             flag: Flag::None,
-        }
+        })
+    }
+
+    pub fn new_from_bot<'err>(
+        value: String,
+        bot: Bot,
+        owner: User,
+        channel: Channel,
+    ) -> error::Result<'err, Self>
+    {
+        let mut message = Self::new(value, owner, channel)?;
+        message.bot = Some(bot);
+
+        Ok(message)
     }
 }
 
@@ -80,6 +103,8 @@ impl Message
             return Ok(false);
         }
 
+        Self::is_value_meeting_requirements(&value)?;
+
         self.value = value;
         self.flag = Flag::Edited {
             date: Utc::now(),
@@ -88,6 +113,25 @@ impl Message
         Ok(true)
     }
 
+    pub fn is_value_meeting_requirements<'err>(
+        value: &str
+    ) -> error::Result<'err, ()>
+    {
+        let length = value.trim().chars().count();
+
+        if length == 0 || length > Self::MAX_LENGTH
+        {
+            return Err(server_error!(
+                error::Kind::InValid,
+                error::OnType::Message
+            )
+            .add_client(error::Client::MESSAGE_INVALID_LENGTH)
+            .add_debug_info("length", length.to_string()));
+        }
+
+        Ok(())
+    }
+
     #[must_use]
     pub fn is_channel_part_of_message(
         &self,
@@ -104,7 +148,9 @@ impl Message
         user_roles_option: Option<&Vec<String>>,
     ) -> bool
     {
-        if self.owner.id != *user_id || !self.flag.is_allowed_to_be_editted()
+        if self.bot.is_some()
+            || self.owner.id != *user_id
+            || !self.flag.is_allowed_to_be_editted()
         {
             return false;
         }
@@ -130,3 +176,33 @@ impl Message
         can_read && can_write
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::message::Message;
+
+    #[test]
+    fn test_value_within_length_is_valid()
+    {
+        assert!(Message::is_value_meeting_requirements("hello").is_ok());
+        assert!(
+            Message::is_value_meeting_requirements(
+                &"a".repeat(Message::MAX_LENGTH)
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_value_empty_or_too_long_is_invalid()
+    {
+        assert!(Message::is_value_meeting_requirements("   ").is_err());
+        assert!(
+            Message::is_value_meeting_requirements(
+                &"a".repeat(Message::MAX_LENGTH + 1)
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+//author of messages posted through an incoming webhook, not a user
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bot
+{
+    pub webhook_id: String,
+    pub name: String,
+}
+
+impl Bot
+{
+    #[must_use]
+    pub fn new(
+        webhook_id: String,
+        name: String,
+    ) -> Self
+    {
+        Self {
+            webhook_id,
+            name,
+        }
+    }
+}
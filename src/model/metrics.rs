@@ -0,0 +1,388 @@
+use std::time::Duration;
+
+use mongodb::event::command::CommandEvent;
+use prometheus::{
+    Encoder,
+    HistogramOpts,
+    HistogramVec,
+    IntCounter,
+    IntCounterVec,
+    IntGauge,
+    Opts,
+    Registry,
+    TextEncoder,
+};
+use tokio::sync::broadcast;
+
+use super::error::{
+    self,
+    Kind,
+    OnType,
+};
+use super::event::{
+    self,
+    Event,
+};
+use crate::server_error;
+
+/// everything `/metrics` exposes, in the prometheus text format.
+pub struct Metrics
+{
+    registry: Registry,
+    //labeled with the route pattern, not the path, to keep the series bounded
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    server_errors: IntCounterVec,
+    mongo_command_duration: HistogramVec,
+    mongo_command_errors: IntCounterVec,
+    active_sessions: IntGauge,
+    messages_created: IntCounter,
+    //events the counter skipped, so `messages_created` undercounts by up to this
+    events_lagged: IntCounter,
+}
+
+impl Metrics
+{
+    pub fn new<'err>() -> error::Result<'err, Self>
+    {
+        let registry = Registry::new_custom(
+            Some(String::from("mogcord")),
+            None,
+        )
+        .map_err(internal_metrics_error)?;
+
+        let http_requests = IntCounterVec::new(
+            Opts::new(
+                "http_requests_total",
+                "handled requests per route and status",
+            ),
+            &["method", "route", "status"],
+        )
+        .map_err(internal_metrics_error)?;
+
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "time to handle a request per route",
+            ),
+            &["method", "route"],
+        )
+        .map_err(internal_metrics_error)?;
+
+        let server_errors = IntCounterVec::new(
+            Opts::new(
+                "server_errors_total",
+                "errors returned by handlers per kind and type",
+            ),
+            &["kind", "on_type"],
+        )
+        .map_err(internal_metrics_error)?;
+
+        let mongo_command_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "mongo_command_duration_seconds",
+                "time of a mongo command per command name",
+            )
+            .buckets(vec![
+                0.000_5,
+                0.001,
+                0.002_5,
+                0.005,
+                0.01,
+                0.025,
+                0.05,
+                0.1,
+                0.25,
+                0.5,
+                1.0,
+            ]),
+            &["command"],
+        )
+        .map_err(internal_metrics_error)?;
+
+        let mongo_command_errors = IntCounterVec::new(
+            Opts::new(
+                "mongo_command_errors_total",
+                "failed mongo commands per command name",
+            ),
+            &["command"],
+        )
+        .map_err(internal_metrics_error)?;
+
+        let active_sessions = IntGauge::new(
+            "active_sessions",
+            "valid refresh tokens, updated on every scrape",
+        )
+        .map_err(internal_metrics_error)?;
+
+        let messages_created = IntCounter::new(
+            "messages_created_total",
+            "messages sent in chats and servers",
+        )
+        .map_err(internal_metrics_error)?;
+
+        let events_lagged = IntCounter::new(
+            "events_lagged_total",
+            "events skipped because the event counter fell behind",
+        )
+        .map_err(internal_metrics_error)?;
+
+        registry
+            .register(Box::new(
+                http_requests.clone(),
+            ))
+            .and_then(|()| {
+                registry.register(Box::new(
+                    http_request_duration.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    server_errors.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    mongo_command_duration.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    mongo_command_errors.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    active_sessions.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    messages_created.clone(),
+                ))
+            })
+            .and_then(|()| {
+                registry.register(Box::new(
+                    events_lagged.clone(),
+                ))
+            })
+            .map_err(internal_metrics_error)?;
+
+        Ok(Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            server_errors,
+            mongo_command_duration,
+            mongo_command_errors,
+            active_sessions,
+            messages_created,
+            events_lagged,
+        })
+    }
+
+    pub fn observe_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration: Duration,
+    )
+    {
+        self.http_requests
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_request_duration
+            .with_label_values(&[method, route])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// counts the outermost error, its children are the same failure.
+    pub fn observe_error(
+        &self,
+        err: &error::Server<'_>,
+    )
+    {
+        self.server_errors
+            .with_label_values(&[err.kind.as_ref(), err.on_type.as_ref()])
+            .inc();
+    }
+
+    /// for the command event handler of the mongo client.
+    pub fn observe_mongo_command(
+        &self,
+        event: &CommandEvent,
+    )
+    {
+        match event
+        {
+            CommandEvent::Succeeded(succeeded) =>
+            {
+                self.mongo_command_duration
+                    .with_label_values(&[&succeeded.command_name])
+                    .observe(succeeded.duration.as_secs_f64());
+            },
+            CommandEvent::Failed(failed) =>
+            {
+                self.mongo_command_duration
+                    .with_label_values(&[&failed.command_name])
+                    .observe(failed.duration.as_secs_f64());
+                self.mongo_command_errors
+                    .with_label_values(&[&failed.command_name])
+                    .inc();
+            },
+            _ =>
+            {},
+        }
+    }
+
+    pub fn set_active_sessions(
+        &self,
+        count: u64,
+    )
+    {
+        self.active_sessions
+            .set(i64::try_from(count).unwrap_or(i64::MAX));
+    }
+
+    /// counts messages from the event bus, so handlers dont have to.
+    pub fn spawn_event_counter(
+        self: std::sync::Arc<Self>,
+        mut receiver: broadcast::Receiver<Event>,
+    )
+    {
+        tokio::spawn(async move {
+            loop
+            {
+                match receiver.recv().await
+                {
+                    Ok(Event {
+                        kind:
+                            event::Kind::MessageCreated {
+                                ..
+                            },
+                        ..
+                    }) => self.messages_created.inc(),
+                    Ok(_) =>
+                    {},
+                    //what the skipped events were is unknown
+                    Err(broadcast::error::RecvError::Lagged(skipped)) =>
+                    {
+                        self.events_lagged.inc_by(skipped);
+                    },
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    pub fn encode<'err>(&self) -> error::Result<'err, String>
+    {
+        let mut buffer = Vec::new();
+
+        TextEncoder::new()
+            .encode(
+                &self.registry.gather(),
+                &mut buffer,
+            )
+            .map_err(internal_metrics_error)?;
+
+        String::from_utf8(buffer).map_err(|err| {
+            server_error!(Kind::Parse, OnType::Metrics)
+                .add_debug_info("error", err.to_string())
+        })
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn internal_metrics_error<'err>(err: prometheus::Error) -> error::Server<'err>
+{
+    server_error!(
+        Kind::Unexpected,
+        OnType::Metrics
+    )
+    .add_debug_info("error", err.to_string())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::broadcast;
+
+    use crate::model::error;
+    use crate::model::event::{
+        self,
+        Event,
+    };
+    use crate::model::metrics::Metrics;
+    use crate::server_error;
+
+    #[test]
+    fn test_encode_is_valid()
+    {
+        let metrics = Metrics::new().unwrap();
+
+        metrics.observe_request(
+            "GET",
+            "/api/chat/:chat_id",
+            200,
+            Duration::from_millis(5),
+        );
+        metrics.observe_error(&server_error!(
+            error::Kind::NotFound,
+            error::OnType::Chat
+        ));
+        metrics.set_active_sessions(3);
+
+        let text = metrics.encode().unwrap();
+
+        assert!(text.contains(
+            "mogcord_http_requests_total{method=\"GET\",route=\"/api/chat/:chat_id\",status=\"200\"} 1"
+        ));
+        assert!(text.contains(
+            "mogcord_server_errors_total{kind=\"NotFound\",on_type=\"Chat\"} 1"
+        ));
+        assert!(text.contains("mogcord_active_sessions 3"));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_event_counter_lagged_is_valid()
+    {
+        let metrics = Arc::new(Metrics::new().unwrap());
+        let (sender, receiver) = broadcast::channel(1);
+
+        for _ in 0..3
+        {
+            sender
+                .send(Event::new(
+                    event::Kind::MemberJoined {
+                        server_id: String::from("server"),
+                        user_id: String::from("user"),
+                    },
+                ))
+                .unwrap();
+        }
+
+        Arc::clone(&metrics).spawn_event_counter(receiver);
+
+        let mut text = String::new();
+        for _ in 0..100
+        {
+            text = metrics.encode().unwrap();
+
+            if text.contains("mogcord_events_lagged_total 2")
+            {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        //member joins, not messages
+        assert!(text.contains("mogcord_events_lagged_total 2"));
+        assert!(text.contains("mogcord_messages_created_total 0"));
+    }
+}
@@ -8,33 +8,60 @@ pub struct Pagination
     pub page_size: usize,
 }
 
+/// page sizes a client gets and may ask for, see `Config::pagination`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageLimits
+{
+    pub default_page_size: usize,
+    pub max_page_size: usize,
+}
+
+impl Default for PageLimits
+{
+    fn default() -> Self
+    {
+        Self {
+            default_page_size: 25,
+            max_page_size: 50,
+        }
+    }
+}
+
 impl Pagination
 {
     const MIN_PAGE_NR: usize = 1;
-    const MAX_PER_PAGE: usize = 50;
 
     fn new_valid(
         page: usize,
         per_page: usize,
+        limits: PageLimits,
     ) -> Self
     {
         Self {
             page: page.max(Self::MIN_PAGE_NR),
-            page_size: per_page.min(Self::MAX_PER_PAGE),
+            page_size: per_page.min(limits.max_page_size),
         }
     }
 
     #[must_use]
-    pub fn new(page_option: Option<Query<Pagination>>) -> Self
+    pub fn new(
+        page_option: Option<Query<Pagination>>,
+        limits: PageLimits,
+    ) -> Self
     {
-        page_option
-            .map(|Query(pagination)| {
+        page_option.map_or(
+            Self {
+                page: Self::MIN_PAGE_NR,
+                page_size: limits.default_page_size,
+            },
+            |Query(pagination)| {
                 Self::new_valid(
                     pagination.page,
                     pagination.page_size,
+                    limits,
                 )
-            })
-            .unwrap_or_default()
+            },
+        )
     }
 }
 
@@ -46,14 +73,3 @@ impl Pagination
         (self.page - 1) * self.page_size
     }
 }
-
-impl Default for Pagination
-{
-    fn default() -> Self
-    {
-        Self {
-            page: 1,
-            page_size: 25,
-        }
-    }
-}
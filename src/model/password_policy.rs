@@ -0,0 +1,452 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::{
+    fmt,
+    fs,
+};
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    Kind,
+    OnType,
+};
+use crate::server_error;
+
+//a few hundred common passwords, most shorter than the minimum length,
+//only for development, see `PASSWORD_BLOCKLIST_BUNDLED`
+const BUNDLED_BLOCKLIST: &str =
+    include_str!("password_policy/common_passwords.txt");
+
+/// passwords that are rejected no matter the other rules, lowercase.
+///
+/// the bundled list is short, point `PASSWORD_BLOCKLIST_PATH` at a breached
+/// password list (e.g. the top 100k of the seclists project) for a real one.
+#[derive(Clone)]
+pub struct Blocklist(Arc<HashSet<String>>);
+
+impl Blocklist
+{
+    /// one password per line, lines starting with # are ignored.
+    ///
+    /// passwords shorter than `min_length` are dropped,
+    /// the length rule rejects those already.
+    #[must_use]
+    pub fn parse(
+        content: &str,
+        min_length: usize,
+    ) -> Self
+    {
+        Self(Arc::new(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter(|line| line.chars().count() >= min_length)
+                .map(str::to_lowercase)
+                .collect(),
+        ))
+    }
+
+    #[must_use]
+    pub fn contains(
+        &self,
+        lowercase_password: &str,
+    ) -> bool
+    {
+        self.0.contains(lowercase_password)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize
+    {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool
+    {
+        self.0.is_empty()
+    }
+}
+
+//the list can be 100k lines, not something to put in an error
+impl fmt::Debug for Blocklist
+{
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result
+    {
+        write!(
+            f,
+            "Blocklist({} passwords)",
+            self.len()
+        )
+    }
+}
+
+/// rules a new password has to follow, checked on registration and reset.
+#[derive(Clone, Debug)]
+pub struct PasswordPolicy
+{
+    pub min_length: usize,
+    pub max_length: usize,
+    //out of lowercase, uppercase, digits and symbols
+    pub min_character_classes: usize,
+    pub blocklist: Blocklist,
+}
+
+impl Default for PasswordPolicy
+{
+    fn default() -> Self
+    {
+        let min_length = 8;
+
+        Self {
+            min_length,
+            max_length: 128,
+            min_character_classes: 2,
+            blocklist: Blocklist::parse(BUNDLED_BLOCKLIST, min_length),
+        }
+    }
+}
+
+impl PasswordPolicy
+{
+    //parts of the username or email shorter than this are too common to reject
+    const MIN_USER_INFO_LENGTH: usize = 3;
+
+    /// reads `PASSWORD_MIN_LENGTH`, `PASSWORD_MAX_LENGTH`,
+    /// `PASSWORD_MIN_CHARACTER_CLASSES` and `PASSWORD_BLOCKLIST_PATH`,
+    /// falling back to the defaults.
+    pub fn from_source<'err>(source: &Source) -> error::Result<'err, Self>
+    {
+        let default = Self::default();
+
+        let min_length = source.parse_or(
+            "PASSWORD_MIN_LENGTH",
+            default.min_length,
+            OnType::Password,
+        )?;
+
+        let bundled = source.parse_or(
+            "PASSWORD_BLOCKLIST_BUNDLED",
+            false,
+            OnType::Password,
+        )?;
+
+        let blocklist = match (
+            source.get("PASSWORD_BLOCKLIST_PATH"),
+            bundled,
+        )
+        {
+            (Some(path), false) =>
+            {
+                let content = fs::read_to_string(path).map_err(|err| {
+                    server_error!(Kind::Read, OnType::Password)
+                        .add_debug_info(
+                            "key",
+                            String::from("PASSWORD_BLOCKLIST_PATH"),
+                        )
+                        .add_debug_info("path", path.to_string())
+                        .add_debug_info("error", err.to_string())
+                })?;
+
+                Blocklist::parse(&content, min_length)
+            },
+            (None, true) => Blocklist::parse(BUNDLED_BLOCKLIST, min_length),
+            (None, false) =>
+            {
+                return Err(server_error!(
+                    Kind::NotFound,
+                    OnType::Password
+                )
+                .add_debug_info(
+                    "key",
+                    String::from("PASSWORD_BLOCKLIST_PATH"),
+                ));
+            },
+            (Some(_), true) =>
+            {
+                return Err(server_error!(
+                    Kind::InValid,
+                    OnType::Password
+                )
+                .add_debug_info(
+                    "key",
+                    String::from("PASSWORD_BLOCKLIST_BUNDLED"),
+                )
+                .add_debug_info(
+                    "reason",
+                    String::from(
+                        "must not be set together with PASSWORD_BLOCKLIST_PATH",
+                    ),
+                ));
+            },
+        };
+
+        let policy = Self {
+            min_length,
+            max_length: source.parse_or(
+                "PASSWORD_MAX_LENGTH",
+                default.max_length,
+                OnType::Password,
+            )?,
+            min_character_classes: source.parse_or(
+                "PASSWORD_MIN_CHARACTER_CLASSES",
+                default.min_character_classes,
+                OnType::Password,
+            )?,
+            blocklist,
+        };
+
+        if policy.min_length == 0
+            || policy.min_length > policy.max_length
+            || policy.min_character_classes > 4
+        {
+            return Err(server_error!(
+                Kind::InValid,
+                OnType::Password
+            )
+            .add_debug_info(
+                "policy",
+                format!("{policy:?}"),
+            ));
+        }
+
+        Ok(policy)
+    }
+
+    pub fn validate<'err>(
+        &self,
+        password: &str,
+        username: &str,
+        email: &str,
+    ) -> error::Result<'err, ()>
+    {
+        let length = password.chars().count();
+
+        if length < self.min_length || length > self.max_length
+        {
+            return Err(server_error!(
+                Kind::InValid,
+                OnType::Password
+            )
+            .add_client(error::Client::PASSWORD_INVALID_LENGTH)
+            .add_public_info(format!(
+                "password must be between {} and {} characters",
+                self.min_length, self.max_length
+            )));
+        }
+
+        if Self::count_character_classes(password) < self.min_character_classes
+        {
+            return Err(server_error!(
+                Kind::InValid,
+                OnType::Password
+            )
+            .add_client(error::Client::PASSWORD_MISSING_CHARACTERS)
+            .add_public_info(format!(
+                "password needs {} out of lowercase, uppercase, digits and symbols",
+                self.min_character_classes
+            )));
+        }
+
+        let lowercase_password = password.to_lowercase();
+
+        let local_part = email.split('@').next().unwrap_or_default();
+        let contains_user_info = [username, local_part]
+            .iter()
+            .map(|part| part.trim().to_lowercase())
+            .filter(|part| part.chars().count() >= Self::MIN_USER_INFO_LENGTH)
+            .any(|part| lowercase_password.contains(&part));
+
+        if contains_user_info
+        {
+            return Err(server_error!(
+                Kind::InValid,
+                OnType::Password
+            )
+            .add_client(error::Client::PASSWORD_CONTAINS_USER_INFO));
+        }
+
+        if self.blocklist.contains(&lowercase_password)
+        {
+            return Err(server_error!(
+                Kind::InValid,
+                OnType::Password
+            )
+            .add_client(error::Client::PASSWORD_TOO_COMMON));
+        }
+
+        Ok(())
+    }
+
+    fn count_character_classes(password: &str) -> usize
+    {
+        let has_lowercase = password.chars().any(char::is_lowercase);
+        let has_uppercase = password.chars().any(char::is_uppercase);
+        let has_digit = password.chars().any(|c| c.is_ascii_digit());
+        let has_symbol = password.chars().any(|c| !c.is_alphanumeric());
+
+        [has_lowercase, has_uppercase, has_digit, has_symbol]
+            .into_iter()
+            .filter(|has_class| *has_class)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use crate::model::config::Source;
+    use crate::model::error::Client;
+    use crate::model::{
+        Blocklist,
+        PasswordPolicy,
+    };
+
+    fn validate_client(password: &str) -> Option<Client>
+    {
+        PasswordPolicy::default()
+            .validate(
+                password,
+                "mogger",
+                "mogger@example.com",
+            )
+            .err()
+            .map(|err| err.client)
+    }
+
+    #[test]
+    fn test_validate_is_valid()
+    {
+        assert!(validate_client("correct horse battery staple").is_none());
+        assert!(validate_client("Tr0ub4dor&3").is_none());
+    }
+
+    #[test]
+    fn test_validate_length_is_invalid()
+    {
+        assert!(matches!(
+            validate_client("aB3$"),
+            Some(Client::PASSWORD_INVALID_LENGTH)
+        ));
+        assert!(matches!(
+            validate_client(&"aB3$".repeat(40)),
+            Some(Client::PASSWORD_INVALID_LENGTH)
+        ));
+    }
+
+    #[test]
+    fn test_validate_character_classes_is_invalid()
+    {
+        assert!(matches!(
+            validate_client("onlylowercase"),
+            Some(Client::PASSWORD_MISSING_CHARACTERS)
+        ));
+    }
+
+    #[test]
+    fn test_validate_user_info_is_invalid()
+    {
+        assert!(matches!(
+            validate_client("my-MOGGER-pass"),
+            Some(Client::PASSWORD_CONTAINS_USER_INFO)
+        ));
+    }
+
+    #[test]
+    fn test_validate_common_password_is_invalid()
+    {
+        assert!(matches!(
+            validate_client("Password123"),
+            Some(Client::PASSWORD_TOO_COMMON)
+        ));
+    }
+
+    #[test]
+    fn test_blocklist_parse_is_valid()
+    {
+        let blocklist = Blocklist::parse(
+            "# comment\n123456\nPassword123\n\n  letmein!  \n",
+            8,
+        );
+
+        assert_eq!(2, blocklist.len());
+        assert!(blocklist.contains("password123"));
+        assert!(blocklist.contains("letmein!"));
+        //shorter than the minimum length, rejected by that rule already
+        assert!(!blocklist.contains("123456"));
+    }
+
+    #[test]
+    fn test_validate_custom_blocklist_is_invalid()
+    {
+        let policy = PasswordPolicy {
+            blocklist: Blocklist::parse("Tr0ub4dor&3", 8),
+            ..PasswordPolicy::default()
+        };
+
+        assert!(matches!(
+            policy
+                .validate(
+                    "Tr0ub4dor&3",
+                    "mogger",
+                    "mogger@example.com"
+                )
+                .err()
+                .map(|err| err.client),
+            Some(Client::PASSWORD_TOO_COMMON)
+        ));
+    }
+
+    fn internal_source(vars: &[(&str, &str)]) -> Source
+    {
+        Source::new(
+            vars.iter()
+                .map(|(key, value)| {
+                    (
+                        key.to_string(),
+                        value.to_string(),
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_password_policy_from_source_is_valid()
+    {
+        let policy = PasswordPolicy::from_source(&internal_source(&[(
+            "PASSWORD_BLOCKLIST_BUNDLED",
+            "true",
+        )]))
+        .unwrap();
+
+        assert!(!policy.blocklist.is_empty());
+    }
+
+    #[test]
+    fn test_password_policy_from_source_is_invalid()
+    {
+        //a blocklist has to be picked
+        assert!(PasswordPolicy::from_source(&internal_source(&[])).is_err());
+        assert!(
+            PasswordPolicy::from_source(&internal_source(&[
+                (
+                    "PASSWORD_BLOCKLIST_BUNDLED",
+                    "true"
+                ),
+                (
+                    "PASSWORD_BLOCKLIST_PATH",
+                    "./passwords.txt"
+                ),
+            ]))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+mod repository;
+mod status;
+
+pub use repository::*;
+pub use status::*;
+
+use chrono::{
+    DateTime,
+    Duration,
+    Utc,
+};
+
+//after this the user counts as offline, regardless of the status they set
+pub const PRESENCE_TTL_MIN: i64 = 5;
+//authenticated requests refresh `last_seen` at most this often per user,
+//see `AppState::presence_touch_limiter`
+pub const PRESENCE_TOUCH_INTERVAL_SEC: u64 = 60;
+pub const TYPING_TTL_SEC: i64 = 8;
+
+#[derive(Clone, Debug)]
+pub struct Presence
+{
+    pub user_id: String,
+    pub status: Status,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+impl Presence
+{
+    #[must_use]
+    pub fn new(
+        user_id: String,
+        status: Status,
+    ) -> Self
+    {
+        Self {
+            user_id,
+            status,
+            last_seen: Some(Utc::now()),
+        }
+    }
+
+    #[must_use]
+    pub fn convert(
+        user_id: String,
+        status: Status,
+        last_seen: Option<DateTime<Utc>>,
+    ) -> Self
+    {
+        Self {
+            user_id,
+            status,
+            last_seen,
+        }
+    }
+
+    #[must_use]
+    pub fn new_offline(user_id: String) -> Self
+    {
+        Self {
+            user_id,
+            status: Status::Offline,
+            last_seen: None,
+        }
+    }
+}
+
+impl Presence
+{
+    /// returns the status other users should see.
+    ///
+    /// a user that hasn't been seen for [`PRESENCE_TTL_MIN`] is offline,
+    /// even if their last set status says otherwise.
+    #[must_use]
+    pub fn effective_status(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Status
+    {
+        match self.last_seen
+        {
+            Some(last_seen)
+                if now - last_seen <= Duration::minutes(PRESENCE_TTL_MIN) =>
+            {
+                self.status.clone()
+            },
+            _ => Status::Offline,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Typing
+{
+    pub user_id: String,
+    pub channel_id: String,
+    pub expiration_date: DateTime<Utc>,
+}
+
+impl Typing
+{
+    #[must_use]
+    pub fn new(
+        user_id: String,
+        channel_id: String,
+    ) -> Self
+    {
+        Self {
+            user_id,
+            channel_id,
+            expiration_date: Utc::now() + Duration::seconds(TYPING_TTL_SEC),
+        }
+    }
+
+    #[must_use]
+    pub fn convert(
+        user_id: String,
+        channel_id: String,
+        expiration_date: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            user_id,
+            channel_id,
+            expiration_date,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::{
+        Duration,
+        Utc,
+    };
+
+    use crate::model::presence::{
+        Presence,
+        Status,
+        PRESENCE_TOUCH_INTERVAL_SEC,
+        PRESENCE_TTL_MIN,
+    };
+
+    #[test]
+    fn test_effective_status_recently_seen_is_valid()
+    {
+        let presence = Presence::new(
+            String::from("user"),
+            Status::DoNotDisturb,
+        );
+
+        assert_eq!(
+            Status::DoNotDisturb,
+            presence.effective_status(Utc::now())
+        );
+    }
+
+    #[test]
+    fn test_effective_status_stale_is_invalid()
+    {
+        let presence = Presence::convert(
+            String::from("user"),
+            Status::Online,
+            Some(Utc::now() - Duration::minutes(PRESENCE_TTL_MIN + 1)),
+        );
+
+        assert_eq!(
+            Status::Offline,
+            presence.effective_status(Utc::now())
+        );
+    }
+
+    #[test]
+    fn test_effective_status_never_seen_is_invalid()
+    {
+        let presence = Presence::new_offline(String::from("user"));
+
+        assert_eq!(
+            Status::Offline,
+            presence.effective_status(Utc::now())
+        );
+    }
+
+    #[test]
+    fn test_effective_status_touched_by_activity_is_valid()
+    {
+        //an active client is touched at most once per interval,
+        //so right before the next touch it still shows its status
+        let presence = Presence::convert(
+            String::from("user"),
+            Status::Idle,
+            Some(
+                Utc::now()
+                    - Duration::seconds(
+                        i64::try_from(PRESENCE_TOUCH_INTERVAL_SEC).unwrap(),
+                    ),
+            ),
+        );
+
+        assert_eq!(
+            Status::Idle,
+            presence.effective_status(Utc::now())
+        );
+    }
+}
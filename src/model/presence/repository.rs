@@ -0,0 +1,34 @@
+use axum::async_trait;
+
+use crate::model::error;
+
+use super::{
+    Presence,
+    Typing,
+};
+
+#[async_trait]
+pub trait Repository: Send + Sync
+{
+    async fn upsert_presence<'input, 'err>(
+        &'input self,
+        presence: Presence,
+    ) -> error::Result<'err, Presence>;
+    /// sets `last_seen` to now, a user without a presence yet shows as online.
+    async fn touch_presence<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, ()>;
+    async fn get_presences<'input, 'err>(
+        &'input self,
+        user_ids: Vec<&'input str>,
+    ) -> error::Result<'err, Vec<Presence>>;
+    async fn upsert_typing<'input, 'err>(
+        &'input self,
+        typing: Typing,
+    ) -> error::Result<'err, ()>;
+    async fn get_typing<'input, 'err>(
+        &'input self,
+        channel_id: &'input str,
+    ) -> error::Result<'err, Vec<Typing>>;
+}
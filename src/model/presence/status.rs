@@ -0,0 +1,32 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status
+{
+    Online,
+    Idle,
+    DoNotDisturb,
+    Offline,
+}
+
+impl fmt::Display for Status
+{
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result
+    {
+        match self
+        {
+            Self::Online => write!(f, "online"),
+            Self::Idle => write!(f, "idle"),
+            Self::DoNotDisturb => write!(f, "do_not_disturb"),
+            Self::Offline => write!(f, "offline"),
+        }
+    }
+}
@@ -0,0 +1,432 @@
+mod quota;
+mod token_bucket;
+
+pub use quota::*;
+pub use token_bucket::*;
+
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+use std::sync::Mutex;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// the state of every key of a limiter.
+///
+/// stale keys are dropped once the map doubled since the last prune instead
+/// of on every hit, so a hit doesnt scan every tracked client under the lock.
+pub(crate) struct KeyMap<V>
+{
+    entries: HashMap<String, V>,
+    prune_at: usize,
+}
+
+impl<V> KeyMap<V>
+{
+    const MIN_PRUNE_AT: usize = 1024;
+
+    pub(crate) fn new() -> Self
+    {
+        Self {
+            entries: HashMap::new(),
+            prune_at: Self::MIN_PRUNE_AT,
+        }
+    }
+
+    /// the entry of the key, after pruning when the map grew enough.
+    pub(crate) fn entry_or_insert_with(
+        &mut self,
+        key: &str,
+        is_live: impl FnMut(&V) -> bool,
+        default: impl FnOnce() -> V,
+    ) -> &mut V
+    {
+        if self.entries.len() >= self.prune_at
+        {
+            self.prune(is_live);
+        }
+
+        self.entries.entry(key.to_string()).or_insert_with(default)
+    }
+
+    fn prune(
+        &mut self,
+        mut is_live: impl FnMut(&V) -> bool,
+    )
+    {
+        self.entries.retain(|_, value| is_live(value));
+        self.prune_at = (self.entries.len() * 2).max(Self::MIN_PRUNE_AT);
+    }
+
+    pub(crate) fn get(
+        &self,
+        key: &str,
+    ) -> Option<&V>
+    {
+        self.entries.get(key)
+    }
+
+    pub(crate) fn remove(
+        &mut self,
+        key: &str,
+    )
+    {
+        self.entries.remove(key);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize
+    {
+        self.entries.len()
+    }
+}
+
+/// in-memory sliding window limiter, keyed by anything that fits in a string.
+///
+/// allows `max_hits` per key within every `window`,
+/// state is per process so it resets on restart.
+pub struct Limiter
+{
+    max_hits: usize,
+    window: Duration,
+    hits: Mutex<KeyMap<VecDeque<Instant>>>,
+}
+
+impl Limiter
+{
+    #[must_use]
+    pub fn new(
+        max_hits: usize,
+        window: Duration,
+    ) -> Self
+    {
+        Self {
+            max_hits,
+            window,
+            hits: Mutex::new(KeyMap::new()),
+        }
+    }
+
+    /// registers a hit for the key.
+    ///
+    /// returns how long to wait before retrying if the key is over its limit,
+    /// rejected hits dont count towards the limit.
+    pub fn hit(
+        &self,
+        key: &str,
+    ) -> Result<(), Duration>
+    {
+        self.hit_at(key, Instant::now())
+    }
+
+    fn hit_at(
+        &self,
+        key: &str,
+        now: Instant,
+    ) -> Result<(), Duration>
+    {
+        let mut hits = self
+            .hits
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        //keys that went quiet get dropped so the map doesnt keep growing
+        let timestamps = hits.entry_or_insert_with(
+            key,
+            |timestamps| {
+                timestamps
+                    .back()
+                    .is_some_and(|last| now.duration_since(*last) < self.window)
+            },
+            VecDeque::new,
+        );
+
+        while timestamps
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= self.window)
+        {
+            timestamps.pop_front();
+        }
+
+        if timestamps.len() >= self.max_hits
+        {
+            let retry_after = timestamps.front().map_or(self.window, |first| {
+                self.window.saturating_sub(now.duration_since(*first))
+            });
+
+            return Err(retry_after);
+        }
+
+        timestamps.push_back(now);
+
+        Ok(())
+    }
+}
+
+/// in-memory lockout, a key that fails `max_failures` times within `window`
+/// gets locked for `lock_duration`.
+///
+/// state is per process so it resets on restart.
+pub struct Lockout
+{
+    max_failures: usize,
+    window: Duration,
+    lock_duration: Duration,
+    entries: Mutex<KeyMap<LockoutEntry>>,
+}
+
+#[derive(Default)]
+struct LockoutEntry
+{
+    failures: VecDeque<Instant>,
+    locked_until: Option<Instant>,
+}
+
+impl Lockout
+{
+    #[must_use]
+    pub fn new(
+        max_failures: usize,
+        window: Duration,
+        lock_duration: Duration,
+    ) -> Self
+    {
+        Self {
+            max_failures,
+            window,
+            lock_duration,
+            entries: Mutex::new(KeyMap::new()),
+        }
+    }
+
+    /// returns how long the key stays locked, if it is.
+    pub fn check(
+        &self,
+        key: &str,
+    ) -> Result<(), Duration>
+    {
+        self.check_at(key, Instant::now())
+    }
+
+    pub fn register_failure(
+        &self,
+        key: &str,
+    )
+    {
+        self.register_failure_at(key, Instant::now());
+    }
+
+    /// forgets the failures of the key, for after a success.
+    pub fn reset(
+        &self,
+        key: &str,
+    )
+    {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(key);
+    }
+
+    fn check_at(
+        &self,
+        key: &str,
+        now: Instant,
+    ) -> Result<(), Duration>
+    {
+        let entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        match entries.get(key).and_then(|entry| entry.locked_until)
+        {
+            Some(locked_until) if locked_until > now =>
+            {
+                Err(locked_until.duration_since(now))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn register_failure_at(
+        &self,
+        key: &str,
+        now: Instant,
+    )
+    {
+        let mut entries = self
+            .entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        //keys that went quiet get dropped so the map doesnt keep growing
+        let entry = entries.entry_or_insert_with(
+            key,
+            |entry| {
+                entry.locked_until.is_some_and(|until| until > now)
+                    || entry.failures.back().is_some_and(|last| {
+                        now.duration_since(*last) < self.window
+                    })
+            },
+            LockoutEntry::default,
+        );
+
+        while entry
+            .failures
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= self.window)
+        {
+            entry.failures.pop_front();
+        }
+
+        entry.failures.push_back(now);
+
+        if entry.failures.len() >= self.max_failures
+        {
+            entry.failures.clear();
+            entry.locked_until = Some(now + self.lock_duration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use crate::model::rate_limit::{
+        KeyMap,
+        Limiter,
+        Lockout,
+    };
+
+    #[test]
+    fn test_hit_over_limit_is_invalid()
+    {
+        let limiter = Limiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+
+        assert!(limiter.hit_at("key", now).is_ok());
+        assert!(limiter.hit_at("key", now).is_ok());
+        assert_eq!(
+            Err(Duration::from_secs(10)),
+            limiter.hit_at("key", now)
+        );
+        assert!(limiter
+            .hit_at(
+                "key",
+                now + Duration::from_secs(10)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_hit_per_key_is_valid()
+    {
+        let limiter = Limiter::new(1, Duration::from_secs(10));
+        let now = Instant::now();
+
+        assert!(limiter.hit_at("key", now).is_ok());
+        assert!(limiter.hit_at("other_key", now).is_ok());
+        assert!(limiter.hit_at("key", now).is_err());
+    }
+
+    #[test]
+    fn test_lockout_after_max_failures_is_invalid()
+    {
+        let lockout = Lockout::new(
+            2,
+            Duration::from_secs(10),
+            Duration::from_secs(30),
+        );
+        let now = Instant::now();
+
+        lockout.register_failure_at("key", now);
+        assert!(lockout.check_at("key", now).is_ok());
+
+        lockout.register_failure_at("key", now);
+        assert_eq!(
+            Err(Duration::from_secs(30)),
+            lockout.check_at("key", now)
+        );
+        assert!(lockout.check_at("other_key", now).is_ok());
+        assert!(lockout
+            .check_at(
+                "key",
+                now + Duration::from_secs(30)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_lockout_failures_outside_window_is_valid()
+    {
+        let lockout = Lockout::new(
+            2,
+            Duration::from_secs(10),
+            Duration::from_secs(30),
+        );
+        let now = Instant::now();
+
+        lockout.register_failure_at("key", now);
+        lockout.register_failure_at(
+            "key",
+            now + Duration::from_secs(10),
+        );
+
+        assert!(lockout
+            .check_at(
+                "key",
+                now + Duration::from_secs(10)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_lockout_reset_is_valid()
+    {
+        let lockout = Lockout::new(
+            2,
+            Duration::from_secs(10),
+            Duration::from_secs(30),
+        );
+        let now = Instant::now();
+
+        lockout.register_failure_at("key", now);
+        lockout.reset("key");
+        lockout.register_failure_at("key", now);
+
+        assert!(lockout.check_at("key", now).is_ok());
+    }
+
+    #[test]
+    fn test_key_map_prune_is_valid()
+    {
+        let mut keys = KeyMap::new();
+        let prune_at = KeyMap::<bool>::MIN_PRUNE_AT;
+
+        for key in 1..prune_at
+        {
+            keys.entry_or_insert_with(
+                &key.to_string(),
+                |live| *live,
+                || false,
+            );
+        }
+
+        //below the threshold the stale keys stay
+        keys.entry_or_insert_with("live", |live| *live, || true);
+        assert_eq!(prune_at, keys.len());
+
+        keys.entry_or_insert_with("other", |live| *live, || true);
+        assert_eq!(2, keys.len());
+        assert!(keys.get("live").is_some());
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::model::config::Source;
+use crate::model::error::{
+    self,
+    OnType,
+};
+
+use super::TokenBucket;
+
+/// `capacity` requests, refilled over `period`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota
+{
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+impl Quota
+{
+    #[must_use]
+    pub fn new(
+        capacity: u32,
+        period: Duration,
+    ) -> Self
+    {
+        Self {
+            capacity,
+            period,
+        }
+    }
+}
+
+/// parses `<requests>/<seconds>`, e.g. `30/60`.
+impl FromStr for Quota
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let (capacity, seconds) = s
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| format!("expected <requests>/<seconds>, got {s}"))?;
+
+        let capacity: u32 = capacity
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid requests in {s}"))?;
+
+        let seconds: u64 = seconds
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid seconds in {s}"))?;
+
+        if capacity == 0 || seconds == 0
+        {
+            return Err(format!(
+                "requests and seconds must be above 0, got {s}"
+            ));
+        }
+
+        Ok(Self::new(
+            capacity,
+            Duration::from_secs(seconds),
+        ))
+    }
+}
+
+/// routes sharing a quota, every route counts towards `Default` as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Group
+{
+    Default,
+    Chat,
+    Message,
+    Relation,
+    Server,
+}
+
+impl Group
+{
+    const MIN: u64 = 60;
+
+    pub const ALL: [Group; 5] = [
+        Group::Default,
+        Group::Chat,
+        Group::Message,
+        Group::Relation,
+        Group::Server,
+    ];
+
+    #[must_use]
+    pub fn env_key(&self) -> &'static str
+    {
+        match self
+        {
+            Group::Default => "RATE_LIMIT_DEFAULT",
+            Group::Chat => "RATE_LIMIT_CHAT",
+            Group::Message => "RATE_LIMIT_MESSAGE",
+            Group::Relation => "RATE_LIMIT_RELATION",
+            Group::Server => "RATE_LIMIT_SERVER",
+        }
+    }
+
+    #[must_use]
+    pub fn default_quota(&self) -> Quota
+    {
+        match self
+        {
+            Group::Default => Quota::new(
+                300,
+                Duration::from_secs(Self::MIN),
+            ),
+            Group::Chat | Group::Server => Quota::new(
+                10,
+                Duration::from_secs(Self::MIN),
+            ),
+            Group::Message => Quota::new(20, Duration::from_secs(10)),
+            Group::Relation => Quota::new(
+                20,
+                Duration::from_secs(Self::MIN),
+            ),
+        }
+    }
+}
+
+/// a token bucket per route group.
+pub struct GroupLimiter
+{
+    buckets: HashMap<Group, TokenBucket>,
+}
+
+impl GroupLimiter
+{
+    #[must_use]
+    pub fn new(quotas: &HashMap<Group, Quota>) -> Self
+    {
+        let buckets = Group::ALL
+            .iter()
+            .map(|group| {
+                let quota = quotas
+                    .get(group)
+                    .copied()
+                    .unwrap_or_else(|| group.default_quota());
+
+                (
+                    *group,
+                    TokenBucket::new(quota),
+                )
+            })
+            .collect();
+
+        Self {
+            buckets,
+        }
+    }
+
+    /// reads every `RATE_LIMIT_<GROUP>` as `<requests>/<seconds>`,
+    /// groups without one keep their default quota.
+    pub fn quotas_from_source<'err>(
+        source: &Source
+    ) -> error::Result<'err, HashMap<Group, Quota>>
+    {
+        let mut quotas = HashMap::new();
+
+        for group in Group::ALL
+        {
+            let quota = source.parse_or(
+                group.env_key(),
+                group.default_quota(),
+                OnType::RateLimit,
+            )?;
+
+            quotas.insert(group, quota);
+        }
+
+        Ok(quotas)
+    }
+
+    pub fn take(
+        &self,
+        group: Group,
+        key: &str,
+    ) -> Result<(), Duration>
+    {
+        match self.buckets.get(&group)
+        {
+            Some(bucket) => bucket.take(key),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::Duration;
+
+    use crate::model::rate_limit::Quota;
+
+    #[test]
+    fn test_quota_from_str_is_valid()
+    {
+        assert_eq!(
+            Ok(Quota::new(
+                30,
+                Duration::from_secs(45)
+            )),
+            " 30 / 45 ".parse::<Quota>()
+        );
+    }
+
+    #[test]
+    fn test_quota_from_str_is_invalid()
+    {
+        assert!("30".parse::<Quota>().is_err());
+        assert!("0/60".parse::<Quota>().is_err());
+        assert!("30/0".parse::<Quota>().is_err());
+        assert!("a/60".parse::<Quota>().is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Mutex;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use super::{
+    KeyMap,
+    Quota,
+};
+
+/// in-memory token bucket limiter, keyed by anything that fits in a string.
+///
+/// every key starts with a full bucket of `capacity` tokens,
+/// the bucket refills completely over the `period` of the quota.
+pub struct TokenBucket
+{
+    quota: Quota,
+    buckets: Mutex<KeyMap<Bucket>>,
+}
+
+struct Bucket
+{
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket
+{
+    #[must_use]
+    pub fn new(quota: Quota) -> Self
+    {
+        Self {
+            quota,
+            buckets: Mutex::new(KeyMap::new()),
+        }
+    }
+
+    /// takes a token of the key.
+    ///
+    /// returns how long to wait until a token is available if the bucket is empty.
+    pub fn take(
+        &self,
+        key: &str,
+    ) -> Result<(), Duration>
+    {
+        self.take_at(key, Instant::now())
+    }
+
+    fn take_at(
+        &self,
+        key: &str,
+        now: Instant,
+    ) -> Result<(), Duration>
+    {
+        let capacity = f64::from(self.quota.capacity);
+        let refill_per_sec = capacity / self.quota.period.as_secs_f64();
+
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        //a bucket idle for a whole period is full again, same as a new one
+        let bucket = buckets.entry_or_insert_with(
+            key,
+            |bucket| now.duration_since(bucket.updated_at) < self.quota.period,
+            || Bucket {
+                tokens: capacity,
+                updated_at: now,
+            },
+        );
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens =
+            elapsed.mul_add(refill_per_sec, bucket.tokens).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens < 1.0
+        {
+            return Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / refill_per_sec,
+            ));
+        }
+
+        bucket.tokens -= 1.0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use crate::model::rate_limit::{
+        Quota,
+        TokenBucket,
+    };
+
+    #[test]
+    fn test_take_empty_bucket_is_invalid()
+    {
+        let bucket = TokenBucket::new(Quota::new(
+            2,
+            Duration::from_secs(10),
+        ));
+        let now = Instant::now();
+
+        assert!(bucket.take_at("key", now).is_ok());
+        assert!(bucket.take_at("key", now).is_ok());
+        assert_eq!(
+            Err(Duration::from_secs(5)),
+            bucket.take_at("key", now)
+        );
+        assert!(bucket.take_at("other_key", now).is_ok());
+        assert!(bucket
+            .take_at(
+                "key",
+                now + Duration::from_secs(5)
+            )
+            .is_ok());
+        assert!(bucket
+            .take_at(
+                "key",
+                now + Duration::from_secs(5)
+            )
+            .is_err());
+    }
+}
@@ -30,16 +30,27 @@ use crate::server_error;
 
 use super::error;
 
-const REFRESH_TOKEN_TTL_IN_DAYS: i64 = 30;
+//default of `Config::refresh_token_ttl_in_days`
+pub const REFRESH_TOKEN_TTL_IN_DAYS: i64 = 30;
+//concurrent refreshes with the same token are expected (multiple tabs),
+//the ones that lose the race are refused, but within this window without burning the family
+pub const ROTATION_GRACE_SEC: i64 = 30;
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct RefreshToken
 {
     pub value: String,
     pub device_id: String,
+    //shared by every token rotated from the same login
+    pub family_id: String,
     pub ip_addr: String,
+    #[serde(default)]
+    pub user_agent: Option<String>,
     #[serde(with = "chrono_datetime_as_bson_datetime")]
     pub expiration_date: DateTime<Utc>,
+    //missing on tokens from before this was tracked, defaults to the epoch
+    #[serde(default, with = "chrono_datetime_as_bson_datetime")]
+    pub last_used: DateTime<Utc>,
     pub flag: Flag,
     pub owner: User,
 }
@@ -50,50 +61,179 @@ impl RefreshToken
     pub fn create_token(
         owner: User,
         ip_addr: String,
+        user_agent: Option<String>,
         device_id_option: Option<String>,
+        ttl: Duration,
     ) -> Self
     {
-        const CUSTOM_ENGINE: GeneralPurpose = GeneralPurpose::new(
-            &alphabet::URL_SAFE,
-            general_purpose::NO_PAD,
-        );
-
-        let mut random_number = [0u8; 64];
-
-        let mut rng = OsRng;
-        rng.fill_bytes(&mut random_number);
-
-        let refresh_token = CUSTOM_ENGINE.encode(random_number);
-
         Self {
-            value: refresh_token,
+            value: Self::internal_generate_value(),
             device_id: device_id_option.unwrap_or(Uuid::now_v7().to_string()),
+            family_id: Uuid::now_v7().to_string(),
             ip_addr,
-            expiration_date: (Utc::now()
-                + Duration::days(REFRESH_TOKEN_TTL_IN_DAYS)),
+            user_agent,
+            expiration_date: Utc::now() + ttl,
+            last_used: Utc::now(),
             flag: Flag::None,
             owner,
         }
     }
 
-    pub fn refresh_expiration<'err>(mut self) -> error::Result<'err, Self>
+    /// creates the successor of this token, with a new value and expiration.
+    ///
+    /// the caller is responsible for marking this token as [`Flag::Rotated`].
+    pub fn rotate<'err>(
+        &self,
+        ttl: Duration,
+    ) -> error::Result<'err, Self>
     {
         if !self.internal_is_valid()
         {
             return Err(server_error!(
                 error::Kind::NotAllowed,
                 error::OnType::RefreshToken
-            ));
+            )
+            .add_debug_info("flag", self.flag.to_string()));
         }
 
-        self.expiration_date =
-            Utc::now() + Duration::days(REFRESH_TOKEN_TTL_IN_DAYS);
+        Ok(Self {
+            value: Self::internal_generate_value(),
+            device_id: self.device_id.clone(),
+            family_id: self.family_id.clone(),
+            ip_addr: self.ip_addr.clone(),
+            user_agent: self.user_agent.clone(),
+            expiration_date: Utc::now() + ttl,
+            last_used: Utc::now(),
+            flag: Flag::None,
+            owner: self.owner.clone(),
+        })
+    }
 
-        Ok(self)
+    /// `true` when a rotated token is presented again outside of the grace window,
+    /// meaning someone else holds a copy of it.
+    ///
+    /// inside the window it is still refused by [`RefreshToken::rotate`].
+    #[must_use]
+    pub fn is_reused(&self) -> bool
+    {
+        matches!(self.flag, Flag::Rotated)
+            && Utc::now() - self.last_used
+                > Duration::seconds(ROTATION_GRACE_SEC)
+    }
+
+    pub fn mark_used(
+        &mut self,
+        ip_addr: String,
+        user_agent: Option<String>,
+    )
+    {
+        self.ip_addr = ip_addr;
+        self.user_agent = user_agent;
+        self.last_used = Utc::now();
     }
 
     fn internal_is_valid(&self) -> bool
     {
-        matches!(self.flag, Flag::None)
+        matches!(self.flag, Flag::None) && self.expiration_date > Utc::now()
+    }
+
+    fn internal_generate_value() -> String
+    {
+        const CUSTOM_ENGINE: GeneralPurpose = GeneralPurpose::new(
+            &alphabet::URL_SAFE,
+            general_purpose::NO_PAD,
+        );
+
+        let mut random_number = [0u8; 64];
+
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut random_number);
+
+        CUSTOM_ENGINE.encode(random_number)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::{
+        Duration,
+        Utc,
+    };
+
+    use crate::model::refresh_token::{
+        Flag,
+        RefreshToken,
+        REFRESH_TOKEN_TTL_IN_DAYS,
+        ROTATION_GRACE_SEC,
+    };
+    use crate::model::user::User;
+
+    fn internal_token() -> RefreshToken
+    {
+        let owner = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+
+        RefreshToken::create_token(
+            owner,
+            String::from("127.0.0.1"),
+            None,
+            None,
+            Duration::days(REFRESH_TOKEN_TTL_IN_DAYS),
+        )
+    }
+
+    #[test]
+    fn test_rotate_keeps_device_and_family_is_valid()
+    {
+        let token = internal_token();
+
+        let rotated = token.rotate(Duration::days(1)).unwrap();
+
+        assert_ne!(token.value, rotated.value);
+        assert_eq!(
+            token.device_id,
+            rotated.device_id
+        );
+        assert_eq!(
+            token.family_id,
+            rotated.family_id
+        );
+        assert!(matches!(
+            rotated.flag,
+            Flag::None
+        ));
+    }
+
+    #[test]
+    fn test_rotate_flagged_or_expired_is_invalid()
+    {
+        let mut token = internal_token();
+        token.flag = Flag::Rotated;
+
+        assert!(token.rotate(Duration::days(1)).is_err());
+
+        let mut token = internal_token();
+        token.expiration_date = Utc::now() - Duration::seconds(1);
+
+        assert!(token.rotate(Duration::days(1)).is_err());
+    }
+
+    #[test]
+    fn test_is_reused_rotated_after_grace_is_invalid()
+    {
+        let mut token = internal_token();
+        token.flag = Flag::Rotated;
+
+        //a concurrent refresh that lost the race isnt taken for theft
+        assert!(!token.is_reused());
+
+        token.last_used =
+            Utc::now() - Duration::seconds(ROTATION_GRACE_SEC + 1);
+
+        assert!(token.is_reused());
     }
 }
@@ -15,6 +15,10 @@ pub enum Flag
     None,
     //can add utc date
     Revoked,
+    //replaced by a newer token of the same family
+    Rotated,
+    //a rotated token got presented again, the whole family is burned
+    ReuseDetected,
 }
 
 impl Flag
@@ -25,7 +29,7 @@ impl Flag
         match &self
         {
             Self::None => false,
-            Self::Revoked => true,
+            Self::Revoked | Self::Rotated | Self::ReuseDetected => true,
         }
     }
 }
@@ -41,6 +45,8 @@ impl fmt::Display for Flag
         {
             Self::None => write!(f, "none"),
             Self::Revoked => write!(f, "revoked"),
+            Self::Rotated => write!(f, "rotated"),
+            Self::ReuseDetected => write!(f, "reuse_detected"),
         }
     }
 }
@@ -77,7 +83,8 @@ impl<'de> Deserialize<'de> for Flag
             }
         }
 
-        const FIELDS: &[&str] = &["none", "revoked"];
+        const FIELDS: &[&str] =
+            &["none", "revoked", "rotated", "reuse_detected"];
 
         deserializer.deserialize_identifier(RefreshTokenFlagVisitor)
     }
@@ -93,6 +100,8 @@ impl FromStr for Flag
         {
             "none" => Ok(Flag::None),
             "revoked" => Ok(Flag::Revoked),
+            "rotated" => Ok(Flag::Rotated),
+            "reuse_detected" => Ok(Flag::ReuseDetected),
             _ => Err(RefreshTokenFlagParseError::InvalidFormat),
         }
     }
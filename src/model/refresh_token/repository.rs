@@ -2,7 +2,10 @@ use axum::async_trait;
 
 use crate::model::error;
 
-use super::RefreshToken;
+use super::{
+    Flag,
+    RefreshToken,
+};
 
 #[async_trait]
 pub trait Repository: Send + Sync
@@ -11,7 +14,7 @@ pub trait Repository: Send + Sync
         &'input self,
         token: RefreshToken,
     ) -> error::Result<'err, RefreshToken>;
-    async fn update_expiration<'input, 'err>(
+    async fn update_token<'input, 'err>(
         &'input self,
         token: &'input RefreshToken,
     ) -> error::Result<'err, ()>;
@@ -20,6 +23,28 @@ pub trait Repository: Send + Sync
         device_id: &'input str,
         user_id: &'input str,
     ) -> error::Result<'err, RefreshToken>;
+    /// returns the token regardless of its flag or expiration.
+    async fn get_token_by_value<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        value: &'input str,
+    ) -> error::Result<'err, RefreshToken>;
+    /// flags `old` as rotated and stores `new`,
+    /// fails when `old` got rotated or revoked in the meantime.
+    async fn rotate_token<'input, 'err>(
+        &'input self,
+        old: &'input RefreshToken,
+        new: RefreshToken,
+    ) -> error::Result<'err, RefreshToken>;
+    async fn flag_token_family<'input, 'err>(
+        &'input self,
+        family_id: &'input str,
+        flag: Flag,
+    ) -> error::Result<'err, ()>;
+    async fn get_valid_tokens<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, Vec<RefreshToken>>;
     async fn revoke_token<'input, 'err>(
         &'input self,
         user_id: &'input str,
@@ -29,4 +54,8 @@ pub trait Repository: Send + Sync
         &'input self,
         user_id: &'input str,
     ) -> error::Result<'err, ()>;
+    /// valid tokens of every user, one per logged in device.
+    async fn count_valid_tokens<'input, 'err>(
+        &'input self
+    ) -> error::Result<'err, u64>;
 }
@@ -40,6 +40,10 @@ pub trait Repository: Send + Sync
         current_user_id: &'input str,
         other_user_id: &'input str,
     ) -> error::Result<'err, ()>;
+    async fn get_friend_ids<'input, 'err>(
+        &'input self,
+        current_user_id: &'input str,
+    ) -> error::Result<'err, Vec<String>>;
     async fn does_blocked_exist<'input, 'err>(
         &'input self,
         current_user_id: &'input str,
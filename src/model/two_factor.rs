@@ -0,0 +1,291 @@
+mod repository;
+
+pub use repository::*;
+
+use argon2::password_hash::rand_core::{
+    OsRng,
+    RngCore,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use data_encoding::BASE32_NOPAD;
+use reqwest::Url;
+use ring::hmac;
+
+//totp (rfc 6238) second factor of a user, only counts once `is_enabled`
+#[derive(Clone, Debug)]
+pub struct TwoFactor
+{
+    pub user_id: String,
+    //base32, needed in plain to calculate the codes
+    pub secret: String,
+    pub is_enabled: bool,
+    //argon hashes, a recovery code is removed once used
+    pub recovery_code_hashes: Vec<String>,
+    //time step of the last accepted code, a code can only be used once
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// what an accepted code used up, see `Repository::consume_two_factor_code`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UsedCode
+{
+    //time step of a totp code
+    Step(i64),
+    //argon hash of a recovery code
+    RecoveryCode(String),
+}
+
+impl TwoFactor
+{
+    pub const ISSUER: &'static str = "Mogcord";
+    pub const DIGITS: u32 = 6;
+    pub const PERIOD_SEC: i64 = 30;
+    //accepts the previous and next code too, for clocks that are a bit off
+    pub const ALLOWED_DRIFT_STEPS: i64 = 1;
+    pub const RECOVERY_CODE_COUNT: usize = 10;
+    //per user, see `AppState::two_factor_limiter`
+    pub const RATE_LIMIT_ATTEMPTS: usize = 5;
+    pub const RATE_LIMIT_WINDOW_SEC: u64 = 5 * 60;
+
+    #[must_use]
+    pub fn new(user_id: String) -> Self
+    {
+        let mut secret = [0u8; 20];
+        OsRng.fill_bytes(&mut secret);
+
+        Self {
+            user_id,
+            secret: BASE32_NOPAD.encode(&secret),
+            is_enabled: false,
+            recovery_code_hashes: Vec::new(),
+            last_used_step: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[must_use]
+    pub fn convert(
+        user_id: String,
+        secret: String,
+        is_enabled: bool,
+        recovery_code_hashes: Vec<String>,
+        last_used_step: Option<i64>,
+        created_at: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            user_id,
+            secret,
+            is_enabled,
+            recovery_code_hashes,
+            last_used_step,
+            created_at,
+        }
+    }
+}
+
+impl TwoFactor
+{
+    /// `otpauth://` uri authenticator apps read from a qr code.
+    #[must_use]
+    pub fn provisioning_uri(
+        &self,
+        account_name: &str,
+    ) -> String
+    {
+        let mut uri =
+            Url::parse("otpauth://totp/").expect("otpauth base uri is valid");
+
+        uri.set_path(&format!(
+            "{}:{account_name}",
+            Self::ISSUER
+        ));
+        uri.query_pairs_mut()
+            .append_pair("secret", &self.secret)
+            .append_pair("issuer", Self::ISSUER)
+            .append_pair("algorithm", "SHA1")
+            .append_pair(
+                "digits",
+                &Self::DIGITS.to_string(),
+            )
+            .append_pair(
+                "period",
+                &Self::PERIOD_SEC.to_string(),
+            );
+
+        uri.to_string()
+    }
+
+    /// checks the code against the steps around `now`,
+    /// remembers and returns the matched step so the same code cant be replayed.
+    pub fn verify_code(
+        &mut self,
+        code: &str,
+        now: DateTime<Utc>,
+    ) -> Option<i64>
+    {
+        let code = code.trim();
+
+        if code.len() != Self::DIGITS as usize
+            || !code.chars().all(|char| char.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let current_step = now.timestamp() / Self::PERIOD_SEC;
+
+        let matched_step = (-Self::ALLOWED_DRIFT_STEPS
+            ..=Self::ALLOWED_DRIFT_STEPS)
+            .map(|drift| current_step + drift)
+            .filter(|step| self.last_used_step.is_none_or(|last| *step > last))
+            .find(|step| {
+                self.code_at_step(*step)
+                    .is_some_and(|expected| expected == code)
+            });
+
+        if let Some(step) = matched_step
+        {
+            self.last_used_step = Some(step);
+        }
+
+        matched_step
+    }
+
+    #[must_use]
+    pub fn code_at_step(
+        &self,
+        step: i64,
+    ) -> Option<String>
+    {
+        let secret = BASE32_NOPAD.decode(self.secret.as_bytes()).ok()?;
+
+        let key = hmac::Key::new(
+            hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            &secret,
+        );
+        let tag = hmac::sign(&key, &step.to_be_bytes());
+        let digest = tag.as_ref();
+
+        //dynamic truncation, rfc 4226 section 5.3
+        let offset = usize::from(digest[digest.len() - 1] & 0x0f);
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+
+        Some(format!(
+            "{:0width$}",
+            binary % 10u32.pow(Self::DIGITS),
+            width = Self::DIGITS as usize
+        ))
+    }
+
+    /// plain codes, shown to the user once, only their hashes get stored.
+    #[must_use]
+    pub fn generate_recovery_codes() -> Vec<String>
+    {
+        (0..Self::RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let mut random_number = [0u8; 5];
+                OsRng.fill_bytes(&mut random_number);
+
+                let code = hex::encode(random_number);
+
+                format!(
+                    "{}-{}",
+                    &code[..5],
+                    &code[5..]
+                )
+            })
+            .collect()
+    }
+
+    /// recovery codes get typed over, so casing and spacing dont matter.
+    #[must_use]
+    pub fn normalize_recovery_code(code: &str) -> String
+    {
+        code.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::{
+        DateTime,
+        Utc,
+    };
+    use data_encoding::BASE32_NOPAD;
+
+    use crate::model::two_factor::TwoFactor;
+
+    //secret and codes from the sha1 test vectors of rfc 6238
+    fn internal_rfc_two_factor() -> TwoFactor
+    {
+        let mut two_factor = TwoFactor::new(String::from("user"));
+        two_factor.secret = BASE32_NOPAD.encode(b"12345678901234567890");
+
+        two_factor
+    }
+
+    #[test]
+    fn test_code_at_step_matches_rfc_is_valid()
+    {
+        let two_factor = internal_rfc_two_factor();
+
+        assert_eq!(
+            Some(String::from("287082")),
+            two_factor.code_at_step(59 / TwoFactor::PERIOD_SEC)
+        );
+        assert_eq!(
+            Some(String::from("005924")),
+            two_factor.code_at_step(1_234_567_890 / TwoFactor::PERIOD_SEC)
+        );
+    }
+
+    #[test]
+    fn test_verify_code_once_is_valid()
+    {
+        let mut two_factor = internal_rfc_two_factor();
+        let now = DateTime::<Utc>::from_timestamp(59, 0).unwrap();
+
+        assert_eq!(
+            Some(1),
+            two_factor.verify_code("287082", now)
+        );
+        assert!(two_factor.verify_code("287082", now).is_none());
+    }
+
+    #[test]
+    fn test_verify_wrong_code_is_invalid()
+    {
+        let mut two_factor = internal_rfc_two_factor();
+        let now = DateTime::<Utc>::from_timestamp(59, 0).unwrap();
+
+        assert!(two_factor.verify_code("123456", now).is_none());
+        assert!(two_factor.verify_code("28708", now).is_none());
+        assert!(two_factor.last_used_step.is_none());
+    }
+
+    #[test]
+    fn test_provisioning_uri_is_valid()
+    {
+        let two_factor = internal_rfc_two_factor();
+
+        let uri = two_factor.provisioning_uri("ElGoblino@example.com");
+
+        assert!(
+            uri.starts_with("otpauth://totp/Mogcord:ElGoblino@example.com?")
+        );
+        assert!(uri.contains(&format!(
+            "secret={}",
+            two_factor.secret
+        )));
+    }
+}
@@ -0,0 +1,39 @@
+use axum::async_trait;
+
+use crate::model::error;
+
+use super::{
+    TwoFactor,
+    UsedCode,
+};
+
+#[async_trait]
+pub trait Repository: Send + Sync
+{
+    /// replaces an enrollment that never got confirmed.
+    async fn create_two_factor<'input, 'err>(
+        &'input self,
+        two_factor: TwoFactor,
+    ) -> error::Result<'err, TwoFactor>;
+    async fn get_two_factor<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, TwoFactor>;
+    /// stores the confirmed enrollment, `AlreadyExists` when a concurrent
+    /// confirm enabled it first.
+    async fn enable_two_factor<'input, 'err>(
+        &'input self,
+        two_factor: &'input TwoFactor,
+    ) -> error::Result<'err, ()>;
+    /// marks the code as used only if it still isnt,
+    /// `NoChange` when a concurrent request used it first.
+    async fn consume_two_factor_code<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        used_code: &'input UsedCode,
+    ) -> error::Result<'err, ()>;
+    async fn delete_two_factor<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+    ) -> error::Result<'err, ()>;
+}
@@ -4,6 +4,10 @@ mod repository;
 pub use flag::*;
 pub use repository::*;
 
+use chrono::{
+    DateTime,
+    Utc,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -18,6 +22,8 @@ pub struct User
     pub email: String,
     pub hashed_password: String,
     pub flag: Flag,
+    //acces tokens issued before this are refused, set by a password reset
+    pub sessions_valid_after: Option<DateTime<Utc>>,
 }
 
 impl User
@@ -29,6 +35,7 @@ impl User
         email: String,
         hashed_password: String,
         flag: Flag,
+        sessions_valid_after: Option<DateTime<Utc>>,
     ) -> Self
     {
         Self {
@@ -37,6 +44,7 @@ impl User
             email,
             hashed_password,
             flag,
+            sessions_valid_after,
         }
     }
     #[must_use]
@@ -49,13 +57,38 @@ impl User
         Self {
             id: Uuid::now_v7().to_string(),
             username,
-            email,
+            email: Self::normalize_email(email),
             hashed_password,
             flag: Flag::None,
+            sessions_valid_after: None,
         }
     }
 }
 
+impl User
+{
+    /// the form emails are stored and looked up in,
+    /// `Foo@Example.com` from a provider has to find `foo@example.com`.
+    #[must_use]
+    pub fn normalize_email(email: impl AsRef<str>) -> String
+    {
+        email.as_ref().trim().to_lowercase()
+    }
+
+    /// `false` for a session issued before [`User::sessions_valid_after`],
+    /// `issued_at` in seconds like the `iat` claim of an acces token.
+    #[must_use]
+    pub fn is_session_valid(
+        &self,
+        issued_at: i64,
+    ) -> bool
+    {
+        //seconds, a token issued right after the cut off still counts
+        self.sessions_valid_after
+            .is_none_or(|valid_after| issued_at >= valid_after.timestamp())
+    }
+}
+
 impl std::hash::Hash for User
 {
     fn hash<H: std::hash::Hasher>(
@@ -82,6 +115,7 @@ impl Eq for User {}
 #[cfg(test)]
 mod tests
 {
+    use chrono::Utc;
     use uuid::Uuid;
 
     use crate::model::user::{
@@ -104,6 +138,7 @@ mod tests
             email.clone(),
             hashed_password.clone(),
             user_flag.clone(),
+            None,
         );
 
         assert_eq!(id, user.id);
@@ -125,17 +160,70 @@ mod tests
 
         let user: User = User::new(
             username.clone(),
-            email.clone(),
+            email,
             hashed_password.clone(),
         );
 
         assert!(Uuid::parse_str(&user.id).is_ok());
         assert_eq!(username, user.username);
-        assert_eq!(email, user.email);
+        assert_eq!(
+            "elgoblino@example.com",
+            user.email
+        );
         assert_eq!(
             hashed_password,
             user.hashed_password
         );
         assert_eq!(Flag::None, user.flag);
     }
+
+    #[test]
+    fn test_normalize_email_is_valid()
+    {
+        let user = User::new(
+            String::from("Gwilom"),
+            String::from("foo@x.com"),
+            String::from("fake_hashed_password"),
+        );
+
+        //what an oidc provider sends for the same account
+        assert_eq!(
+            user.email,
+            User::normalize_email(" Foo@X.com ")
+        );
+    }
+
+    #[test]
+    fn test_is_session_valid_is_valid()
+    {
+        let mut user = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+        let now = Utc::now();
+
+        assert!(user.is_session_valid(0));
+
+        user.sessions_valid_after = Some(now);
+
+        assert!(user.is_session_valid(now.timestamp()));
+        assert!(user.is_session_valid(now.timestamp() + 60));
+    }
+
+    #[test]
+    fn test_is_session_valid_is_invalid()
+    {
+        let mut user = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+        let now = Utc::now();
+        user.sessions_valid_after = Some(now);
+
+        assert!(!user.is_session_valid(now.timestamp() - 1));
+        //tokens without an `iat` claim
+        assert!(!user.is_session_valid(0));
+    }
 }
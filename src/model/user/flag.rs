@@ -17,6 +17,8 @@ use std::str::FromStr;
 pub enum Flag
 {
     None,
+    //registered, email not verified yet
+    Unverified,
     Disabled,
     Deleted
     {
@@ -61,6 +63,7 @@ impl fmt::Display for Flag
         match self
         {
             Self::None => write!(f, "none"),
+            Self::Unverified => write!(f, "unverified"),
             Self::Disabled => write!(f, "disabled"),
             Self::Banned {
                 date,
@@ -106,8 +109,15 @@ impl<'de> Deserialize<'de> for Flag
             }
         }
 
-        const FIELDS: &[&str] =
-            &["none", "disabled", "deleted", "banned", "admin", "owner"];
+        const FIELDS: &[&str] = &[
+            "none",
+            "unverified",
+            "disabled",
+            "deleted",
+            "banned",
+            "admin",
+            "owner",
+        ];
 
         deserializer.deserialize_identifier(UserFlagVisitor)
     }
@@ -124,6 +134,7 @@ impl FromStr for Flag
         match parts[0].to_lowercase().as_str()
         {
             "none" => Ok(Flag::None),
+            "unverified" => Ok(Flag::Unverified),
             "disabled" => Ok(Flag::Disabled),
             "deleted" =>
             {
@@ -230,6 +241,10 @@ mod tests
         test_from_str_none_variant_casing_with_lf_is_valid: ("\nnONe\n", Flag::None),
         test_from_str_none_variant_casing_with_cr_is_valid: ("\rnONe\r", Flag::None),
         test_from_str_none_variant_casing_with_crlf_is_valid: ("\r\nnONe\r\n", Flag::None),
+        test_from_str_unverified_all_lowercase_is_valid:("unverified", Flag::Unverified),
+        test_from_str_unverified_all_lowercase_with_whitespace_is_valid: (" unverified ", Flag::Unverified),
+        test_from_str_unverified_all_uppercase_is_valid:("UNVERIFIED", Flag::Unverified),
+        test_from_str_unverified_variant_casing_is_valid:("unVERified", Flag::Unverified),
         test_from_str_disabled_all_lowercase_is_valid:("disabled", Flag::Disabled),
         test_from_str_disabled_all_lowercase_with_whitespace_is_valid: (" disabled ", Flag::Disabled),
         test_from_str_disabled_all_lowercase_with_lf_is_valid: ("\ndisabled\n", Flag::Disabled),
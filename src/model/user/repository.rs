@@ -1,6 +1,13 @@
 use axum::async_trait;
+use chrono::{
+    DateTime,
+    Utc,
+};
 
-use super::User;
+use super::{
+    Flag,
+    User,
+};
 use crate::model::error;
 use crate::model::pagination::Pagination;
 
@@ -43,4 +50,19 @@ pub trait Repository: Send + Sync
         &'input self,
         pagination: Pagination,
     ) -> error::Result<'err, Vec<User>>;
+    async fn update_user_flag<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        flag: Flag,
+    ) -> error::Result<'err, ()>;
+    async fn update_user_hashed_password<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        hashed_password: String,
+    ) -> error::Result<'err, ()>;
+    async fn update_user_sessions_valid_after<'input, 'err>(
+        &'input self,
+        user_id: &'input str,
+        sessions_valid_after: DateTime<Utc>,
+    ) -> error::Result<'err, ()>;
 }
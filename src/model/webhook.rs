@@ -0,0 +1,266 @@
+mod delivery;
+mod event_type;
+mod incoming;
+mod repository;
+
+pub use delivery::*;
+pub use event_type::*;
+pub use incoming::*;
+pub use repository::*;
+
+use argon2::password_hash::rand_core::{
+    OsRng,
+    RngCore,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use uuid::Uuid;
+
+use super::event;
+use crate::model::error;
+use crate::server_error;
+
+#[derive(Clone, Debug)]
+pub struct Webhook
+{
+    pub id: String,
+    pub server_id: String,
+    pub target_url: String,
+    //empty means every channel of the server
+    pub channel_ids: Vec<String>,
+    pub event_types: Vec<EventType>,
+    //shared with the receiver, used to sign the payloads
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook
+{
+    pub fn new<'err>(
+        server_id: String,
+        target_url: &str,
+        channel_ids: Vec<String>,
+        event_types: Vec<EventType>,
+    ) -> error::Result<'err, Self>
+    {
+        let target_url = target_url.trim().to_string();
+
+        if !Self::is_valid_target_url(&target_url)
+        {
+            return Err(server_error!(
+                error::Kind::InValid,
+                error::OnType::Webhook
+            )
+            .add_client(error::Client::WEBHOOK_INVALID_URL)
+            .add_debug_info("target url", target_url));
+        }
+
+        if event_types.is_empty()
+        {
+            return Err(server_error!(
+                error::Kind::InValid,
+                error::OnType::Webhook
+            )
+            .add_client(error::Client::INVALID_PARAMS));
+        }
+
+        let mut random_number = [0u8; 32];
+        OsRng.fill_bytes(&mut random_number);
+
+        Ok(Self {
+            id: Uuid::now_v7().to_string(),
+            server_id,
+            target_url,
+            channel_ids,
+            event_types,
+            secret: hex::encode(random_number),
+            created_at: Utc::now(),
+        })
+    }
+
+    #[must_use]
+    pub fn convert(
+        id: String,
+        server_id: String,
+        target_url: String,
+        channel_ids: Vec<String>,
+        event_types: Vec<EventType>,
+        secret: String,
+        created_at: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            id,
+            server_id,
+            target_url,
+            channel_ids,
+            event_types,
+            secret,
+            created_at,
+        }
+    }
+}
+
+impl Webhook
+{
+    #[must_use]
+    pub fn is_valid_target_url(target_url: &str) -> bool
+    {
+        let Ok(url) = reqwest::Url::parse(target_url)
+        else
+        {
+            return false;
+        };
+
+        matches!(url.scheme(), "http" | "https") && url.host().is_some()
+    }
+
+    /// returns `true` if the event should be delivered to this webhook.
+    #[must_use]
+    pub fn is_subscribed_to(
+        &self,
+        event: &event::Kind,
+    ) -> bool
+    {
+        let Some(event_type) = EventType::from_event(event)
+        else
+        {
+            return false;
+        };
+
+        if event.server_id() != Some(self.server_id.as_str())
+            || !self.event_types.contains(&event_type)
+        {
+            return false;
+        }
+
+        match event.channel_id()
+        {
+            Some(channel_id) =>
+            {
+                self.channel_ids.is_empty()
+                    || self.channel_ids.iter().any(|id| id == channel_id)
+            },
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::channel::Channel;
+    use crate::model::event;
+    use crate::model::message::Message;
+    use crate::model::user::User;
+    use crate::model::webhook::{
+        EventType,
+        Webhook,
+    };
+
+    fn internal_message_event(
+        server_id: &str,
+        channel: Channel,
+    ) -> event::Kind
+    {
+        let owner = User::new(
+            String::from("Gwilom"),
+            String::from("ElGoblino@example.com"),
+            String::from("fake_hashed_password"),
+        );
+
+        event::Kind::MessageCreated {
+            server_id: Some(server_id.to_string()),
+            message: Message::new(
+                String::from("hello"),
+                owner,
+                channel,
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_new_webhook_with_invalid_url_is_invalid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "ftp://example.com",
+            Vec::new(),
+            vec![EventType::MessageCreated],
+        );
+
+        assert!(webhook.is_err());
+    }
+
+    #[test]
+    fn test_new_webhook_without_event_types_is_invalid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "https://example.com/hook",
+            Vec::new(),
+            Vec::new(),
+        );
+
+        assert!(webhook.is_err());
+    }
+
+    #[test]
+    fn test_is_subscribed_to_server_and_channel_is_valid()
+    {
+        let channel = Channel::new(Some(String::from("ci")), true);
+        let other_channel = Channel::new(
+            Some(String::from("off-topic")),
+            true,
+        );
+
+        let webhook = Webhook::new(
+            String::from("server"),
+            "https://example.com/hook",
+            vec![channel.id.clone()],
+            vec![EventType::MessageCreated],
+        )
+        .unwrap();
+
+        assert!(
+            webhook.is_subscribed_to(&internal_message_event(
+                "server",
+                channel
+            ))
+        );
+        assert!(
+            !webhook.is_subscribed_to(&internal_message_event(
+                "server",
+                other_channel.clone()
+            ))
+        );
+        assert!(
+            !webhook.is_subscribed_to(&internal_message_event(
+                "other_server",
+                other_channel
+            ))
+        );
+    }
+
+    #[test]
+    fn test_is_subscribed_to_unselected_event_type_is_invalid()
+    {
+        let webhook = Webhook::new(
+            String::from("server"),
+            "https://example.com/hook",
+            Vec::new(),
+            vec![EventType::MessageCreated],
+        )
+        .unwrap();
+
+        assert!(
+            !webhook.is_subscribed_to(&event::Kind::MemberJoined {
+                server_id: String::from("server"),
+                user_id: String::from("user"),
+            })
+        );
+    }
+}
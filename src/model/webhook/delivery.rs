@@ -0,0 +1,79 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use uuid::Uuid;
+
+use super::EventType;
+
+//one row per attempt, so retries show up individually
+#[derive(Clone, Debug)]
+pub struct Delivery
+{
+    pub id: String,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub event_type: EventType,
+    pub attempt: u32,
+    pub succeeded: bool,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Delivery
+{
+    #[must_use]
+    pub fn new(
+        webhook_id: String,
+        event_id: String,
+        event_type: EventType,
+        attempt: u32,
+        response_status: Option<u16>,
+        error: Option<String>,
+    ) -> Self
+    {
+        let succeeded = error.is_none()
+            && response_status
+                .is_some_and(|status| (200..300).contains(&status));
+
+        Self {
+            id: Uuid::now_v7().to_string(),
+            webhook_id,
+            event_id,
+            event_type,
+            attempt,
+            succeeded,
+            response_status,
+            error,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn convert(
+        id: String,
+        webhook_id: String,
+        event_id: String,
+        event_type: EventType,
+        attempt: u32,
+        succeeded: bool,
+        response_status: Option<u16>,
+        error: Option<String>,
+        timestamp: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            id,
+            webhook_id,
+            event_id,
+            event_type,
+            attempt,
+            succeeded,
+            response_status,
+            error,
+            timestamp,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::model::event;
+
+//the server scoped events a webhook can subscribe to
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType
+{
+    MessageCreated,
+    MessageUpdated,
+    MemberJoined,
+}
+
+impl EventType
+{
+    #[must_use]
+    pub fn from_event(event: &event::Kind) -> Option<Self>
+    {
+        match event
+        {
+            event::Kind::MessageCreated {
+                ..
+            } => Some(Self::MessageCreated),
+            event::Kind::MessageUpdated {
+                ..
+            } => Some(Self::MessageUpdated),
+            event::Kind::MemberJoined {
+                ..
+            } => Some(Self::MemberJoined),
+            event::Kind::FriendRequested {
+                ..
+            }
+            | event::Kind::FriendConfirmed {
+                ..
+            }
+            | event::Kind::ChatUsersAdded {
+                ..
+            } => None,
+        }
+    }
+}
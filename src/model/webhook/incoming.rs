@@ -0,0 +1,167 @@
+use argon2::password_hash::rand_core::{
+    OsRng,
+    RngCore,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use uuid::Uuid;
+
+use crate::model::error;
+use crate::model::message::Bot;
+use crate::server_error;
+
+//lets external systems post into one channel, identified by id + token
+#[derive(Clone, Debug)]
+pub struct Incoming
+{
+    pub id: String,
+    pub server_id: String,
+    pub channel_id: String,
+    pub name: String,
+    //only the hash is kept, the token is shown once on creation
+    pub token_hash: String,
+    pub creator_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Incoming
+{
+    pub const MAX_NAME_LENGTH: usize = 32;
+    //per webhook, see `AppState::incoming_webhook_limiter`
+    pub const RATE_LIMIT_MESSAGES: usize = 30;
+    pub const RATE_LIMIT_WINDOW_SEC: u64 = 60;
+
+    /// returns the webhook together with its plain token.
+    pub fn new<'err>(
+        server_id: String,
+        channel_id: String,
+        name: &str,
+        creator_id: String,
+    ) -> error::Result<'err, (Self, String)>
+    {
+        let name = name.trim();
+        let name_length = name.chars().count();
+
+        if name_length == 0 || name_length > Self::MAX_NAME_LENGTH
+        {
+            return Err(server_error!(
+                error::Kind::InValid,
+                error::OnType::Webhook
+            )
+            .add_client(error::Client::WEBHOOK_INVALID_NAME)
+            .add_debug_info(
+                "name length",
+                name_length.to_string(),
+            ));
+        }
+
+        let mut random_number = [0u8; 32];
+        OsRng.fill_bytes(&mut random_number);
+        let token = hex::encode(random_number);
+
+        let webhook = Self {
+            id: Uuid::now_v7().to_string(),
+            server_id,
+            channel_id,
+            name: name.to_string(),
+            token_hash: Self::hash_token(&token),
+            creator_id,
+            created_at: Utc::now(),
+        };
+
+        Ok((webhook, token))
+    }
+
+    #[must_use]
+    pub fn convert(
+        id: String,
+        server_id: String,
+        channel_id: String,
+        name: String,
+        token_hash: String,
+        creator_id: String,
+        created_at: DateTime<Utc>,
+    ) -> Self
+    {
+        Self {
+            id,
+            server_id,
+            channel_id,
+            name,
+            token_hash,
+            creator_id,
+            created_at,
+        }
+    }
+}
+
+impl Incoming
+{
+    //tokens are random and long, a plain sha256 is enough, no need for argon
+    #[must_use]
+    pub fn hash_token(token: &str) -> String
+    {
+        hex::encode(Sha256::digest(
+            token.as_bytes(),
+        ))
+    }
+
+    #[must_use]
+    pub fn is_token_valid(
+        &self,
+        token: &str,
+    ) -> bool
+    {
+        Self::hash_token(token) == self.token_hash
+    }
+
+    #[must_use]
+    pub fn bot(&self) -> Bot
+    {
+        Bot::new(
+            self.id.clone(),
+            self.name.clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::model::webhook::Incoming;
+
+    #[test]
+    fn test_new_incoming_is_valid()
+    {
+        let (webhook, token) = Incoming::new(
+            String::from("server"),
+            String::from("channel"),
+            "CI",
+            String::from("owner"),
+        )
+        .unwrap();
+
+        assert_ne!(token, webhook.token_hash);
+        assert!(webhook.is_token_valid(&token));
+        assert!(!webhook.is_token_valid("not_the_token"));
+    }
+
+    #[test]
+    fn test_new_incoming_with_empty_name_is_invalid()
+    {
+        let webhook = Incoming::new(
+            String::from("server"),
+            String::from("channel"),
+            "   ",
+            String::from("owner"),
+        );
+
+        assert!(webhook.is_err());
+    }
+}
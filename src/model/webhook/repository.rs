@@ -0,0 +1,61 @@
+use axum::async_trait;
+
+use crate::model::{
+    error,
+    Pagination,
+};
+
+use super::{
+    Delivery,
+    Incoming,
+    Webhook,
+};
+
+#[async_trait]
+pub trait Repository: Send + Sync
+{
+    async fn create_webhook<'input, 'err>(
+        &'input self,
+        webhook: Webhook,
+    ) -> error::Result<'err, Webhook>;
+    async fn get_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, Webhook>;
+    async fn get_webhooks_by_server<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+    ) -> error::Result<'err, Vec<Webhook>>;
+    async fn delete_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, ()>;
+    async fn create_delivery<'input, 'err>(
+        &'input self,
+        delivery: Delivery,
+    ) -> error::Result<'err, ()>;
+    async fn get_deliveries<'input, 'err>(
+        &'input self,
+        webhook_id: &'input str,
+        pagination: Pagination,
+    ) -> error::Result<'err, Vec<Delivery>>;
+    async fn create_incoming_webhook<'input, 'err>(
+        &'input self,
+        webhook: Incoming,
+    ) -> error::Result<'err, Incoming>;
+    async fn get_incoming_webhook<'input, 'err>(
+        &'input self,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, Incoming>;
+    async fn get_incoming_webhooks_by_server<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+    ) -> error::Result<'err, Vec<Incoming>>;
+    async fn delete_incoming_webhook<'input, 'err>(
+        &'input self,
+        server_id: &'input str,
+        webhook_id: &'input str,
+    ) -> error::Result<'err, ()>;
+}